@@ -17,6 +17,28 @@ fn get_src_image(width: NonZeroU32, height: NonZeroU32, pixel: u32) -> Image<'st
     Image::from_vec_u32(width, height, buffer, PixelType::U8x4).unwrap()
 }
 
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+fn multiplies_alpha_avx512(bench: &mut Bench) {
+    let width = NonZeroU32::new(4096).unwrap();
+    let height = NonZeroU32::new(2048).unwrap();
+    let src_data = get_src_image(width, height, p(255, 128, 0, 128));
+    let mut dst_data = Image::new(width, height, PixelType::U8x4);
+    let src_view = src_data.view();
+    let mut dst_view = dst_data.view_mut();
+    let mut alpha_mul_div: MulDiv = Default::default();
+    unsafe {
+        alpha_mul_div.set_cpu_extensions(CpuExtensions::Avx512);
+    }
+
+    bench.task("Multiplies alpha AVX-512", |task| {
+        task.iter(|| {
+            alpha_mul_div
+                .multiply_alpha(&src_view, &mut dst_view)
+                .unwrap();
+        })
+    });
+}
+
 #[cfg(target_arch = "x86_64")]
 fn multiplies_alpha_avx2(bench: &mut Bench) {
     let width = NonZeroU32::new(4096).unwrap();
@@ -40,7 +62,7 @@ fn multiplies_alpha_avx2(bench: &mut Bench) {
 }
 
 #[cfg(target_arch = "x86_64")]
-fn multiplies_alpha_sse2(bench: &mut Bench) {
+fn multiplies_alpha_sse4(bench: &mut Bench) {
     let width = NonZeroU32::new(4096).unwrap();
     let height = NonZeroU32::new(2048).unwrap();
     let src_data = get_src_image(width, height, p(255, 128, 0, 128));
@@ -49,10 +71,10 @@ fn multiplies_alpha_sse2(bench: &mut Bench) {
     let mut dst_view = dst_data.view_mut();
     let mut alpha_mul_div: MulDiv = Default::default();
     unsafe {
-        alpha_mul_div.set_cpu_extensions(CpuExtensions::Sse2);
+        alpha_mul_div.set_cpu_extensions(CpuExtensions::Sse4_1);
     }
 
-    bench.task("Multiplies alpha SSE2", |task| {
+    bench.task("Multiplies alpha SSE4.1", |task| {
         task.iter(|| {
             alpha_mul_div
                 .multiply_alpha(&src_view, &mut dst_view)
@@ -82,6 +104,28 @@ fn multiplies_alpha_native(bench: &mut Bench) {
     });
 }
 
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+fn divides_alpha_avx512(bench: &mut Bench) {
+    let width = NonZeroU32::new(4096).unwrap();
+    let height = NonZeroU32::new(2048).unwrap();
+    let src_data = get_src_image(width, height, p(128, 64, 0, 128));
+    let mut dst_data = Image::new(width, height, PixelType::U8x4);
+    let src_view = src_data.view();
+    let mut dst_view = dst_data.view_mut();
+    let mut alpha_mul_div: MulDiv = Default::default();
+    unsafe {
+        alpha_mul_div.set_cpu_extensions(CpuExtensions::Avx512);
+    }
+
+    bench.task("Divides alpha AVX-512", |task| {
+        task.iter(|| {
+            alpha_mul_div
+                .divide_alpha(&src_view, &mut dst_view)
+                .unwrap();
+        })
+    });
+}
+
 #[cfg(target_arch = "x86_64")]
 fn divides_alpha_avx2(bench: &mut Bench) {
     let width = NonZeroU32::new(4096).unwrap();
@@ -153,12 +197,16 @@ pub fn main() {
     let cmd = Command::read();
     if cmd.include_bench(name) {
         let mut bench = create_bench(name, "Alpha", &cmd);
+        #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+        multiplies_alpha_avx512(&mut bench);
         #[cfg(target_arch = "x86_64")]
         {
             multiplies_alpha_avx2(&mut bench);
-            multiplies_alpha_sse2(&mut bench);
+            multiplies_alpha_sse4(&mut bench);
         }
         multiplies_alpha_native(&mut bench);
+        #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+        divides_alpha_avx512(&mut bench);
         #[cfg(target_arch = "x86_64")]
         {
             divides_alpha_avx2(&mut bench);
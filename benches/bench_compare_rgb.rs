@@ -71,16 +71,15 @@ pub fn bench_downscale_rgb(bench: &mut Bench) {
     }
     for (cpu_ext, ext_name) in cpu_ext_and_name {
         for alg_name in alg_names {
-            let src_rgba_image = utils::get_big_rgba_image();
             let src_image_data = Image::from_vec_u8(
                 NonZeroU32::new(src_image.width()).unwrap(),
                 NonZeroU32::new(src_image.height()).unwrap(),
-                src_rgba_image.into_raw(),
-                PixelType::U8x4,
+                src_image.as_raw().clone(),
+                PixelType::U8x3,
             )
             .unwrap();
             let src_view = src_image_data.view();
-            let mut dst_image = Image::new(new_width, new_height, PixelType::U8x4);
+            let mut dst_image = Image::new(new_width, new_height, PixelType::U8x3);
             let mut dst_view = dst_image.view_mut();
 
             let resize_alg = match alg_name {
@@ -70,6 +70,8 @@ pub fn bench_downscale_rgba(bench: &mut Bench) {
         cpu_ext_and_name.push((CpuExtensions::Sse4_1, "sse4.1"));
         cpu_ext_and_name.push((CpuExtensions::Avx2, "avx2"));
     }
+    #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+    cpu_ext_and_name.push((CpuExtensions::Avx512, "avx512"));
     for (cpu_ext, ext_name) in cpu_ext_and_name {
         for alg_name in alg_names {
             let resize_alg = match alg_name {
@@ -0,0 +1,86 @@
+use std::arch::x86_64::*;
+
+use super::native;
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::F32x4;
+
+pub(crate) fn multiply_alpha(
+    src_image: TypedImageView<F32x4>,
+    mut dst_image: TypedImageViewMut<F32x4>,
+) {
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        unsafe {
+            multiply_alpha_row(src_row, dst_row);
+        }
+    }
+}
+
+pub(crate) fn multiply_alpha_inplace(mut image: TypedImageViewMut<F32x4>) {
+    for dst_row in image.iter_rows_mut() {
+        unsafe {
+            let src_row = std::slice::from_raw_parts(dst_row.as_ptr(), dst_row.len());
+            multiply_alpha_row(src_row, dst_row);
+        }
+    }
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn multiply_alpha_row(src_row: &[[f32; 4]], dst_row: &mut [[f32; 4]]) {
+    let src_chunks = src_row.chunks_exact(2);
+    let src_tail = src_chunks.remainder();
+    let mut dst_chunks = dst_row.chunks_exact_mut(2);
+    for (src, dst) in src_chunks.zip(&mut dst_chunks) {
+        let pixels = _mm256_loadu_ps(src.as_ptr() as *const f32);
+        let alpha = _mm256_permute_ps::<0b11_11_11_11>(pixels);
+        let res = _mm256_blend_ps::<0b1000_1000>(_mm256_mul_ps(pixels, alpha), pixels);
+        _mm256_storeu_ps(dst.as_mut_ptr() as *mut f32, res);
+    }
+
+    let dst_tail = dst_chunks.into_remainder();
+    native::multiply_alpha_row(src_tail, dst_tail);
+}
+
+pub(crate) fn divide_alpha(
+    src_image: TypedImageView<F32x4>,
+    mut dst_image: TypedImageViewMut<F32x4>,
+) {
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        unsafe {
+            divide_alpha_row(src_row, dst_row);
+        }
+    }
+}
+
+pub(crate) fn divide_alpha_inplace(mut image: TypedImageViewMut<F32x4>) {
+    for dst_row in image.iter_rows_mut() {
+        unsafe {
+            let src_row = std::slice::from_raw_parts(dst_row.as_ptr(), dst_row.len());
+            divide_alpha_row(src_row, dst_row);
+        }
+    }
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn divide_alpha_row(src_row: &[[f32; 4]], dst_row: &mut [[f32; 4]]) {
+    let zero = _mm256_setzero_ps();
+    let src_chunks = src_row.chunks_exact(2);
+    let src_tail = src_chunks.remainder();
+    let mut dst_chunks = dst_row.chunks_exact_mut(2);
+    for (src, dst) in src_chunks.zip(&mut dst_chunks) {
+        let pixels = _mm256_loadu_ps(src.as_ptr() as *const f32);
+        let alpha = _mm256_permute_ps::<0b11_11_11_11>(pixels);
+        let res = _mm256_blend_ps::<0b1000_1000>(_mm256_div_ps(pixels, alpha), pixels);
+        // Pixels with zero alpha become fully transparent black.
+        let res = _mm256_andnot_ps(_mm256_cmp_ps::<_CMP_EQ_OQ>(alpha, zero), res);
+        _mm256_storeu_ps(dst.as_mut_ptr() as *mut f32, res);
+    }
+
+    let dst_tail = dst_chunks.into_remainder();
+    native::divide_alpha_row(src_tail, dst_tail);
+}
@@ -0,0 +1,60 @@
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::F32x4;
+
+pub(crate) fn multiply_alpha(
+    src_image: TypedImageView<F32x4>,
+    mut dst_image: TypedImageViewMut<F32x4>,
+) {
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        multiply_alpha_row(src_row, dst_row);
+    }
+}
+
+pub(crate) fn multiply_alpha_inplace(mut image: TypedImageViewMut<F32x4>) {
+    for dst_row in image.iter_rows_mut() {
+        let src_row = unsafe { std::slice::from_raw_parts(dst_row.as_ptr(), dst_row.len()) };
+        multiply_alpha_row(src_row, dst_row);
+    }
+}
+
+#[inline(always)]
+pub(crate) fn multiply_alpha_row(src_row: &[[f32; 4]], dst_row: &mut [[f32; 4]]) {
+    for (&[r, g, b, alpha], dst_pixel) in src_row.iter().zip(dst_row) {
+        *dst_pixel = [r * alpha, g * alpha, b * alpha, alpha];
+    }
+}
+
+pub(crate) fn divide_alpha(
+    src_image: TypedImageView<F32x4>,
+    mut dst_image: TypedImageViewMut<F32x4>,
+) {
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        divide_alpha_row(src_row, dst_row);
+    }
+}
+
+pub(crate) fn divide_alpha_inplace(mut image: TypedImageViewMut<F32x4>) {
+    for dst_row in image.iter_rows_mut() {
+        let src_row = unsafe { std::slice::from_raw_parts(dst_row.as_ptr(), dst_row.len()) };
+        divide_alpha_row(src_row, dst_row);
+    }
+}
+
+/// Color-channels are not clipped after division,
+/// because floating point images may contain HDR values.
+#[inline(always)]
+pub(crate) fn divide_alpha_row(src_row: &[[f32; 4]], dst_row: &mut [[f32; 4]]) {
+    for (&[r, g, b, alpha], dst_pixel) in src_row.iter().zip(dst_row) {
+        *dst_pixel = if alpha == 0. {
+            [0., 0., 0., 0.]
+        } else {
+            [r / alpha, g / alpha, b / alpha, alpha]
+        };
+    }
+}
@@ -0,0 +1,73 @@
+use std::arch::x86_64::*;
+
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::F32x4;
+
+pub(crate) fn multiply_alpha(
+    src_image: TypedImageView<F32x4>,
+    mut dst_image: TypedImageViewMut<F32x4>,
+) {
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        unsafe {
+            multiply_alpha_row(src_row, dst_row);
+        }
+    }
+}
+
+pub(crate) fn multiply_alpha_inplace(mut image: TypedImageViewMut<F32x4>) {
+    for dst_row in image.iter_rows_mut() {
+        unsafe {
+            let src_row = std::slice::from_raw_parts(dst_row.as_ptr(), dst_row.len());
+            multiply_alpha_row(src_row, dst_row);
+        }
+    }
+}
+
+#[target_feature(enable = "sse4.1")]
+unsafe fn multiply_alpha_row(src_row: &[[f32; 4]], dst_row: &mut [[f32; 4]]) {
+    for (src_pixel, dst_pixel) in src_row.iter().zip(dst_row) {
+        let pixel = _mm_loadu_ps(src_pixel.as_ptr());
+        let alpha = _mm_shuffle_ps::<0b11_11_11_11>(pixel, pixel);
+        let res = _mm_blend_ps::<0b1000>(_mm_mul_ps(pixel, alpha), pixel);
+        _mm_storeu_ps(dst_pixel.as_mut_ptr(), res);
+    }
+}
+
+pub(crate) fn divide_alpha(
+    src_image: TypedImageView<F32x4>,
+    mut dst_image: TypedImageViewMut<F32x4>,
+) {
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        unsafe {
+            divide_alpha_row(src_row, dst_row);
+        }
+    }
+}
+
+pub(crate) fn divide_alpha_inplace(mut image: TypedImageViewMut<F32x4>) {
+    for dst_row in image.iter_rows_mut() {
+        unsafe {
+            let src_row = std::slice::from_raw_parts(dst_row.as_ptr(), dst_row.len());
+            divide_alpha_row(src_row, dst_row);
+        }
+    }
+}
+
+#[target_feature(enable = "sse4.1")]
+unsafe fn divide_alpha_row(src_row: &[[f32; 4]], dst_row: &mut [[f32; 4]]) {
+    let zero = _mm_setzero_ps();
+    for (src_pixel, dst_pixel) in src_row.iter().zip(dst_row) {
+        let pixel = _mm_loadu_ps(src_pixel.as_ptr());
+        let alpha = _mm_shuffle_ps::<0b11_11_11_11>(pixel, pixel);
+        let res = _mm_blend_ps::<0b1000>(_mm_div_ps(pixel, alpha), pixel);
+        // Pixels with zero alpha become fully transparent black.
+        let res = _mm_andnot_ps(_mm_cmpeq_ps(alpha, zero), res);
+        _mm_storeu_ps(dst_pixel.as_mut_ptr(), res);
+    }
+}
@@ -1,7 +0,0 @@
-pub(crate) use div::{divide_alpha_inplace_native, divide_alpha_native, divide_alpha_row_native};
-pub(crate) use mul::{
-    multiply_alpha_inplace_native, multiply_alpha_native, multiply_alpha_row_native,
-};
-
-mod div;
-mod mul;
@@ -0,0 +1,32 @@
+use super::AlphaMulDiv;
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::U16x4;
+use crate::CpuExtensions;
+
+mod native;
+
+impl AlphaMulDiv for U16x4 {
+    fn multiply_alpha(
+        src_image: TypedImageView<Self>,
+        dst_image: TypedImageViewMut<Self>,
+        _cpu_extensions: CpuExtensions,
+    ) {
+        native::multiply_alpha(src_image, dst_image);
+    }
+
+    fn multiply_alpha_inplace(image: TypedImageViewMut<Self>, _cpu_extensions: CpuExtensions) {
+        native::multiply_alpha_inplace(image);
+    }
+
+    fn divide_alpha(
+        src_image: TypedImageView<Self>,
+        dst_image: TypedImageViewMut<Self>,
+        _cpu_extensions: CpuExtensions,
+    ) {
+        native::divide_alpha(src_image, dst_image);
+    }
+
+    fn divide_alpha_inplace(image: TypedImageViewMut<Self>, _cpu_extensions: CpuExtensions) {
+        native::divide_alpha_inplace(image);
+    }
+}
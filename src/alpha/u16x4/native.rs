@@ -0,0 +1,83 @@
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::U16x4;
+
+pub(crate) fn multiply_alpha(
+    src_image: TypedImageView<U16x4>,
+    mut dst_image: TypedImageViewMut<U16x4>,
+) {
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        multiply_alpha_row(src_row, dst_row);
+    }
+}
+
+pub(crate) fn multiply_alpha_inplace(mut image: TypedImageViewMut<U16x4>) {
+    for dst_row in image.iter_rows_mut() {
+        let src_row = unsafe { std::slice::from_raw_parts(dst_row.as_ptr(), dst_row.len()) };
+        multiply_alpha_row(src_row, dst_row);
+    }
+}
+
+#[inline(always)]
+pub(crate) fn multiply_alpha_row(src_row: &[[u16; 4]], dst_row: &mut [[u16; 4]]) {
+    for (&[r, g, b, alpha], dst_pixel) in src_row.iter().zip(dst_row) {
+        *dst_pixel = [
+            mul_div_65535(r, alpha),
+            mul_div_65535(g, alpha),
+            mul_div_65535(b, alpha),
+            alpha,
+        ];
+    }
+}
+
+#[inline(always)]
+pub(crate) fn mul_div_65535(a: u16, b: u16) -> u16 {
+    let tmp = a as u32 * b as u32 + 0x8000;
+    (((tmp >> 16) + tmp) >> 16) as u16
+}
+
+pub(crate) fn divide_alpha(
+    src_image: TypedImageView<U16x4>,
+    mut dst_image: TypedImageViewMut<U16x4>,
+) {
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        divide_alpha_row(src_row, dst_row);
+    }
+}
+
+pub(crate) fn divide_alpha_inplace(mut image: TypedImageViewMut<U16x4>) {
+    for dst_row in image.iter_rows_mut() {
+        let src_row = unsafe { std::slice::from_raw_parts(dst_row.as_ptr(), dst_row.len()) };
+        divide_alpha_row(src_row, dst_row);
+    }
+}
+
+#[inline(always)]
+pub(crate) fn divide_alpha_row(src_row: &[[u16; 4]], dst_row: &mut [[u16; 4]]) {
+    for (&[r, g, b, alpha], dst_pixel) in src_row.iter().zip(dst_row) {
+        *dst_pixel = if alpha == 0 {
+            [0, 0, 0, 0]
+        } else {
+            [
+                div_and_clip(r, alpha),
+                div_and_clip(g, alpha),
+                div_and_clip(b, alpha),
+                alpha,
+            ]
+        };
+    }
+}
+
+/// Divides `v` by `alpha` with rounding and stretches result
+/// into the range of `u16`.
+#[inline(always)]
+pub(crate) fn div_and_clip(v: u16, alpha: u16) -> u16 {
+    let alpha = alpha as u32;
+    let res = (v as u32 * 0xffff + (alpha >> 1)) / alpha;
+    res.min(0xffff) as u16
+}
@@ -0,0 +1,115 @@
+use std::arch::x86_64::*;
+
+use super::native;
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::U8x2;
+use crate::simd_utils;
+
+pub(crate) fn multiply_alpha(
+    src_image: TypedImageView<U8x2>,
+    mut dst_image: TypedImageViewMut<U8x2>,
+) {
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        unsafe {
+            multiply_alpha_row(src_row, dst_row);
+        }
+    }
+}
+
+pub(crate) fn multiply_alpha_inplace(mut image: TypedImageViewMut<U8x2>) {
+    for dst_row in image.iter_rows_mut() {
+        unsafe {
+            let src_row = std::slice::from_raw_parts(dst_row.as_ptr(), dst_row.len());
+            multiply_alpha_row(src_row, dst_row);
+        }
+    }
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn multiply_alpha_row(src_row: &[[u8; 2]], dst_row: &mut [[u8; 2]]) {
+    let luma_mask = _mm256_set1_epi16(0xff);
+    let alpha_mask = _mm256_set1_epi16(0xff00u16 as i16);
+    let half = _mm256_set1_epi16(128);
+
+    let width = src_row.len();
+    let mut x: usize = 0;
+    while x < width.saturating_sub(15) {
+        let pixels = simd_utils::loadu_si256(src_row, x);
+
+        let luma = _mm256_and_si256(pixels, luma_mask);
+        let alpha = _mm256_srli_epi16::<8>(pixels);
+        // (l * a + 128 + ((l * a + 128) >> 8)) >> 8
+        let tmp = _mm256_add_epi16(_mm256_mullo_epi16(luma, alpha), half);
+        let luma = _mm256_srli_epi16::<8>(_mm256_add_epi16(tmp, _mm256_srli_epi16::<8>(tmp)));
+        let pixels = _mm256_or_si256(luma, _mm256_and_si256(pixels, alpha_mask));
+
+        let dst_ptr = dst_row.get_unchecked_mut(x..).as_mut_ptr() as *mut __m256i;
+        _mm256_storeu_si256(dst_ptr, pixels);
+
+        x += 16;
+    }
+
+    let src_tail = &src_row[x..];
+    let dst_tail = &mut dst_row[x..];
+    native::multiply_alpha_row(src_tail, dst_tail);
+}
+
+pub(crate) fn divide_alpha(
+    src_image: TypedImageView<U8x2>,
+    mut dst_image: TypedImageViewMut<U8x2>,
+) {
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        unsafe {
+            divide_alpha_row(src_row, dst_row);
+        }
+    }
+}
+
+pub(crate) fn divide_alpha_inplace(mut image: TypedImageViewMut<U8x2>) {
+    for dst_row in image.iter_rows_mut() {
+        unsafe {
+            let src_row = std::slice::from_raw_parts(dst_row.as_ptr(), dst_row.len());
+            divide_alpha_row(src_row, dst_row);
+        }
+    }
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn divide_alpha_row(src_row: &[[u8; 2]], dst_row: &mut [[u8; 2]]) {
+    let luma_mask = _mm256_set1_epi32(0xff);
+    let max_value = _mm256_set1_ps(255.);
+    let zero = _mm256_setzero_si256();
+
+    let width = src_row.len();
+    let mut x: usize = 0;
+    while x < width.saturating_sub(7) {
+        let pixels = _mm256_cvtepu16_epi32(simd_utils::loadu_si128(src_row, x));
+
+        let luma = _mm256_cvtepi32_ps(_mm256_and_si256(pixels, luma_mask));
+        let alpha_i32 = _mm256_srli_epi32::<8>(pixels);
+        let mut recip_alpha = _mm256_div_ps(max_value, _mm256_cvtepi32_ps(alpha_i32));
+        // Pixels with zero alpha get zero as reciprocal of alpha.
+        let is_zero_alpha = _mm256_castsi256_ps(_mm256_cmpeq_epi32(alpha_i32, zero));
+        recip_alpha = _mm256_andnot_ps(is_zero_alpha, recip_alpha);
+        let luma = _mm256_cvttps_epi32(_mm256_min_ps(_mm256_mul_ps(luma, recip_alpha), max_value));
+
+        let pixels = _mm256_or_si256(luma, _mm256_slli_epi32::<8>(alpha_i32));
+        let pixels = _mm256_packus_epi32(pixels, pixels);
+        let pixels = _mm256_permute4x64_epi64::<0b11_01_10_00>(pixels);
+
+        let dst_ptr = dst_row.get_unchecked_mut(x..).as_mut_ptr() as *mut __m128i;
+        _mm_storeu_si128(dst_ptr, _mm256_castsi256_si128(pixels));
+
+        x += 8;
+    }
+
+    let src_tail = &src_row[x..];
+    let dst_tail = &mut dst_row[x..];
+    native::divide_alpha_row(src_tail, dst_tail);
+}
@@ -0,0 +1,56 @@
+use crate::alpha::u8x4::native::{div_and_clip, mul_div_255};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::U8x2;
+
+pub(crate) fn multiply_alpha(
+    src_image: TypedImageView<U8x2>,
+    mut dst_image: TypedImageViewMut<U8x2>,
+) {
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        multiply_alpha_row(src_row, dst_row);
+    }
+}
+
+pub(crate) fn multiply_alpha_inplace(mut image: TypedImageViewMut<U8x2>) {
+    for dst_row in image.iter_rows_mut() {
+        let src_row = unsafe { std::slice::from_raw_parts(dst_row.as_ptr(), dst_row.len()) };
+        multiply_alpha_row(src_row, dst_row);
+    }
+}
+
+#[inline(always)]
+pub(crate) fn multiply_alpha_row(src_row: &[[u8; 2]], dst_row: &mut [[u8; 2]]) {
+    for (&[luma, alpha], dst_pixel) in src_row.iter().zip(dst_row) {
+        *dst_pixel = [mul_div_255(luma, alpha), alpha];
+    }
+}
+
+pub(crate) fn divide_alpha(
+    src_image: TypedImageView<U8x2>,
+    mut dst_image: TypedImageViewMut<U8x2>,
+) {
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        divide_alpha_row(src_row, dst_row);
+    }
+}
+
+pub(crate) fn divide_alpha_inplace(mut image: TypedImageViewMut<U8x2>) {
+    for dst_row in image.iter_rows_mut() {
+        let src_row = unsafe { std::slice::from_raw_parts(dst_row.as_ptr(), dst_row.len()) };
+        divide_alpha_row(src_row, dst_row);
+    }
+}
+
+#[inline(always)]
+pub(crate) fn divide_alpha_row(src_row: &[[u8; 2]], dst_row: &mut [[u8; 2]]) {
+    for (&[luma, alpha], dst_pixel) in src_row.iter().zip(dst_row) {
+        let recip_alpha = if alpha == 0 { 0. } else { 255. / alpha as f32 };
+        *dst_pixel = [div_and_clip(luma, recip_alpha), alpha];
+    }
+}
@@ -0,0 +1,114 @@
+use std::arch::x86_64::*;
+
+use super::native;
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::U8x2;
+use crate::simd_utils;
+
+pub(crate) fn multiply_alpha(
+    src_image: TypedImageView<U8x2>,
+    mut dst_image: TypedImageViewMut<U8x2>,
+) {
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        unsafe {
+            multiply_alpha_row(src_row, dst_row);
+        }
+    }
+}
+
+pub(crate) fn multiply_alpha_inplace(mut image: TypedImageViewMut<U8x2>) {
+    for dst_row in image.iter_rows_mut() {
+        unsafe {
+            let src_row = std::slice::from_raw_parts(dst_row.as_ptr(), dst_row.len());
+            multiply_alpha_row(src_row, dst_row);
+        }
+    }
+}
+
+#[target_feature(enable = "sse4.1")]
+unsafe fn multiply_alpha_row(src_row: &[[u8; 2]], dst_row: &mut [[u8; 2]]) {
+    let luma_mask = _mm_set1_epi16(0xff);
+    let alpha_mask = _mm_set1_epi16(0xff00u16 as i16);
+    let half = _mm_set1_epi16(128);
+
+    let width = src_row.len();
+    let mut x: usize = 0;
+    while x < width.saturating_sub(7) {
+        let pixels = simd_utils::loadu_si128(src_row, x);
+
+        let luma = _mm_and_si128(pixels, luma_mask);
+        let alpha = _mm_srli_epi16::<8>(pixels);
+        // (l * a + 128 + ((l * a + 128) >> 8)) >> 8
+        let tmp = _mm_add_epi16(_mm_mullo_epi16(luma, alpha), half);
+        let luma = _mm_srli_epi16::<8>(_mm_add_epi16(tmp, _mm_srli_epi16::<8>(tmp)));
+        let pixels = _mm_or_si128(luma, _mm_and_si128(pixels, alpha_mask));
+
+        let dst_ptr = dst_row.get_unchecked_mut(x..).as_mut_ptr() as *mut __m128i;
+        _mm_storeu_si128(dst_ptr, pixels);
+
+        x += 8;
+    }
+
+    let src_tail = &src_row[x..];
+    let dst_tail = &mut dst_row[x..];
+    native::multiply_alpha_row(src_tail, dst_tail);
+}
+
+pub(crate) fn divide_alpha(
+    src_image: TypedImageView<U8x2>,
+    mut dst_image: TypedImageViewMut<U8x2>,
+) {
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        unsafe {
+            divide_alpha_row(src_row, dst_row);
+        }
+    }
+}
+
+pub(crate) fn divide_alpha_inplace(mut image: TypedImageViewMut<U8x2>) {
+    for dst_row in image.iter_rows_mut() {
+        unsafe {
+            let src_row = std::slice::from_raw_parts(dst_row.as_ptr(), dst_row.len());
+            divide_alpha_row(src_row, dst_row);
+        }
+    }
+}
+
+#[target_feature(enable = "sse4.1")]
+unsafe fn divide_alpha_row(src_row: &[[u8; 2]], dst_row: &mut [[u8; 2]]) {
+    let luma_mask = _mm_set1_epi32(0xff);
+    let max_value = _mm_set1_ps(255.);
+    let zero = _mm_setzero_si128();
+
+    let width = src_row.len();
+    let mut x: usize = 0;
+    while x < width.saturating_sub(3) {
+        let pixels = _mm_cvtepu16_epi32(simd_utils::loadl_epi64(src_row, x));
+
+        let luma = _mm_cvtepi32_ps(_mm_and_si128(pixels, luma_mask));
+        let alpha_i32 = _mm_srli_epi32::<8>(pixels);
+        let mut recip_alpha = _mm_div_ps(max_value, _mm_cvtepi32_ps(alpha_i32));
+        // Pixels with zero alpha get zero as reciprocal of alpha.
+        let is_zero_alpha = _mm_castsi128_ps(_mm_cmpeq_epi32(alpha_i32, zero));
+        recip_alpha = _mm_andnot_ps(is_zero_alpha, recip_alpha);
+        let luma = _mm_cvttps_epi32(_mm_min_ps(_mm_mul_ps(luma, recip_alpha), max_value));
+
+        let pixels = _mm_or_si128(luma, _mm_slli_epi32::<8>(alpha_i32));
+        let pixels = _mm_packus_epi32(pixels, pixels);
+
+        let dst_ptr = dst_row.get_unchecked_mut(x..).as_mut_ptr() as *mut __m128i;
+        _mm_storel_epi64(dst_ptr, pixels);
+
+        x += 4;
+    }
+
+    let src_tail = &src_row[x..];
+    let dst_tail = &mut dst_row[x..];
+    native::divide_alpha_row(src_tail, dst_tail);
+}
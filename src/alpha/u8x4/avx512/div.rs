@@ -0,0 +1,77 @@
+use std::arch::x86_64::*;
+
+use super::pixels_mask;
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::U8x4;
+
+pub(crate) fn divide_alpha_avx512(
+    src_image: TypedImageView<U8x4>,
+    mut dst_image: TypedImageViewMut<U8x4>,
+) {
+    let width = src_image.width().get() as usize;
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        unsafe {
+            divide_alpha_row_avx512(src_row, dst_row, width);
+        }
+    }
+}
+
+pub(crate) fn divide_alpha_inplace_avx512(mut image: TypedImageViewMut<U8x4>) {
+    let width = image.width().get() as usize;
+    for dst_row in image.iter_rows_mut() {
+        unsafe {
+            let src_row = std::slice::from_raw_parts(dst_row.as_ptr(), dst_row.len());
+            divide_alpha_row_avx512(src_row, dst_row, width);
+        }
+    }
+}
+
+/// Result is equal to result of native implementation,
+/// the last pixels of row are processed with help of masked loads and stores.
+#[target_feature(enable = "avx512f,avx512bw")]
+unsafe fn divide_alpha_row_avx512(src_row: &[u32], dst_row: &mut [u32], width: usize) {
+    let zero = _mm512_setzero_si512();
+    let byte_mask = _mm512_set1_epi32(0xff);
+    let alpha_mask = _mm512_set1_epi32(0xff000000u32 as i32);
+    let max_value = _mm512_set1_ps(255.);
+
+    let mut x: usize = 0;
+    while x < width {
+        let mask = pixels_mask(width - x);
+        let src_ptr = src_row.get_unchecked(x..).as_ptr() as *const i32;
+        let source = _mm512_maskz_loadu_epi32(mask, src_ptr);
+
+        let alpha = _mm512_srli_epi32::<24>(source);
+        let not_zero_alpha = _mm512_cmpneq_epi32_mask(alpha, zero);
+        // Reciprocal of zero alpha is zero
+        let recip_alpha = _mm512_maskz_div_ps(not_zero_alpha, max_value, _mm512_cvtepi32_ps(alpha));
+
+        let r = _mm512_and_si512(source, byte_mask);
+        let g = _mm512_and_si512(_mm512_srli_epi32::<8>(source), byte_mask);
+        let b = _mm512_and_si512(_mm512_srli_epi32::<16>(source), byte_mask);
+
+        let r = div_and_clip(r, recip_alpha, max_value);
+        let g = _mm512_slli_epi32::<8>(div_and_clip(g, recip_alpha, max_value));
+        let b = _mm512_slli_epi32::<16>(div_and_clip(b, recip_alpha, max_value));
+
+        let mut result = _mm512_and_si512(source, alpha_mask);
+        result = _mm512_or_si512(result, _mm512_or_si512(r, _mm512_or_si512(g, b)));
+
+        let dst_ptr = dst_row.get_unchecked_mut(x..).as_mut_ptr() as *mut i32;
+        _mm512_mask_storeu_epi32(dst_ptr, mask, result);
+
+        x += 16;
+    }
+}
+
+/// Multiplies 32-bit values by reciprocal of alpha, clips results
+/// to 255 and truncates them - the same as `native::div_and_clip()` does.
+#[inline]
+#[target_feature(enable = "avx512f")]
+unsafe fn div_and_clip(values: __m512i, recip_alpha: __m512, max_value: __m512) -> __m512i {
+    let res = _mm512_mul_ps(_mm512_cvtepi32_ps(values), recip_alpha);
+    _mm512_cvttps_epi32(_mm512_min_ps(res, max_value))
+}
@@ -0,0 +1,16 @@
+pub(crate) use div::{divide_alpha_avx512, divide_alpha_inplace_avx512};
+pub(crate) use mul::{multiply_alpha_avx512, multiply_alpha_inplace_avx512};
+
+mod div;
+mod mul;
+
+/// Returns mask for masked loading and storing of `count` pixels
+/// (no more than 16).
+#[inline(always)]
+fn pixels_mask(count: usize) -> u16 {
+    if count >= 16 {
+        u16::MAX
+    } else {
+        (1u16 << count) - 1
+    }
+}
@@ -0,0 +1,76 @@
+use std::arch::x86_64::*;
+
+use super::pixels_mask;
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::U8x4;
+
+pub(crate) fn multiply_alpha_avx512(
+    src_image: TypedImageView<U8x4>,
+    mut dst_image: TypedImageViewMut<U8x4>,
+) {
+    let width = src_image.width().get() as usize;
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        unsafe {
+            multiply_alpha_row_avx512(src_row, dst_row, width);
+        }
+    }
+}
+
+pub(crate) fn multiply_alpha_inplace_avx512(mut image: TypedImageViewMut<U8x4>) {
+    let width = image.width().get() as usize;
+    for dst_row in image.iter_rows_mut() {
+        unsafe {
+            let src_row = std::slice::from_raw_parts(dst_row.as_ptr(), dst_row.len());
+            multiply_alpha_row_avx512(src_row, dst_row, width);
+        }
+    }
+}
+
+/// Result is equal to result of native implementation,
+/// the last pixels of row are processed with help of masked loads and stores.
+#[target_feature(enable = "avx512f,avx512bw")]
+unsafe fn multiply_alpha_row_avx512(src_row: &[u32], dst_row: &mut [u32], width: usize) {
+    let low_bytes_mask = _mm512_set1_epi16(0x00ff);
+    let half = _mm512_set1_epi16(128);
+    // Selects alpha channel (the last byte) of every pixel
+    let alpha_bytes: __mmask64 = 0x8888_8888_8888_8888;
+
+    let mut x: usize = 0;
+    while x < width {
+        let mask = pixels_mask(width - x);
+        let src_ptr = src_row.get_unchecked(x..).as_ptr() as *const i32;
+        let source = _mm512_maskz_loadu_epi32(mask, src_ptr);
+
+        // [16] a a a a ... - alpha of pixel in both 16-bit halves
+        let alpha = _mm512_srli_epi32::<24>(source);
+        let alpha = _mm512_or_si512(alpha, _mm512_slli_epi32::<16>(alpha));
+
+        // [16] b r b r ...
+        let color_even = _mm512_and_si512(source, low_bytes_mask);
+        // [16] a g a g ...
+        let color_odd = _mm512_srli_epi16::<8>(source);
+
+        let color_even = mul_div_255(color_even, alpha, half);
+        let color_odd = mul_div_255(color_odd, alpha, half);
+
+        let mut result = _mm512_or_si512(color_even, _mm512_slli_epi16::<8>(color_odd));
+        result = _mm512_mask_blend_epi8(alpha_bytes, result, source);
+
+        let dst_ptr = dst_row.get_unchecked_mut(x..).as_mut_ptr() as *mut i32;
+        _mm512_mask_storeu_epi32(dst_ptr, mask, result);
+
+        x += 16;
+    }
+}
+
+/// Calculates `(a * b + 128 + ((a * b + 128) >> 8)) >> 8` for every
+/// 16-bit value - the same as `native::mul_div_255()` does.
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw")]
+unsafe fn mul_div_255(a: __m512i, b: __m512i, half: __m512i) -> __m512i {
+    let tmp = _mm512_add_epi16(_mm512_mullo_epi16(a, b), half);
+    _mm512_srli_epi16::<8>(_mm512_add_epi16(tmp, _mm512_srli_epi16::<8>(tmp)))
+}
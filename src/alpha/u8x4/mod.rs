@@ -0,0 +1,94 @@
+use super::AlphaMulDiv;
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::U8x4;
+use crate::CpuExtensions;
+
+#[cfg(target_arch = "x86_64")]
+mod avx2;
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+mod avx512;
+pub(crate) mod native;
+#[cfg(target_arch = "aarch64")]
+mod neon;
+#[cfg(target_arch = "x86_64")]
+mod sse2;
+#[cfg(target_arch = "x86_64")]
+mod sse4;
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+mod wasm32;
+
+impl AlphaMulDiv for U8x4 {
+    fn multiply_alpha(
+        src_image: TypedImageView<Self>,
+        dst_image: TypedImageViewMut<Self>,
+        cpu_extensions: CpuExtensions,
+    ) {
+        match cpu_extensions {
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Avx2 => avx2::multiply_alpha_avx2(src_image, dst_image),
+            #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+            CpuExtensions::Avx512 => avx512::multiply_alpha_avx512(src_image, dst_image),
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Sse4_1 => sse4::multiply_alpha_sse4(src_image, dst_image),
+            #[cfg(target_arch = "aarch64")]
+            CpuExtensions::Neon => neon::multiply_alpha_neon(src_image, dst_image),
+            #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+            CpuExtensions::Simd128 => wasm32::multiply_alpha_wasm32(src_image, dst_image),
+            _ => native::multiply_alpha_native(src_image, dst_image),
+        }
+    }
+
+    fn multiply_alpha_inplace(image: TypedImageViewMut<Self>, cpu_extensions: CpuExtensions) {
+        match cpu_extensions {
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Avx2 => avx2::multiply_alpha_inplace_avx2(image),
+            #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+            CpuExtensions::Avx512 => avx512::multiply_alpha_inplace_avx512(image),
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Sse4_1 => sse4::multiply_alpha_inplace_sse4(image),
+            #[cfg(target_arch = "aarch64")]
+            CpuExtensions::Neon => neon::multiply_alpha_inplace_neon(image),
+            #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+            CpuExtensions::Simd128 => wasm32::multiply_alpha_inplace_wasm32(image),
+            _ => native::multiply_alpha_inplace_native(image),
+        }
+    }
+
+    fn divide_alpha(
+        src_image: TypedImageView<Self>,
+        dst_image: TypedImageViewMut<Self>,
+        cpu_extensions: CpuExtensions,
+    ) {
+        match cpu_extensions {
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Avx2 => avx2::divide_alpha_avx2(src_image, dst_image),
+            #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+            CpuExtensions::Avx512 => avx512::divide_alpha_avx512(src_image, dst_image),
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Sse4_1 | CpuExtensions::Sse2 => {
+                sse2::divide_alpha_sse2(src_image, dst_image)
+            }
+            #[cfg(target_arch = "aarch64")]
+            CpuExtensions::Neon => neon::divide_alpha_neon(src_image, dst_image),
+            #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+            CpuExtensions::Simd128 => wasm32::divide_alpha_wasm32(src_image, dst_image),
+            _ => native::divide_alpha_native(src_image, dst_image),
+        }
+    }
+
+    fn divide_alpha_inplace(image: TypedImageViewMut<Self>, cpu_extensions: CpuExtensions) {
+        match cpu_extensions {
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Avx2 => avx2::divide_alpha_inplace_avx2(image),
+            #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+            CpuExtensions::Avx512 => avx512::divide_alpha_inplace_avx512(image),
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Sse4_1 | CpuExtensions::Sse2 => sse2::divide_alpha_inplace_sse2(image),
+            #[cfg(target_arch = "aarch64")]
+            CpuExtensions::Neon => neon::divide_alpha_inplace_neon(image),
+            #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+            CpuExtensions::Simd128 => wasm32::divide_alpha_inplace_wasm32(image),
+            _ => native::divide_alpha_inplace_native(image),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+pub(crate) use div::{div_and_clip, divide_alpha_inplace_native, divide_alpha_native};
+pub(crate) use mul::{mul_div_255, multiply_alpha_inplace_native, multiply_alpha_native};
+#[cfg(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    all(target_arch = "wasm32", target_feature = "simd128")
+))]
+pub(crate) use {div::divide_alpha_row_native, mul::multiply_alpha_row_native};
+
+mod div;
+mod mul;
@@ -0,0 +1,85 @@
+use std::arch::aarch64::*;
+
+use crate::alpha::u8x4::native;
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::U8x4;
+
+pub(crate) fn divide_alpha_neon(
+    src_image: TypedImageView<U8x4>,
+    mut dst_image: TypedImageViewMut<U8x4>,
+) {
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        unsafe {
+            divide_alpha_row_neon(src_row, dst_row);
+        }
+    }
+}
+
+pub(crate) fn divide_alpha_inplace_neon(mut image: TypedImageViewMut<U8x4>) {
+    for dst_row in image.iter_rows_mut() {
+        unsafe {
+            let src_row = std::slice::from_raw_parts(dst_row.as_ptr(), dst_row.len());
+            divide_alpha_row_neon(src_row, dst_row);
+        }
+    }
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn divide_alpha_row_neon(src_row: &[u32], dst_row: &mut [u32]) {
+    let src_chunks = src_row.chunks_exact(8);
+    let src_tail = src_chunks.remainder();
+    let mut dst_chunks = dst_row.chunks_exact_mut(8);
+
+    for (src, dst) in src_chunks.zip(&mut dst_chunks) {
+        // Deinterleave components of 8 pixels into 4 registers
+        let mut pixels = vld4_u8(src.as_ptr() as *const u8);
+
+        let (alpha_lo, alpha_hi) = u8_to_f32(pixels.3);
+        let recip_alpha_lo = recip_alpha_neon(alpha_lo);
+        let recip_alpha_hi = recip_alpha_neon(alpha_hi);
+
+        pixels.0 = div_and_clip_neon(pixels.0, recip_alpha_lo, recip_alpha_hi);
+        pixels.1 = div_and_clip_neon(pixels.1, recip_alpha_lo, recip_alpha_hi);
+        pixels.2 = div_and_clip_neon(pixels.2, recip_alpha_lo, recip_alpha_hi);
+        vst4_u8(dst.as_mut_ptr() as *mut u8, pixels);
+    }
+
+    native::divide_alpha_row_native(src_tail, dst_chunks.into_remainder());
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn u8_to_f32(v: uint8x8_t) -> (float32x4_t, float32x4_t) {
+    let v = vmovl_u8(v);
+    (
+        vcvtq_f32_u32(vmovl_u16(vget_low_u16(v))),
+        vcvtq_f32_u32(vmovl_u16(vget_high_u16(v))),
+    )
+}
+
+/// Returns `255 / alpha` or zero if alpha is zero.
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn recip_alpha_neon(alpha: float32x4_t) -> float32x4_t {
+    let recip = vdivq_f32(vdupq_n_f32(255.), alpha);
+    let zero_mask = vceqq_f32(alpha, vdupq_n_f32(0.));
+    vreinterpretq_f32_u32(vbicq_u32(vreinterpretq_u32_f32(recip), zero_mask))
+}
+
+/// Vectorized version of `native::div_and_clip()`.
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn div_and_clip_neon(
+    v: uint8x8_t,
+    recip_alpha_lo: float32x4_t,
+    recip_alpha_hi: float32x4_t,
+) -> uint8x8_t {
+    let max = vdupq_n_f32(255.);
+    let (lo, hi) = u8_to_f32(v);
+    let lo = vcvtq_u32_f32(vminq_f32(vmulq_f32(lo, recip_alpha_lo), max));
+    let hi = vcvtq_u32_f32(vminq_f32(vmulq_f32(hi, recip_alpha_hi), max));
+    vmovn_u16(vcombine_u16(vmovn_u32(lo), vmovn_u32(hi)))
+}
@@ -0,0 +1,5 @@
+pub(crate) use div::{divide_alpha_inplace_neon, divide_alpha_neon};
+pub(crate) use mul::{multiply_alpha_inplace_neon, multiply_alpha_neon};
+
+mod div;
+mod mul;
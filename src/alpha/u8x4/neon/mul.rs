@@ -0,0 +1,59 @@
+use std::arch::aarch64::*;
+
+use crate::alpha::u8x4::native;
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::U8x4;
+
+pub(crate) fn multiply_alpha_neon(
+    src_image: TypedImageView<U8x4>,
+    mut dst_image: TypedImageViewMut<U8x4>,
+) {
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        unsafe {
+            multiply_alpha_row_neon(src_row, dst_row);
+        }
+    }
+}
+
+pub(crate) fn multiply_alpha_inplace_neon(mut image: TypedImageViewMut<U8x4>) {
+    for dst_row in image.iter_rows_mut() {
+        unsafe {
+            let src_row = std::slice::from_raw_parts(dst_row.as_ptr(), dst_row.len());
+            multiply_alpha_row_neon(src_row, dst_row);
+        }
+    }
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn multiply_alpha_row_neon(src_row: &[u32], dst_row: &mut [u32]) {
+    let src_chunks = src_row.chunks_exact(16);
+    let src_tail = src_chunks.remainder();
+    let mut dst_chunks = dst_row.chunks_exact_mut(16);
+
+    for (src, dst) in src_chunks.zip(&mut dst_chunks) {
+        // Deinterleave components of 16 pixels into 4 registers
+        let mut pixels = vld4q_u8(src.as_ptr() as *const u8);
+        pixels.0 = mul_div_255_neon(pixels.0, pixels.3);
+        pixels.1 = mul_div_255_neon(pixels.1, pixels.3);
+        pixels.2 = mul_div_255_neon(pixels.2, pixels.3);
+        vst4q_u8(dst.as_mut_ptr() as *mut u8, pixels);
+    }
+
+    native::multiply_alpha_row_native(src_tail, dst_chunks.into_remainder());
+}
+
+/// Vectorized version of `native::mul_div_255()`.
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn mul_div_255_neon(a: uint8x16_t, b: uint8x16_t) -> uint8x16_t {
+    let half = vdupq_n_u16(128);
+    let lo = vaddq_u16(vmull_u8(vget_low_u8(a), vget_low_u8(b)), half);
+    let hi = vaddq_u16(vmull_u8(vget_high_u8(a), vget_high_u8(b)), half);
+    // ((tmp >> 8) + tmp) >> 8
+    let lo = vshrn_n_u16::<8>(vsraq_n_u16::<8>(lo, lo));
+    let hi = vshrn_n_u16::<8>(vsraq_n_u16::<8>(hi, hi));
+    vcombine_u8(lo, hi)
+}
@@ -1,6 +1,6 @@
 use std::arch::x86_64::*;
 
-use crate::alpha::native;
+use crate::alpha::u8x4::native;
 use crate::image_view::{TypedImageView, TypedImageViewMut};
 use crate::pixels::U8x4;
 use crate::simd_utils;
@@ -1,4 +1,3 @@
 pub(crate) use div::{divide_alpha_inplace_sse2, divide_alpha_sse2};
 
 mod div;
-mod mul;
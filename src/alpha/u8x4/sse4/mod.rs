@@ -0,0 +1,3 @@
+pub(crate) use mul::{multiply_alpha_inplace_sse4, multiply_alpha_sse4};
+
+mod mul;
@@ -0,0 +1,72 @@
+use std::arch::x86_64::*;
+
+use crate::alpha::u8x4::native;
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::U8x4;
+use crate::simd_utils;
+
+pub(crate) fn multiply_alpha_sse4(
+    src_image: TypedImageView<U8x4>,
+    mut dst_image: TypedImageViewMut<U8x4>,
+) {
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        unsafe {
+            multiply_alpha_row_sse4(src_row, dst_row);
+        }
+    }
+}
+
+pub(crate) fn multiply_alpha_inplace_sse4(mut image: TypedImageViewMut<U8x4>) {
+    for dst_row in image.iter_rows_mut() {
+        unsafe {
+            let src_row = std::slice::from_raw_parts(dst_row.as_ptr(), dst_row.len());
+            multiply_alpha_row_sse4(src_row, dst_row);
+        }
+    }
+}
+
+/// Result is equal to result of native implementation.
+#[target_feature(enable = "sse4.1")]
+unsafe fn multiply_alpha_row_sse4(src_row: &[u32], dst_row: &mut [u32]) {
+    let low_bytes_mask = _mm_set1_epi16(0xff);
+    let alpha_mask = _mm_set1_epi32(0xff000000u32 as i32);
+    let half = _mm_set1_epi16(128);
+    // [16] a3 a3 a2 a2 a1 a1 a0 a0
+    let shuffle_alpha = _mm_set_epi8(-1, 15, -1, 15, -1, 11, -1, 11, -1, 7, -1, 7, -1, 3, -1, 3);
+
+    let width = src_row.len();
+    let mut x: usize = 0;
+    while x < width.saturating_sub(3) {
+        let pixels = simd_utils::loadu_si128(src_row, x);
+        let alpha = _mm_shuffle_epi8(pixels, shuffle_alpha);
+
+        // [16] b3 r3 b2 r2 b1 r1 b0 r0
+        let color_even = mul_div_255_sse4(_mm_and_si128(pixels, low_bytes_mask), alpha, half);
+        // [16] a3 g3 a2 g2 a1 g1 a0 g0
+        let color_odd = mul_div_255_sse4(_mm_srli_epi16::<8>(pixels), alpha, half);
+
+        let result = _mm_or_si128(color_even, _mm_slli_epi16::<8>(color_odd));
+        let result = _mm_blendv_epi8(result, pixels, alpha_mask);
+
+        let dst_ptr = dst_row.get_unchecked_mut(x..).as_mut_ptr() as *mut __m128i;
+        _mm_storeu_si128(dst_ptr, result);
+
+        x += 4;
+    }
+
+    let src_tail = &src_row[x..];
+    let dst_tail = &mut dst_row[x..];
+    native::multiply_alpha_row_native(src_tail, dst_tail);
+}
+
+/// Calculates `(a * b + 128 + ((a * b + 128) >> 8)) >> 8` for every
+/// 16-bit value - the same as `native::mul_div_255()` does.
+#[inline]
+#[target_feature(enable = "sse4.1")]
+unsafe fn mul_div_255_sse4(a: __m128i, b: __m128i, half: __m128i) -> __m128i {
+    let tmp = _mm_add_epi16(_mm_mullo_epi16(a, b), half);
+    _mm_srli_epi16::<8>(_mm_add_epi16(tmp, _mm_srli_epi16::<8>(tmp)))
+}
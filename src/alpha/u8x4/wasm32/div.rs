@@ -0,0 +1,72 @@
+use std::arch::wasm32::*;
+
+use crate::alpha::u8x4::native;
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::U8x4;
+
+pub(crate) fn divide_alpha_wasm32(
+    src_image: TypedImageView<U8x4>,
+    mut dst_image: TypedImageViewMut<U8x4>,
+) {
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        unsafe {
+            divide_alpha_row_wasm32(src_row, dst_row);
+        }
+    }
+}
+
+pub(crate) fn divide_alpha_inplace_wasm32(mut image: TypedImageViewMut<U8x4>) {
+    for dst_row in image.iter_rows_mut() {
+        unsafe {
+            let src_row = std::slice::from_raw_parts(dst_row.as_ptr(), dst_row.len());
+            divide_alpha_row_wasm32(src_row, dst_row);
+        }
+    }
+}
+
+#[target_feature(enable = "simd128")]
+unsafe fn divide_alpha_row_wasm32(src_row: &[u32], dst_row: &mut [u32]) {
+    let zero = u32x4_splat(0);
+    let byte_mask = u32x4_splat(0xff);
+    let alpha_mask = u32x4_splat(0xff000000);
+    let max_value = f32x4_splat(255.);
+
+    let src_chunks = src_row.chunks_exact(4);
+    let src_tail = src_chunks.remainder();
+    let mut dst_chunks = dst_row.chunks_exact_mut(4);
+
+    for (src, dst) in src_chunks.zip(&mut dst_chunks) {
+        let source = v128_load(src.as_ptr() as *const v128);
+
+        let alpha = u32x4_shr(source, 24);
+        let recip_alpha = f32x4_div(max_value, f32x4_convert_u32x4(alpha));
+        // Reciprocal of zero alpha is zero
+        let recip_alpha = v128_and(recip_alpha, i32x4_ne(alpha, zero));
+
+        let r = v128_and(source, byte_mask);
+        let g = v128_and(u32x4_shr(source, 8), byte_mask);
+        let b = v128_and(u32x4_shr(source, 16), byte_mask);
+
+        let r = div_and_clip_wasm32(r, recip_alpha, max_value);
+        let g = i32x4_shl(div_and_clip_wasm32(g, recip_alpha, max_value), 8);
+        let b = i32x4_shl(div_and_clip_wasm32(b, recip_alpha, max_value), 16);
+
+        let result = v128_or(v128_and(source, alpha_mask), v128_or(r, v128_or(g, b)));
+        v128_store(dst.as_mut_ptr() as *mut v128, result);
+    }
+
+    let dst_tail = dst_chunks.into_remainder();
+    native::divide_alpha_row_native(src_tail, dst_tail);
+}
+
+/// Multiplies 32-bit values by reciprocal of alpha, clips results
+/// to 255 and truncates them - the same as `native::div_and_clip()` does.
+#[inline]
+#[target_feature(enable = "simd128")]
+unsafe fn div_and_clip_wasm32(values: v128, recip_alpha: v128, max_value: v128) -> v128 {
+    let res = f32x4_mul(f32x4_convert_u32x4(values), recip_alpha);
+    u32x4_trunc_sat_f32x4(f32x4_min(res, max_value))
+}
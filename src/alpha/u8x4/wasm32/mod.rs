@@ -0,0 +1,5 @@
+pub(crate) use div::{divide_alpha_inplace_wasm32, divide_alpha_wasm32};
+pub(crate) use mul::{multiply_alpha_inplace_wasm32, multiply_alpha_wasm32};
+
+mod div;
+mod mul;
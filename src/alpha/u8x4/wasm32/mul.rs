@@ -0,0 +1,68 @@
+use std::arch::wasm32::*;
+
+use crate::alpha::u8x4::native;
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::U8x4;
+
+pub(crate) fn multiply_alpha_wasm32(
+    src_image: TypedImageView<U8x4>,
+    mut dst_image: TypedImageViewMut<U8x4>,
+) {
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        unsafe {
+            multiply_alpha_row_wasm32(src_row, dst_row);
+        }
+    }
+}
+
+pub(crate) fn multiply_alpha_inplace_wasm32(mut image: TypedImageViewMut<U8x4>) {
+    for dst_row in image.iter_rows_mut() {
+        unsafe {
+            let src_row = std::slice::from_raw_parts(dst_row.as_ptr(), dst_row.len());
+            multiply_alpha_row_wasm32(src_row, dst_row);
+        }
+    }
+}
+
+#[target_feature(enable = "simd128")]
+unsafe fn multiply_alpha_row_wasm32(src_row: &[u32], dst_row: &mut [u32]) {
+    let low_bytes_mask = u16x8_splat(0x00ff);
+    let alpha_mask = u32x4_splat(0xff000000);
+    let half = u16x8_splat(128);
+
+    let src_chunks = src_row.chunks_exact(4);
+    let src_tail = src_chunks.remainder();
+    let mut dst_chunks = dst_row.chunks_exact_mut(4);
+
+    for (src, dst) in src_chunks.zip(&mut dst_chunks) {
+        let source = v128_load(src.as_ptr() as *const v128);
+
+        // [16] a a a a a a a a - alpha of pixel in both 16-bit halves
+        let alpha = u32x4_shr(source, 24);
+        let alpha = v128_or(alpha, i32x4_shl(alpha, 16));
+
+        // [16] b r b r b r b r
+        let color_even = mul_div_255_wasm32(v128_and(source, low_bytes_mask), alpha, half);
+        // [16] a g a g a g a g
+        let color_odd = mul_div_255_wasm32(u16x8_shr(source, 8), alpha, half);
+
+        let result = v128_or(color_even, i16x8_shl(color_odd, 8));
+        let result = v128_bitselect(source, result, alpha_mask);
+        v128_store(dst.as_mut_ptr() as *mut v128, result);
+    }
+
+    let dst_tail = dst_chunks.into_remainder();
+    native::multiply_alpha_row_native(src_tail, dst_tail);
+}
+
+/// Calculates `(a * b + 128 + ((a * b + 128) >> 8)) >> 8` for every
+/// 16-bit value - the same as `native::mul_div_255()` does.
+#[inline]
+#[target_feature(enable = "simd128")]
+unsafe fn mul_div_255_wasm32(a: v128, b: v128, half: v128) -> v128 {
+    let tmp = i16x8_add(i16x8_mul(a, b), half);
+    u16x8_shr(i16x8_add(tmp, u16x8_shr(tmp, 8)), 8)
+}
@@ -0,0 +1,149 @@
+use std::arch::x86_64::*;
+
+use super::tables::{Component, Lut};
+use super::ColorPixel;
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+
+/// Components which are converted by eight at once.
+pub(crate) trait Avx2Component: Component {
+    /// Converts the first eight components of `src` into linear light.
+    unsafe fn to_linear_x8(src: &[Self], lut: &Lut) -> __m256;
+
+    /// Converts eight values in linear light into the first eight
+    /// components of `dst`.
+    unsafe fn from_linear_x8(values: __m256, dst: &mut [Self], lut: &Lut);
+}
+
+impl Avx2Component for u8 {
+    #[inline(always)]
+    unsafe fn to_linear_x8(src: &[Self], lut: &Lut) -> __m256 {
+        let components = _mm_loadl_epi64(src.as_ptr() as *const __m128i);
+        let indexes = _mm256_cvtepu8_epi32(components);
+        _mm256_i32gather_ps::<4>(lut.table().as_ptr(), indexes)
+    }
+
+    #[inline(always)]
+    unsafe fn from_linear_x8(values: __m256, dst: &mut [Self], lut: &Lut) {
+        let components = search_x8(values, lut.table());
+        // Components are less than 256, so they are not saturated.
+        let components = _mm256_packus_epi32(components, components);
+        let components = _mm256_packus_epi16(components, components);
+        let components =
+            _mm256_permutevar8x32_epi32(components, _mm256_set_epi32(0, 0, 0, 0, 0, 0, 4, 0));
+        _mm_storel_epi64(
+            dst.as_mut_ptr() as *mut __m128i,
+            _mm256_castsi256_si128(components),
+        );
+    }
+}
+
+impl Avx2Component for u16 {
+    #[inline(always)]
+    unsafe fn to_linear_x8(src: &[Self], lut: &Lut) -> __m256 {
+        let components = _mm_loadu_si128(src.as_ptr() as *const __m128i);
+        let indexes = _mm256_cvtepu16_epi32(components);
+        _mm256_i32gather_ps::<4>(lut.table().as_ptr(), indexes)
+    }
+
+    #[inline(always)]
+    unsafe fn from_linear_x8(values: __m256, dst: &mut [Self], lut: &Lut) {
+        let components = search_x8(values, lut.table());
+        // Components are less than 65536, so they are not saturated.
+        let components = _mm256_packus_epi32(components, components);
+        let components = _mm256_permute4x64_epi64::<0b1000>(components);
+        _mm_storeu_si128(
+            dst.as_mut_ptr() as *mut __m128i,
+            _mm256_castsi256_si128(components),
+        );
+    }
+}
+
+/// Floating point components are converted by transfer function
+/// for every component.
+impl Avx2Component for f32 {
+    #[inline(always)]
+    unsafe fn to_linear_x8(src: &[Self], lut: &Lut) -> __m256 {
+        let mut values = [0f32; 8];
+        for (&src, value) in src.iter().zip(values.iter_mut()) {
+            *value = src.to_linear(lut);
+        }
+        _mm256_loadu_ps(values.as_ptr())
+    }
+
+    #[inline(always)]
+    unsafe fn from_linear_x8(values: __m256, dst: &mut [Self], lut: &Lut) {
+        let mut linear = [0f32; 8];
+        _mm256_storeu_ps(linear.as_mut_ptr(), values);
+        for (&value, dst) in linear.iter().zip(dst.iter_mut()) {
+            *dst = f32::from_linear(value, lut);
+        }
+    }
+}
+
+/// Returns count of items of `table` that are less than value
+/// for every of eight values. Size of `table` must be a power of two
+/// and its last item must be greater than any value.
+#[inline(always)]
+unsafe fn search_x8(values: __m256, table: &[f32]) -> __m256i {
+    debug_assert!(table.len().is_power_of_two());
+    let mut positions = _mm256_setzero_si256();
+    let mut step = table.len() / 2;
+    while step > 0 {
+        let step_v = _mm256_set1_epi32(step as i32);
+        let indexes = _mm256_add_epi32(positions, _mm256_set1_epi32(step as i32 - 1));
+        let items = _mm256_i32gather_ps::<4>(table.as_ptr(), indexes);
+        let less = _mm256_castps_si256(_mm256_cmp_ps::<_CMP_LT_OQ>(items, values));
+        positions = _mm256_add_epi32(positions, _mm256_and_si256(less, step_v));
+        step /= 2;
+    }
+    positions
+}
+
+pub(crate) fn convert<S: ColorPixel, D: ColorPixel>(
+    src_image: TypedImageView<S>,
+    mut dst_image: TypedImageViewMut<D>,
+    decoder: &Lut,
+    encoder: &Lut,
+) {
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        // Safety: pixels are arrays of components (`U8x4` pixel is stored
+        // as `u32`, but in memory it is an array of four `u8`).
+        let src_components = unsafe { src_row.align_to::<S::Component>().1 };
+        let dst_components = unsafe { dst_row.align_to_mut::<D::Component>().1 };
+        unsafe {
+            convert_row::<S, D>(src_components, dst_components, decoder, encoder);
+        }
+    }
+}
+
+/// All components are converted as color-channels, after that
+/// alpha-channel is overwritten by its stretched source value.
+#[target_feature(enable = "avx2")]
+unsafe fn convert_row<S: ColorPixel, D: ColorPixel>(
+    src_row: &[S::Component],
+    dst_row: &mut [D::Component],
+    decoder: &Lut,
+    encoder: &Lut,
+) {
+    let mut src_chunks = src_row.chunks_exact(8);
+    let mut dst_chunks = dst_row.chunks_exact_mut(8);
+    for (src, dst) in (&mut src_chunks).zip(&mut dst_chunks) {
+        let values = S::Component::to_linear_x8(src, decoder);
+        D::Component::from_linear_x8(values, dst, encoder);
+    }
+    let src_tail = src_chunks.remainder();
+    for (&src, dst) in src_tail.iter().zip(dst_chunks.into_remainder()) {
+        *dst = D::Component::from_linear(src.to_linear(decoder), encoder);
+    }
+
+    if S::HAS_ALPHA {
+        let alpha = S::COUNT - 1;
+        let src_pixels = src_row.chunks_exact(S::COUNT);
+        let dst_pixels = dst_row.chunks_exact_mut(D::COUNT);
+        for (src_pixel, dst_pixel) in src_pixels.zip(dst_pixels) {
+            dst_pixel[alpha] = D::Component::from_unit(src_pixel[alpha].to_unit());
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum GammaConvertError {
+    #[error("Size of source image does not match to destination image")]
+    SizeIsDifferent,
+    #[error("Count of channels of source image does not match to destination image")]
+    CountOfChannelsIsDifferent,
+    #[error("Pixel type of image is not supported")]
+    UnsupportedPixelType,
+}
@@ -0,0 +1,263 @@
+//! Conversion of color-channels of images between transfer functions
+//! (sRGB, gamma curves and linear light).
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::{F32x3, F32x4, Pixel, U16x3, U16x4, U8x2, U8x3, U8x4, F32, U16, U8};
+use crate::{CpuExtensions, ImageView, ImageViewMut, PixelType};
+pub use errors::*;
+pub(crate) use srgb_luts::SrgbLuts;
+use tables::{Component, Decoder, Encoder, Lut};
+
+#[cfg(target_arch = "x86_64")]
+mod avx2;
+mod errors;
+mod native;
+mod srgb_luts;
+mod tables;
+
+/// Transfer function used to encode values of color-channels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum TransferFunction {
+    /// Values are proportional to intensity of light.
+    Linear,
+    /// Transfer function from the sRGB specification (IEC 61966-2-1).
+    Srgb,
+    /// Pure power function with given exponent, e.g. `Gamma(2.2)`.
+    /// Linear value is calculated as `encoded.powf(gamma)`.
+    Gamma(f32),
+}
+
+impl TransferFunction {
+    /// Converts encoded value (`1.0` is maximal value of component)
+    /// into linear light.
+    pub fn to_linear(self, v: f32) -> f32 {
+        match self {
+            Self::Linear => v,
+            Self::Srgb => {
+                if v <= 0.04045 {
+                    v / 12.92
+                } else {
+                    ((v + 0.055) / 1.055).powf(2.4)
+                }
+            }
+            Self::Gamma(gamma) => v.abs().powf(gamma).copysign(v),
+        }
+    }
+
+    /// Converts value in linear light into encoded value
+    /// (`1.0` is maximal value of component).
+    pub fn from_linear(self, v: f32) -> f32 {
+        match self {
+            Self::Linear => v,
+            Self::Srgb => {
+                if v <= 0.0031308 {
+                    v * 12.92
+                } else {
+                    1.055 * v.powf(1. / 2.4) - 0.055
+                }
+            }
+            Self::Gamma(gamma) => v.abs().powf(1. / gamma).copysign(v),
+        }
+    }
+}
+
+/// Types of pixels supported by [GammaConverter].
+pub(crate) trait ColorPixel: Pixel {
+    #[cfg(target_arch = "x86_64")]
+    type Component: avx2::Avx2Component;
+    #[cfg(not(target_arch = "x86_64"))]
+    type Component: Component;
+    /// Count of components in pixel.
+    const COUNT: usize;
+    /// The last component of pixel is alpha-channel.
+    const HAS_ALPHA: bool;
+}
+
+macro_rules! color_pixel {
+    ($name:ident, $component:ty, $count:expr, $has_alpha:expr) => {
+        impl ColorPixel for $name {
+            type Component = $component;
+            const COUNT: usize = $count;
+            const HAS_ALPHA: bool = $has_alpha;
+        }
+    };
+}
+
+color_pixel!(U8, u8, 1, false);
+color_pixel!(U8x2, u8, 2, true);
+color_pixel!(U8x3, u8, 3, false);
+color_pixel!(U8x4, u8, 4, true);
+color_pixel!(U16, u16, 1, false);
+color_pixel!(U16x3, u16, 3, false);
+color_pixel!(U16x4, u16, 4, true);
+color_pixel!(F32, f32, 1, false);
+color_pixel!(F32x3, f32, 3, false);
+color_pixel!(F32x4, f32, 4, true);
+
+/// Methods of this structure used to convert color-channels of images
+/// from one transfer function into another. Types of pixels of source
+/// and destination images may be different, but they must have the same
+/// count of channels (e.g. `U8x4` and `F32x4`).
+///
+/// Alpha-channel is not encoded by transfer functions, so it is only
+/// stretched into the range of destination type of components.
+///
+/// Integer components are converted with help of lookup tables which
+/// are built at the first conversion of components of corresponding type
+/// and reused by subsequent conversions. Conversion of 8-bit sRGB values
+/// into 16-bit or floating point values in linear light and back
+/// returns original values. For pure gamma curves it is true only for
+/// floating point values, because 16 bits are not enough to store
+/// the darkest values of such curves in linear light.
+///
+/// Supported pixel types: all except `I32`.
+///
+/// By default, instance of `GammaConverter` created with best CPU-extensions
+/// provided by your CPU. You can change this by use method
+/// [GammaConverter::set_cpu_extensions]. Conversion of integer components
+/// has optimisations for AVX2.
+///
+/// # Examples
+///
+/// ```
+/// use std::num::NonZeroU32;
+/// use fast_image_resize::color::{GammaConverter, TransferFunction};
+/// use fast_image_resize::{Image, PixelType};
+///
+/// let width = NonZeroU32::new(10).unwrap();
+/// let height = NonZeroU32::new(7).unwrap();
+/// let src_image = Image::new(width, height, PixelType::U8x4);
+/// let mut dst_image = Image::new(width, height, PixelType::F32x4);
+///
+/// let converter = GammaConverter::new(TransferFunction::Srgb, TransferFunction::Linear);
+/// converter.convert(&src_image.view(), &mut dst_image.view_mut()).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct GammaConverter {
+    decoder: Decoder,
+    encoder: Encoder,
+    cpu_extensions: CpuExtensions,
+}
+
+impl GammaConverter {
+    /// Creates converter of images with color-channels encoded
+    /// by `src_transfer` into images with color-channels encoded
+    /// by `dst_transfer`.
+    ///
+    /// Building of lookup tables takes some time (especially for 16-bit
+    /// components), so it is better to reuse the instance of converter.
+    pub fn new(src_transfer: TransferFunction, dst_transfer: TransferFunction) -> Self {
+        Self {
+            decoder: Decoder::new(src_transfer),
+            encoder: Encoder::new(dst_transfer),
+            cpu_extensions: Default::default(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn src_transfer(&self) -> TransferFunction {
+        self.decoder.transfer()
+    }
+
+    #[inline(always)]
+    pub fn dst_transfer(&self) -> TransferFunction {
+        self.encoder.transfer()
+    }
+
+    #[inline(always)]
+    pub fn cpu_extensions(&self) -> CpuExtensions {
+        self.cpu_extensions
+    }
+
+    /// # Safety
+    /// This is unsafe because this method allows you to set a CPU-extensions
+    /// that is not actually supported by your CPU.
+    pub unsafe fn set_cpu_extensions(&mut self, extensions: CpuExtensions) {
+        self.cpu_extensions = extensions;
+    }
+
+    /// Converts color-channels of source image and store
+    /// result into destination image.
+    pub fn convert(
+        &self,
+        src_image: &ImageView,
+        dst_image: &mut ImageViewMut,
+    ) -> Result<(), GammaConvertError> {
+        match dst_image.pixel_type() {
+            PixelType::U8 => self.convert_into(src_image, dst_image.u8_image()),
+            PixelType::U8x2 => self.convert_into(src_image, dst_image.u8x2_image()),
+            PixelType::U8x3 => self.convert_into(src_image, dst_image.u8x3_image()),
+            PixelType::U8x4 => self.convert_into(src_image, dst_image.u32_image()),
+            PixelType::U16 => self.convert_into(src_image, dst_image.u16_image()),
+            PixelType::U16x3 => self.convert_into(src_image, dst_image.u16x3_image()),
+            PixelType::U16x4 => self.convert_into(src_image, dst_image.u16x4_image()),
+            PixelType::F32 => self.convert_into(src_image, dst_image.f32_image()),
+            PixelType::F32x3 => self.convert_into(src_image, dst_image.f32x3_image()),
+            PixelType::F32x4 => self.convert_into(src_image, dst_image.f32x4_image()),
+            PixelType::I32 => Err(GammaConvertError::UnsupportedPixelType),
+        }
+    }
+
+    fn convert_into<D: ColorPixel>(
+        &self,
+        src_image: &ImageView,
+        dst_image: Option<TypedImageViewMut<D>>,
+    ) -> Result<(), GammaConvertError> {
+        let dst_image = dst_image.ok_or(GammaConvertError::UnsupportedPixelType)?;
+        match src_image.pixel_type() {
+            PixelType::U8 => self.convert_typed(src_image.u8_image(), dst_image),
+            PixelType::U8x2 => self.convert_typed(src_image.u8x2_image(), dst_image),
+            PixelType::U8x3 => self.convert_typed(src_image.u8x3_image(), dst_image),
+            PixelType::U8x4 => self.convert_typed(src_image.u32_image(), dst_image),
+            PixelType::U16 => self.convert_typed(src_image.u16_image(), dst_image),
+            PixelType::U16x3 => self.convert_typed(src_image.u16x3_image(), dst_image),
+            PixelType::U16x4 => self.convert_typed(src_image.u16x4_image(), dst_image),
+            PixelType::F32 => self.convert_typed(src_image.f32_image(), dst_image),
+            PixelType::F32x3 => self.convert_typed(src_image.f32x3_image(), dst_image),
+            PixelType::F32x4 => self.convert_typed(src_image.f32x4_image(), dst_image),
+            PixelType::I32 => Err(GammaConvertError::UnsupportedPixelType),
+        }
+    }
+
+    fn convert_typed<S: ColorPixel, D: ColorPixel>(
+        &self,
+        src_image: Option<TypedImageView<S>>,
+        dst_image: TypedImageViewMut<D>,
+    ) -> Result<(), GammaConvertError> {
+        let src_image = src_image.ok_or(GammaConvertError::UnsupportedPixelType)?;
+        if S::COUNT != D::COUNT {
+            return Err(GammaConvertError::CountOfChannelsIsDifferent);
+        }
+        if src_image.width() != dst_image.width() || src_image.height() != dst_image.height() {
+            return Err(GammaConvertError::SizeIsDifferent);
+        }
+        convert(
+            src_image,
+            dst_image,
+            &self.decoder,
+            &self.encoder,
+            self.cpu_extensions,
+        );
+        Ok(())
+    }
+}
+
+fn convert<S: ColorPixel, D: ColorPixel>(
+    src_image: TypedImageView<S>,
+    dst_image: TypedImageViewMut<D>,
+    decoder: &Decoder,
+    encoder: &Encoder,
+    cpu_extensions: CpuExtensions,
+) {
+    let decoding_table = S::Component::decoding_table(decoder);
+    let encoding_table = D::Component::encoding_table(encoder);
+    let decoder = Lut::new(decoder.transfer(), decoding_table.as_deref());
+    let encoder = Lut::new(encoder.transfer(), encoding_table.as_deref());
+    match cpu_extensions {
+        #[cfg(target_arch = "x86_64")]
+        CpuExtensions::Avx2 => avx2::convert(src_image, dst_image, &decoder, &encoder),
+        #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+        CpuExtensions::Avx512 => avx2::convert(src_image, dst_image, &decoder, &encoder),
+        _ => native::convert(src_image, dst_image, &decoder, &encoder),
+    }
+}
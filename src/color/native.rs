@@ -0,0 +1,42 @@
+use super::tables::{Component, Lut};
+use super::ColorPixel;
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+
+pub(crate) fn convert<S: ColorPixel, D: ColorPixel>(
+    src_image: TypedImageView<S>,
+    mut dst_image: TypedImageViewMut<D>,
+    decoder: &Lut,
+    encoder: &Lut,
+) {
+    let src_rows = src_image.iter_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+    for (src_row, dst_row) in src_rows.zip(dst_rows) {
+        // Safety: pixels are arrays of components (`U8x4` pixel is stored
+        // as `u32`, but in memory it is an array of four `u8`).
+        let src_components = unsafe { src_row.align_to::<S::Component>().1 };
+        let dst_components = unsafe { dst_row.align_to_mut::<D::Component>().1 };
+        convert_row::<S, D>(src_components, dst_components, decoder, encoder);
+    }
+}
+
+#[inline(always)]
+fn convert_row<S: ColorPixel, D: ColorPixel>(
+    src_row: &[S::Component],
+    dst_row: &mut [D::Component],
+    decoder: &Lut,
+    encoder: &Lut,
+) {
+    let colors_count = if S::HAS_ALPHA { S::COUNT - 1 } else { S::COUNT };
+    let src_pixels = src_row.chunks_exact(S::COUNT);
+    let dst_pixels = dst_row.chunks_exact_mut(D::COUNT);
+    for (src_pixel, dst_pixel) in src_pixels.zip(dst_pixels) {
+        let (src_colors, src_alpha) = src_pixel.split_at(colors_count);
+        let (dst_colors, dst_alpha) = dst_pixel.split_at_mut(colors_count);
+        for (&src, dst) in src_colors.iter().zip(dst_colors) {
+            *dst = D::Component::from_linear(src.to_linear(decoder), encoder);
+        }
+        for (&src, dst) in src_alpha.iter().zip(dst_alpha) {
+            *dst = D::Component::from_unit(src.to_unit());
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use super::TransferFunction;
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::{U16x4, U8x4, U16, U8};
+
+/// Lookup tables to convert 8-bit sRGB components into 16-bit
+/// components in linear light and vice versa.
+#[derive(Debug, Clone)]
+pub(crate) struct SrgbLuts {
+    /// sRGB `u8` -> linear `u16`
+    decode: Vec<u16>,
+    /// linear `u16` -> sRGB `u8`
+    encode: Vec<u8>,
+}
+
+impl SrgbLuts {
+    pub fn new() -> Self {
+        let decode = (0..=u8::MAX)
+            .map(|v| (TransferFunction::Srgb.to_linear(v as f32 / 255.) * 65535.).round() as u16)
+            .collect();
+        let encode = (0..=u16::MAX)
+            .map(|v| (TransferFunction::Srgb.from_linear(v as f32 / 65535.) * 255.).round() as u8)
+            .collect();
+        Self { decode, encode }
+    }
+
+    #[inline(always)]
+    fn decode(&self, v: u8) -> u16 {
+        // Safety: size of the table is 256
+        unsafe { *self.decode.get_unchecked(v as usize) }
+    }
+
+    #[inline(always)]
+    fn encode(&self, v: u16) -> u8 {
+        // Safety: size of the table is 65536
+        unsafe { *self.encode.get_unchecked(v as usize) }
+    }
+
+    /// Converts pixels covered by the crop box of source image
+    /// into linear light (the same is true for other `*_to_linear`
+    /// methods). Size of destination image must be equal to the size
+    /// of the smallest region of pixels that contains the crop box.
+    pub fn u8_to_linear(
+        &self,
+        src_image: TypedImageView<U8>,
+        mut dst_image: TypedImageViewMut<U16>,
+    ) {
+        let src_rows = src_image.iter_crop_box_rows();
+        for (src_row, dst_row) in src_rows.zip(dst_image.iter_rows_mut()) {
+            for (&src, dst) in src_row.iter().zip(dst_row.iter_mut()) {
+                *dst = self.decode(src);
+            }
+        }
+    }
+
+    pub fn linear_to_u8(
+        &self,
+        src_image: TypedImageView<U16>,
+        mut dst_image: TypedImageViewMut<U8>,
+    ) {
+        let src_rows = src_image.iter_rows(0, src_image.height().get());
+        for (src_row, dst_row) in src_rows.zip(dst_image.iter_rows_mut()) {
+            for (&src, dst) in src_row.iter().zip(dst_row.iter_mut()) {
+                *dst = self.encode(src);
+            }
+        }
+    }
+
+    /// Converts RGBA-pixels with color-channels premultiplied by alpha
+    /// in sRGB space into pixels with color-channels premultiplied
+    /// by alpha in linear light. Alpha-channel is not gamma-encoded,
+    /// so it is only stretched into the range of `u16`.
+    pub fn u8x4_to_linear(
+        &self,
+        src_image: TypedImageView<U8x4>,
+        mut dst_image: TypedImageViewMut<U16x4>,
+    ) {
+        let src_rows = src_image.iter_crop_box_rows();
+        for (src_row, dst_row) in src_rows.zip(dst_image.iter_rows_mut()) {
+            for (&src, dst) in src_row.iter().zip(dst_row.iter_mut()) {
+                let [r, g, b, a] = src.to_le_bytes();
+                *dst = match a {
+                    0 => [0; 4],
+                    255 => [self.decode(r), self.decode(g), self.decode(b), u16::MAX],
+                    _ => {
+                        let alpha = a as u32;
+                        let decode = |c: u8| {
+                            let c = ((c as u32 * 255 + alpha / 2) / alpha).min(255) as u8;
+                            ((self.decode(c) as u32 * alpha + 127) / 255) as u16
+                        };
+                        [decode(r), decode(g), decode(b), a as u16 * 257]
+                    }
+                };
+            }
+        }
+    }
+
+    /// Reverse operation for [SrgbLuts::u8x4_to_linear].
+    pub fn linear_to_u8x4(
+        &self,
+        src_image: TypedImageView<U16x4>,
+        mut dst_image: TypedImageViewMut<U8x4>,
+    ) {
+        let src_rows = src_image.iter_rows(0, src_image.height().get());
+        for (src_row, dst_row) in src_rows.zip(dst_image.iter_rows_mut()) {
+            for (&[r, g, b, a], dst) in src_row.iter().zip(dst_row.iter_mut()) {
+                let alpha = (a as u32 * 255 + 0x7fff) / 0xffff;
+                *dst = match (alpha, a) {
+                    (0, _) => 0,
+                    (_, u16::MAX) => {
+                        u32::from_le_bytes([self.encode(r), self.encode(g), self.encode(b), 255])
+                    }
+                    _ => {
+                        let a = a as u32;
+                        let encode = |c: u16| {
+                            let c = ((c as u32 * 0xffff + a / 2) / a).min(0xffff) as u16;
+                            ((self.encode(c) as u32 * alpha + 127) / 255) as u8
+                        };
+                        u32::from_le_bytes([encode(r), encode(g), encode(b), alpha as u8])
+                    }
+                };
+            }
+        }
+    }
+
+    /// Converts RGBA-pixels with color-channels not premultiplied
+    /// by alpha in sRGB space into pixels with color-channels not
+    /// premultiplied by alpha in linear light.
+    pub fn u8x4_straight_to_linear(
+        &self,
+        src_image: TypedImageView<U8x4>,
+        mut dst_image: TypedImageViewMut<U16x4>,
+    ) {
+        let src_rows = src_image.iter_crop_box_rows();
+        for (src_row, dst_row) in src_rows.zip(dst_image.iter_rows_mut()) {
+            for (&src, dst) in src_row.iter().zip(dst_row.iter_mut()) {
+                let [r, g, b, a] = src.to_le_bytes();
+                *dst = [
+                    self.decode(r),
+                    self.decode(g),
+                    self.decode(b),
+                    a as u16 * 257,
+                ];
+            }
+        }
+    }
+
+    /// Reverse operation for [SrgbLuts::u8x4_straight_to_linear].
+    pub fn linear_to_u8x4_straight(
+        &self,
+        src_image: TypedImageView<U16x4>,
+        mut dst_image: TypedImageViewMut<U8x4>,
+    ) {
+        let src_rows = src_image.iter_rows(0, src_image.height().get());
+        for (src_row, dst_row) in src_rows.zip(dst_image.iter_rows_mut()) {
+            for (&[r, g, b, a], dst) in src_row.iter().zip(dst_row.iter_mut()) {
+                let alpha = (a as u32 * 255 + 0x7fff) / 0xffff;
+                *dst = u32::from_le_bytes([
+                    self.encode(r),
+                    self.encode(g),
+                    self.encode(b),
+                    alpha as u8,
+                ]);
+            }
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use std::sync::{Arc, RwLock};
+
+use super::TransferFunction;
+
+/// Lookup table that is built on the first use.
+#[derive(Debug, Default)]
+struct LazyTable(RwLock<Option<Arc<[f32]>>>);
+
+impl LazyTable {
+    fn get_or_init(&self, init: impl FnOnce() -> Vec<f32>) -> Arc<[f32]> {
+        if let Some(table) = self.0.read().unwrap().as_ref() {
+            return table.clone();
+        }
+        let mut table = self.0.write().unwrap();
+        table.get_or_insert_with(|| init().into()).clone()
+    }
+}
+
+impl Clone for LazyTable {
+    fn clone(&self) -> Self {
+        Self(RwLock::new(self.0.read().unwrap().clone()))
+    }
+}
+
+/// Lookup tables to convert encoded integer components into linear light.
+///
+/// Tables are built only for types of components which are really
+/// converted, because table for `u16` components is big.
+#[derive(Debug, Clone)]
+pub(crate) struct Decoder {
+    transfer: TransferFunction,
+    u8_table: LazyTable,
+    u16_table: LazyTable,
+}
+
+impl Decoder {
+    pub fn new(transfer: TransferFunction) -> Self {
+        Self {
+            transfer,
+            u8_table: Default::default(),
+            u16_table: Default::default(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn transfer(&self) -> TransferFunction {
+        self.transfer
+    }
+}
+
+/// Lookup tables to convert values in linear light into encoded
+/// integer components.
+///
+/// Tables contain values in linear light that correspond to
+/// the middles between adjacent encoded values. So encoded value is
+/// a count of table items that are less than given linear value.
+/// Tables are padded by infinity up to the size that is a power of two,
+/// so they may be searched by branchless binary search.
+#[derive(Debug, Clone)]
+pub(crate) struct Encoder {
+    transfer: TransferFunction,
+    u8_thresholds: LazyTable,
+    u16_thresholds: LazyTable,
+}
+
+impl Encoder {
+    pub fn new(transfer: TransferFunction) -> Self {
+        Self {
+            transfer,
+            u8_thresholds: Default::default(),
+            u16_thresholds: Default::default(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn transfer(&self) -> TransferFunction {
+        self.transfer
+    }
+}
+
+/// Transfer function with lookup table for one type of components.
+pub(crate) struct Lut<'a> {
+    transfer: TransferFunction,
+    table: &'a [f32],
+}
+
+impl<'a> Lut<'a> {
+    pub fn new(transfer: TransferFunction, table: Option<&'a [f32]>) -> Self {
+        Self {
+            transfer,
+            table: table.unwrap_or_default(),
+        }
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[inline(always)]
+    pub fn table(&self) -> &'a [f32] {
+        self.table
+    }
+}
+
+pub(crate) trait Component: Copy {
+    /// Returns lookup table for [Component::to_linear].
+    fn decoding_table(decoder: &Decoder) -> Option<Arc<[f32]>>;
+
+    /// Returns lookup table for [Component::from_linear].
+    fn encoding_table(encoder: &Encoder) -> Option<Arc<[f32]>>;
+
+    fn to_linear(self, lut: &Lut) -> f32;
+
+    fn from_linear(v: f32, lut: &Lut) -> Self;
+
+    /// Converts alpha-channel into the range `[0, 1]`.
+    fn to_unit(self) -> f32;
+
+    /// Converts alpha-channel from the range `[0, 1]`.
+    fn from_unit(v: f32) -> Self;
+}
+
+impl Component for u8 {
+    fn decoding_table(decoder: &Decoder) -> Option<Arc<[f32]>> {
+        let transfer = decoder.transfer;
+        Some(decoder.u8_table.get_or_init(|| {
+            (0..=u8::MAX)
+                .map(|v| transfer.to_linear(v as f32 / 255.))
+                .collect()
+        }))
+    }
+
+    fn encoding_table(encoder: &Encoder) -> Option<Arc<[f32]>> {
+        let transfer = encoder.transfer;
+        Some(encoder.u8_thresholds.get_or_init(|| {
+            (0..u8::MAX)
+                .map(|v| transfer.to_linear((v as f32 + 0.5) / 255.))
+                .chain([f32::INFINITY])
+                .collect()
+        }))
+    }
+
+    #[inline(always)]
+    fn to_linear(self, lut: &Lut) -> f32 {
+        debug_assert_eq!(lut.table.len(), 256);
+        // Safety: size of the table returned by `decoding_table()` is 256
+        unsafe { *lut.table.get_unchecked(self as usize) }
+    }
+
+    #[inline(always)]
+    fn from_linear(v: f32, lut: &Lut) -> Self {
+        lut.table.partition_point(|&t| t < v) as u8
+    }
+
+    #[inline(always)]
+    fn to_unit(self) -> f32 {
+        self as f32 / 255.
+    }
+
+    #[inline(always)]
+    fn from_unit(v: f32) -> Self {
+        (v * 255.).round() as u8
+    }
+}
+
+impl Component for u16 {
+    fn decoding_table(decoder: &Decoder) -> Option<Arc<[f32]>> {
+        let transfer = decoder.transfer;
+        Some(decoder.u16_table.get_or_init(|| {
+            (0..=u16::MAX)
+                .map(|v| transfer.to_linear(v as f32 / 65535.))
+                .collect()
+        }))
+    }
+
+    fn encoding_table(encoder: &Encoder) -> Option<Arc<[f32]>> {
+        let transfer = encoder.transfer;
+        Some(encoder.u16_thresholds.get_or_init(|| {
+            (0..u16::MAX)
+                .map(|v| transfer.to_linear((v as f32 + 0.5) / 65535.))
+                .chain([f32::INFINITY])
+                .collect()
+        }))
+    }
+
+    #[inline(always)]
+    fn to_linear(self, lut: &Lut) -> f32 {
+        debug_assert_eq!(lut.table.len(), 65536);
+        // Safety: size of the table returned by `decoding_table()` is 65536
+        unsafe { *lut.table.get_unchecked(self as usize) }
+    }
+
+    #[inline(always)]
+    fn from_linear(v: f32, lut: &Lut) -> Self {
+        lut.table.partition_point(|&t| t < v) as u16
+    }
+
+    #[inline(always)]
+    fn to_unit(self) -> f32 {
+        self as f32 / 65535.
+    }
+
+    #[inline(always)]
+    fn from_unit(v: f32) -> Self {
+        (v * 65535.).round() as u16
+    }
+}
+
+impl Component for f32 {
+    fn decoding_table(_decoder: &Decoder) -> Option<Arc<[f32]>> {
+        None
+    }
+
+    fn encoding_table(_encoder: &Encoder) -> Option<Arc<[f32]>> {
+        None
+    }
+
+    #[inline(always)]
+    fn to_linear(self, lut: &Lut) -> f32 {
+        lut.transfer.to_linear(self)
+    }
+
+    #[inline(always)]
+    fn from_linear(v: f32, lut: &Lut) -> Self {
+        lut.transfer.from_linear(v)
+    }
+
+    #[inline(always)]
+    fn to_unit(self) -> f32 {
+        self
+    }
+
+    #[inline(always)]
+    fn from_unit(v: f32) -> Self {
+        v
+    }
+}
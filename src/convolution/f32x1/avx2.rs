@@ -0,0 +1,71 @@
+use std::arch::x86_64::*;
+
+use crate::convolution::{CoefficientsChunks, CoefficientsView};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::F32;
+use crate::simd_utils;
+
+#[inline]
+pub(crate) fn horiz_convolution(
+    src_image: TypedImageView<F32>,
+    mut dst_image: TypedImageViewMut<F32>,
+    offset: u32,
+    coeffs: CoefficientsView<f64>,
+) {
+    let coefficients_chunks = coeffs.chunks();
+    let dst_rows = dst_image.iter_rows_mut();
+    for (y_dst, dst_row) in dst_rows.enumerate() {
+        if let Some(src_row) = src_image.get_row(y_dst as u32 + offset) {
+            unsafe {
+                horiz_convolution_row(src_row, dst_row, coefficients_chunks.clone());
+            }
+        }
+    }
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - coefficients_chunks.len() == dst_row.len()
+/// - max(chunk.start + chunk.values.len() for chunk in coefficients_chunks) <= src_row.len()
+#[target_feature(enable = "avx2,fma")]
+unsafe fn horiz_convolution_row(
+    src_row: &[f32],
+    dst_row: &mut [f32],
+    coefficients_chunks: CoefficientsChunks<f64>,
+) {
+    for (dst_pixel, coeffs_chunk) in dst_row.iter_mut().zip(coefficients_chunks) {
+        let first_x_src = coeffs_chunk.start as usize;
+        let src_pixels = src_row.get_unchecked(first_x_src..);
+        let coeffs = coeffs_chunk.values;
+        let mut x: usize = 0;
+
+        // Pixels are accumulated as two quads of `f64` values.
+        let mut sum0 = _mm256_setzero_pd();
+        let mut sum1 = _mm256_setzero_pd();
+        while x + 8 <= coeffs.len() {
+            let pixels0 = _mm256_cvtps_pd(_mm_loadu_ps(src_pixels.get_unchecked(x..).as_ptr()));
+            let pixels1 = _mm256_cvtps_pd(_mm_loadu_ps(src_pixels.get_unchecked(x + 4..).as_ptr()));
+            let mmk0 = _mm256_loadu_pd(coeffs.get_unchecked(x..).as_ptr());
+            let mmk1 = _mm256_loadu_pd(coeffs.get_unchecked(x + 4..).as_ptr());
+            sum0 = _mm256_fmadd_pd(pixels0, mmk0, sum0);
+            sum1 = _mm256_fmadd_pd(pixels1, mmk1, sum1);
+            x += 8;
+        }
+
+        if x + 4 <= coeffs.len() {
+            let pixels = _mm256_cvtps_pd(_mm_loadu_ps(src_pixels.get_unchecked(x..).as_ptr()));
+            let mmk = _mm256_loadu_pd(coeffs.get_unchecked(x..).as_ptr());
+            sum0 = _mm256_fmadd_pd(pixels, mmk, sum0);
+            x += 4;
+        }
+
+        let mut ss = simd_utils::hsum_256_pd(_mm256_add_pd(sum0, sum1));
+        for (&k, &pixel) in coeffs
+            .get_unchecked(x..)
+            .iter()
+            .zip(src_pixels.get_unchecked(x..))
+        {
+            ss += pixel as f64 * k;
+        }
+        *dst_pixel = ss as f32;
+    }
+}
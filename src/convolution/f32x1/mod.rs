@@ -1,19 +1,33 @@
-use super::{Coefficients, Convolution};
+#[cfg(target_arch = "x86_64")]
+use super::vertical_f32;
+use super::{CoefficientsView, Convolution};
 use crate::image_view::{TypedImageView, TypedImageViewMut};
 use crate::pixels::F32;
 use crate::CpuExtensions;
 
+#[cfg(target_arch = "x86_64")]
+mod avx2;
 mod native;
+#[cfg(target_arch = "x86_64")]
+mod sse4;
 
 impl Convolution for F32 {
+    type Coefficient = f64;
+
     fn horiz_convolution(
         src_image: TypedImageView<Self>,
         dst_image: TypedImageViewMut<Self>,
         offset: u32,
-        coeffs: Coefficients,
+        coeffs: CoefficientsView<f64>,
         cpu_extensions: CpuExtensions,
     ) {
         match cpu_extensions {
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Avx2 => avx2::horiz_convolution(src_image, dst_image, offset, coeffs),
+            #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+            CpuExtensions::Avx512 => avx2::horiz_convolution(src_image, dst_image, offset, coeffs),
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Sse4_1 => sse4::horiz_convolution(src_image, dst_image, offset, coeffs),
             _ => native::horiz_convolution(src_image, dst_image, offset, coeffs),
         }
     }
@@ -21,10 +35,22 @@ impl Convolution for F32 {
     fn vert_convolution(
         src_image: TypedImageView<Self>,
         dst_image: TypedImageViewMut<Self>,
-        coeffs: Coefficients,
+        coeffs: CoefficientsView<f64>,
         cpu_extensions: CpuExtensions,
     ) {
         match cpu_extensions {
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Avx2 => {
+                vertical_f32::avx2::vert_convolution(src_image, dst_image, coeffs)
+            }
+            #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+            CpuExtensions::Avx512 => {
+                vertical_f32::avx2::vert_convolution(src_image, dst_image, coeffs)
+            }
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Sse4_1 => {
+                vertical_f32::sse4::vert_convolution(src_image, dst_image, coeffs)
+            }
             _ => native::vert_convolution(src_image, dst_image, coeffs),
         }
     }
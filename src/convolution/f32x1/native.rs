@@ -1,4 +1,4 @@
-use crate::convolution::Coefficients;
+use crate::convolution::CoefficientsView;
 use crate::image_view::{TypedImageView, TypedImageViewMut};
 use crate::pixels::F32;
 
@@ -6,43 +6,40 @@ pub(crate) fn horiz_convolution(
     src_image: TypedImageView<F32>,
     mut dst_image: TypedImageViewMut<F32>,
     offset: u32,
-    coeffs: Coefficients,
+    coeffs: CoefficientsView<f64>,
 ) {
-    let coefficients_chunks = coeffs.get_chunks();
-    let mut y_src = offset;
+    let coefficients_chunks = coeffs.chunks();
 
-    for out_row in dst_image.iter_rows_mut() {
-        for (out_pixel, coeffs_chunk) in out_row.iter_mut().zip(&coefficients_chunks) {
+    for (y_dst, out_row) in dst_image.iter_rows_mut().enumerate() {
+        let y_src = y_dst as u32 + offset;
+        for (out_pixel, coeffs_chunk) in out_row.iter_mut().zip(coefficients_chunks.clone()) {
             let first_x_src = coeffs_chunk.start;
             let mut ss = 0.;
             let pixels = src_image.iter_horiz(first_x_src, y_src);
             for (&k, &pixel) in coeffs_chunk.values.iter().zip(pixels) {
                 ss += pixel as f64 * k;
             }
-            *out_pixel = ss.round() as f32;
+            *out_pixel = ss as f32;
         }
-        y_src += 1;
     }
 }
 
 pub(crate) fn vert_convolution(
     src_image: TypedImageView<F32>,
     mut dst_image: TypedImageViewMut<F32>,
-    coeffs: Coefficients,
+    coeffs: CoefficientsView<f64>,
 ) {
-    let coefficients_chunks = coeffs.get_chunks();
+    let coefficients_chunks = coeffs.chunks();
 
     for (out_row, coeffs_chunk) in dst_image.iter_rows_mut().zip(coefficients_chunks) {
         let first_y_src = coeffs_chunk.start;
         for (x_src, out_pixel) in out_row.iter_mut().enumerate() {
             let mut ss = 0.;
-            let mut y_src = first_y_src;
-            for &k in coeffs_chunk.values.iter() {
-                let pixel = src_image.get_pixel(x_src as u32, y_src);
+            for (dy, &k) in coeffs_chunk.values.iter().enumerate() {
+                let pixel = src_image.get_pixel(x_src as u32, first_y_src + dy as u32);
                 ss += pixel as f64 * k;
-                y_src += 1;
             }
-            *out_pixel = ss.round() as f32;
+            *out_pixel = ss as f32;
         }
     }
 }
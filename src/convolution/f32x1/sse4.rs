@@ -0,0 +1,65 @@
+use std::arch::x86_64::*;
+
+use crate::convolution::{CoefficientsChunks, CoefficientsView};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::F32;
+use crate::simd_utils;
+
+#[inline]
+pub(crate) fn horiz_convolution(
+    src_image: TypedImageView<F32>,
+    mut dst_image: TypedImageViewMut<F32>,
+    offset: u32,
+    coeffs: CoefficientsView<f64>,
+) {
+    let coefficients_chunks = coeffs.chunks();
+    let dst_rows = dst_image.iter_rows_mut();
+    for (y_dst, dst_row) in dst_rows.enumerate() {
+        if let Some(src_row) = src_image.get_row(y_dst as u32 + offset) {
+            unsafe {
+                horiz_convolution_row(src_row, dst_row, coefficients_chunks.clone());
+            }
+        }
+    }
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - coefficients_chunks.len() == dst_row.len()
+/// - max(chunk.start + chunk.values.len() for chunk in coefficients_chunks) <= src_row.len()
+#[target_feature(enable = "sse4.1")]
+unsafe fn horiz_convolution_row(
+    src_row: &[f32],
+    dst_row: &mut [f32],
+    coefficients_chunks: CoefficientsChunks<f64>,
+) {
+    for (dst_pixel, coeffs_chunk) in dst_row.iter_mut().zip(coefficients_chunks) {
+        let first_x_src = coeffs_chunk.start as usize;
+        let src_pixels = src_row.get_unchecked(first_x_src..);
+        let coeffs = coeffs_chunk.values;
+        let mut x: usize = 0;
+
+        // Pixels are accumulated as two pairs of `f64` values.
+        let mut sum_lo = _mm_setzero_pd();
+        let mut sum_hi = _mm_setzero_pd();
+        while x + 4 <= coeffs.len() {
+            let pixels = _mm_loadu_ps(src_pixels.get_unchecked(x..).as_ptr());
+            let lo = _mm_cvtps_pd(pixels);
+            let hi = _mm_cvtps_pd(_mm_movehl_ps(pixels, pixels));
+            let mmk_lo = _mm_loadu_pd(coeffs.get_unchecked(x..).as_ptr());
+            let mmk_hi = _mm_loadu_pd(coeffs.get_unchecked(x + 2..).as_ptr());
+            sum_lo = _mm_add_pd(sum_lo, _mm_mul_pd(lo, mmk_lo));
+            sum_hi = _mm_add_pd(sum_hi, _mm_mul_pd(hi, mmk_hi));
+            x += 4;
+        }
+
+        let mut ss = simd_utils::hsum_pd(_mm_add_pd(sum_lo, sum_hi));
+        for (&k, &pixel) in coeffs
+            .get_unchecked(x..)
+            .iter()
+            .zip(src_pixels.get_unchecked(x..))
+        {
+            ss += pixel as f64 * k;
+        }
+        *dst_pixel = ss as f32;
+    }
+}
@@ -0,0 +1,49 @@
+use crate::convolution::CoefficientsView;
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::F32x3;
+
+pub(crate) fn horiz_convolution(
+    src_image: TypedImageView<F32x3>,
+    mut dst_image: TypedImageViewMut<F32x3>,
+    offset: u32,
+    coeffs: CoefficientsView<f64>,
+) {
+    let coefficients_chunks = coeffs.chunks();
+
+    for (y_dst, dst_row) in dst_image.iter_rows_mut().enumerate() {
+        let y_src = y_dst as u32 + offset;
+        for (dst_pixel, coeffs_chunk) in dst_row.iter_mut().zip(coefficients_chunks.clone()) {
+            let first_x_src = coeffs_chunk.start;
+            let mut ss = [0f64; 3];
+            let src_pixels = src_image.iter_horiz(first_x_src, y_src);
+            for (&k, src_pixel) in coeffs_chunk.values.iter().zip(src_pixels) {
+                for (s, &c) in ss.iter_mut().zip(src_pixel) {
+                    *s += c as f64 * k;
+                }
+            }
+            *dst_pixel = ss.map(|s| s as f32);
+        }
+    }
+}
+
+pub(crate) fn vert_convolution(
+    src_image: TypedImageView<F32x3>,
+    mut dst_image: TypedImageViewMut<F32x3>,
+    coeffs: CoefficientsView<f64>,
+) {
+    let coefficients_chunks = coeffs.chunks();
+
+    for (dst_row, coeffs_chunk) in dst_image.iter_rows_mut().zip(coefficients_chunks) {
+        let first_y_src = coeffs_chunk.start;
+        for (x_src, dst_pixel) in dst_row.iter_mut().enumerate() {
+            let mut ss = [0f64; 3];
+            for (dy, &k) in coeffs_chunk.values.iter().enumerate() {
+                let src_pixel = src_image.get_pixel(x_src as u32, first_y_src + dy as u32);
+                for (s, c) in ss.iter_mut().zip(src_pixel) {
+                    *s += c as f64 * k;
+                }
+            }
+            *dst_pixel = ss.map(|s| s as f32);
+        }
+    }
+}
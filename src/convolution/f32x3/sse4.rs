@@ -0,0 +1,55 @@
+use std::arch::x86_64::*;
+
+use crate::convolution::{CoefficientsChunks, CoefficientsView};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::F32x3;
+
+#[inline]
+pub(crate) fn horiz_convolution(
+    src_image: TypedImageView<F32x3>,
+    mut dst_image: TypedImageViewMut<F32x3>,
+    offset: u32,
+    coeffs: CoefficientsView<f64>,
+) {
+    let coefficients_chunks = coeffs.chunks();
+    let dst_rows = dst_image.iter_rows_mut();
+    for (y_dst, dst_row) in dst_rows.enumerate() {
+        if let Some(src_row) = src_image.get_row(y_dst as u32 + offset) {
+            unsafe {
+                horiz_convolution_row(src_row, dst_row, coefficients_chunks.clone());
+            }
+        }
+    }
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - coefficients_chunks.len() == dst_row.len()
+/// - max(chunk.start + chunk.values.len() for chunk in coefficients_chunks) <= src_row.len()
+#[target_feature(enable = "sse4.1")]
+unsafe fn horiz_convolution_row(
+    src_row: &[[f32; 3]],
+    dst_row: &mut [[f32; 3]],
+    coefficients_chunks: CoefficientsChunks<f64>,
+) {
+    for (dst_pixel, coeffs_chunk) in dst_row.iter_mut().zip(coefficients_chunks) {
+        let first_x_src = coeffs_chunk.start as usize;
+        let src_pixels = src_row.get_unchecked(first_x_src..);
+
+        // Components of pixel are accumulated as two pairs of `f64` values.
+        let mut sum_lo = _mm_setzero_pd();
+        let mut sum_hi = _mm_setzero_pd();
+        for (&k, src_pixel) in coeffs_chunk.values.iter().zip(src_pixels) {
+            let mmk = _mm_set1_pd(k);
+            let [r, g, b] = *src_pixel;
+            let lo = _mm_set_pd(g as f64, r as f64);
+            let hi = _mm_set_sd(b as f64);
+            sum_lo = _mm_add_pd(sum_lo, _mm_mul_pd(lo, mmk));
+            sum_hi = _mm_add_pd(sum_hi, _mm_mul_pd(hi, mmk));
+        }
+
+        let mut res = [0f64; 4];
+        _mm_storeu_pd(res.as_mut_ptr(), sum_lo);
+        _mm_storeu_pd(res[2..].as_mut_ptr(), sum_hi);
+        *dst_pixel = [res[0] as f32, res[1] as f32, res[2] as f32];
+    }
+}
@@ -0,0 +1,47 @@
+use std::arch::x86_64::*;
+
+use crate::convolution::{CoefficientsChunks, CoefficientsView};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::F32x4;
+
+#[inline]
+pub(crate) fn horiz_convolution(
+    src_image: TypedImageView<F32x4>,
+    mut dst_image: TypedImageViewMut<F32x4>,
+    offset: u32,
+    coeffs: CoefficientsView<f64>,
+) {
+    let coefficients_chunks = coeffs.chunks();
+    let dst_rows = dst_image.iter_rows_mut();
+    for (y_dst, dst_row) in dst_rows.enumerate() {
+        if let Some(src_row) = src_image.get_row(y_dst as u32 + offset) {
+            unsafe {
+                horiz_convolution_row(src_row, dst_row, coefficients_chunks.clone());
+            }
+        }
+    }
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - coefficients_chunks.len() == dst_row.len()
+/// - max(chunk.start + chunk.values.len() for chunk in coefficients_chunks) <= src_row.len()
+#[target_feature(enable = "avx2,fma")]
+unsafe fn horiz_convolution_row(
+    src_row: &[[f32; 4]],
+    dst_row: &mut [[f32; 4]],
+    coefficients_chunks: CoefficientsChunks<f64>,
+) {
+    for (dst_pixel, coeffs_chunk) in dst_row.iter_mut().zip(coefficients_chunks) {
+        let first_x_src = coeffs_chunk.start as usize;
+        let src_pixels = src_row.get_unchecked(first_x_src..);
+
+        // All components of pixel are accumulated as `f64` values in one register.
+        let mut sum = _mm256_setzero_pd();
+        for (&k, src_pixel) in coeffs_chunk.values.iter().zip(src_pixels) {
+            let pixel = _mm256_cvtps_pd(_mm_loadu_ps(src_pixel.as_ptr()));
+            sum = _mm256_fmadd_pd(pixel, _mm256_set1_pd(k), sum);
+        }
+
+        _mm_storeu_ps(dst_pixel.as_mut_ptr(), _mm256_cvtpd_ps(sum));
+    }
+}
@@ -1,6 +1,195 @@
 use std::f64::consts::PI;
+use std::fmt::{self, Debug, Formatter};
 
-pub type FilterFn<'a> = &'a dyn Fn(f64) -> f64;
+use crate::errors::{InvalidFilterParamsError, InvalidFilterSupportError};
+
+/// Custom filter function used by convolution.
+///
+/// Function of filter is a weight function of distance (in pixels)
+/// between centers of source and destination pixels. It must return
+/// zero for any distance greater than `support` by absolute value.
+///
+/// Function may depend on parameters stored in the filter, so one
+/// function may be used to create a family of filters.
+///
+/// Filters are compared by name, support and parameters, so different
+/// functions must be used with different names.
+///
+/// # Examples
+///
+/// ```
+/// use fast_image_resize::{Filter, FilterType, ResizeAlg};
+///
+/// fn triangle(x: f64) -> f64 {
+///     (1. - x.abs()).max(0.)
+/// }
+///
+/// let filter = Filter::new("triangle", triangle, 1.0).unwrap();
+/// let alg = ResizeAlg::Convolution(FilterType::Custom(filter));
+///
+/// // Gaussian function with standard deviation passed as parameter.
+/// fn gaussian(x: f64, params: &[f64]) -> f64 {
+///     let sigma = params[0];
+///     (-x * x / (2. * sigma * sigma)).exp()
+/// }
+///
+/// let sigma = 0.7;
+/// let filter = Filter::with_params("gaussian", gaussian, &[sigma], 3. * sigma).unwrap();
+/// let alg = ResizeAlg::Convolution(FilterType::Custom(filter));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Filter {
+    name: &'static str,
+    func: CustomFunc,
+    params: [f64; Filter::MAX_PARAMS],
+    params_count: usize,
+    support: f64,
+}
+
+#[derive(Clone, Copy)]
+enum CustomFunc {
+    Plain(fn(f64) -> f64),
+    WithParams(fn(f64, &[f64]) -> f64),
+}
+
+impl Filter {
+    /// Maximal count of parameters of filter function.
+    pub const MAX_PARAMS: usize = 4;
+
+    /// Creates custom filter. `support` must be a finite positive number.
+    pub fn new(
+        name: &'static str,
+        func: fn(f64) -> f64,
+        support: f64,
+    ) -> Result<Self, InvalidFilterSupportError> {
+        if !support.is_finite() || support <= 0. {
+            return Err(InvalidFilterSupportError);
+        }
+        Ok(Self {
+            name,
+            func: CustomFunc::Plain(func),
+            params: [0.; Self::MAX_PARAMS],
+            params_count: 0,
+            support,
+        })
+    }
+
+    /// Creates custom filter which function receives given parameters.
+    /// `support` must be a finite positive number, `params` must contain
+    /// no more than [Filter::MAX_PARAMS] finite numbers.
+    pub fn with_params(
+        name: &'static str,
+        func: fn(f64, &[f64]) -> f64,
+        params: &[f64],
+        support: f64,
+    ) -> Result<Self, InvalidFilterParamsError> {
+        if !support.is_finite() || support <= 0. {
+            return Err(InvalidFilterParamsError);
+        }
+        if params.len() > Self::MAX_PARAMS || !params.iter().all(|p| p.is_finite()) {
+            return Err(InvalidFilterParamsError);
+        }
+        let mut stored_params = [0.; Self::MAX_PARAMS];
+        stored_params[..params.len()].copy_from_slice(params);
+        Ok(Self {
+            name,
+            func: CustomFunc::WithParams(func),
+            params: stored_params,
+            params_count: params.len(),
+            support,
+        })
+    }
+
+    #[inline(always)]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    #[inline(always)]
+    pub fn params(&self) -> &[f64] {
+        &self.params[..self.params_count]
+    }
+
+    #[inline(always)]
+    pub fn support(&self) -> f64 {
+        self.support
+    }
+
+    #[inline]
+    fn call(&self, x: f64) -> f64 {
+        match self.func {
+            CustomFunc::Plain(func) => func(x),
+            CustomFunc::WithParams(func) => func(x, self.params()),
+        }
+    }
+}
+
+impl Debug for Filter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Filter")
+            .field("name", &self.name)
+            .field("params", &self.params())
+            .field("support", &self.support)
+            .finish()
+    }
+}
+
+impl PartialEq for Filter {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.params() == other.params() && self.support == other.support
+    }
+}
+
+/// Parameters of Gaussian filter (see [FilterType::Gaussian]).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GaussianParams {
+    sigma: f64,
+}
+
+impl GaussianParams {
+    /// Creates parameters of Gaussian filter with given standard deviation.
+    /// `sigma` must be a finite positive number.
+    pub fn new(sigma: f64) -> Result<Self, InvalidFilterParamsError> {
+        if !sigma.is_finite() || sigma <= 0. {
+            return Err(InvalidFilterParamsError);
+        }
+        Ok(Self { sigma })
+    }
+
+    #[inline(always)]
+    pub fn sigma(&self) -> f64 {
+        self.sigma
+    }
+}
+
+/// Parameters of Mitchell–Netravali bicubic filter
+/// (see [FilterType::Bicubic]).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BicubicParams {
+    b: f64,
+    c: f64,
+}
+
+impl BicubicParams {
+    /// Creates parameters of bicubic filter.
+    /// `b` and `c` must be finite numbers.
+    pub fn new(b: f64, c: f64) -> Result<Self, InvalidFilterParamsError> {
+        if !b.is_finite() || !c.is_finite() {
+            return Err(InvalidFilterParamsError);
+        }
+        Ok(Self { b, c })
+    }
+
+    #[inline(always)]
+    pub fn b(&self) -> f64 {
+        self.b
+    }
+
+    #[inline(always)]
+    pub fn c(&self) -> f64 {
+        self.c
+    }
+}
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[non_exhaustive]
@@ -30,24 +219,125 @@ pub enum FilterType {
     /// Lanczos filter (a truncated sinc) on all pixels that may contribute
     /// to the output value.
     Lanczos3,
+    /// Custom filter function (see [Filter]).
+    Custom(Filter),
+    /// Gaussian filter with given standard deviation. Support of the filter
+    /// is `4 * sigma` (like in ImageMagick). ImageMagick uses `sigma = 0.5`
+    /// by default.
+    ///
+    /// Use [FilterType::gaussian] to create the filter.
+    Gaussian(GaussianParams),
+    /// Lanczos filter with two lobes. It is sharper than `Bilinear`
+    /// and has less ringing artifacts than `Lanczos3`.
+    Lanczos2,
+    /// Lanczos filter with four lobes.
+    Lanczos4,
+    /// Cubic B-spline (Mitchell–Netravali filter with B = 1 and C = 0).
+    /// It is very smooth and produces blurred images without ringing.
+    BSpline,
+    /// Hermite filter (Mitchell–Netravali filter with B = 0 and C = 0).
+    Hermite,
+    /// Spline16 filter from Panorama Tools (4 taps).
+    Spline16,
+    /// Spline36 filter from Panorama Tools (6 taps).
+    Spline36,
+    /// Sinc function windowed by Blackman window with three lobes.
+    Blackman,
+    /// Magic Kernel Sharp 2013 filter by John Costella.
+    MagicKernelSharp2013,
+    /// Magic Kernel Sharp 2021 filter by John Costella.
+    MagicKernelSharp2021,
+    /// General Mitchell–Netravali bicubic filter with parameters `b` and `c`.
+    /// Known filters of this family:
+    /// - B-spline: `b = 1, c = 0`;
+    /// - Hermite: `b = 0, c = 0`;
+    /// - Catmull-Rom: `b = 0, c = 0.5`;
+    /// - Mitchell: `b = 1/3, c = 1/3`;
+    /// - Robidoux: `b = 0.3782, c = 0.3109`;
+    /// - Robidoux Sharp: `b = 0.2620, c = 0.3690`.
+    ///
+    /// Use [FilterType::bicubic] to create the filter.
+    Bicubic(BicubicParams),
+}
+
+impl FilterType {
+    /// Creates Gaussian filter with given standard deviation
+    /// (see [FilterType::Gaussian]). `sigma` must be a finite positive number.
+    pub fn gaussian(sigma: f64) -> Result<Self, InvalidFilterParamsError> {
+        GaussianParams::new(sigma).map(Self::Gaussian)
+    }
+
+    /// Creates Mitchell–Netravali bicubic filter
+    /// (see [FilterType::Bicubic]). `b` and `c` must be finite numbers.
+    pub fn bicubic(b: f64, c: f64) -> Result<Self, InvalidFilterParamsError> {
+        BicubicParams::new(b, c).map(Self::Bicubic)
+    }
+
+    /// Returns radius of filter (in pixels of source image for upscaling
+    /// and in pixels of destination image for downscaling).
+    pub fn support(&self) -> f64 {
+        get_filter_func(*self).1
+    }
+
+    /// Returns value of filter function for distance `x` between
+    /// centers of source and destination pixels.
+    pub fn weight(&self, x: f64) -> f64 {
+        get_filter_func(*self).0.call(x)
+    }
 }
 
+// `#[default]` attribute for enum variants requires Rust 1.62.
+#[allow(clippy::derivable_impls)]
 impl Default for FilterType {
     fn default() -> Self {
         FilterType::Lanczos3
     }
 }
 
-/// Returns reference to filter function and value of `filter_support`.
+/// Function of filter used to calculate coefficients of convolution.
+#[derive(Clone, Copy)]
+pub enum FilterFunc {
+    Plain(fn(f64) -> f64),
+    Custom(Filter),
+    Gaussian(GaussianParams),
+    Bicubic(BicubicParams),
+}
+
+impl FilterFunc {
+    #[inline]
+    pub fn call(self, x: f64) -> f64 {
+        match self {
+            Self::Plain(func) => func(x),
+            Self::Custom(filter) => filter.call(x),
+            Self::Gaussian(params) => gaussian_filter(x, params.sigma),
+            Self::Bicubic(params) => bicubic_filter(x, params.b, params.c),
+        }
+    }
+}
+
+/// Returns filter function and value of `filter_support`.
 #[inline]
-pub fn get_filter_func(filter_type: FilterType) -> (FilterFn<'static>, f64) {
+pub fn get_filter_func(filter_type: FilterType) -> (FilterFunc, f64) {
+    use FilterFunc::Plain;
     match filter_type {
-        FilterType::Box => (&box_filter, 0.5),
-        FilterType::Bilinear => (&bilinear_filter, 1.0),
-        FilterType::Hamming => (&hamming_filter, 1.0),
-        FilterType::CatmullRom => (&catmul_filter, 2.0),
-        FilterType::Mitchell => (&mitchell_filter, 2.0),
-        FilterType::Lanczos3 => (&lanczos_filter, 3.0),
+        FilterType::Box => (Plain(box_filter), 0.5),
+        FilterType::Bilinear => (Plain(bilinear_filter), 1.0),
+        FilterType::Hamming => (Plain(hamming_filter), 1.0),
+        FilterType::CatmullRom => (Plain(catmul_filter), 2.0),
+        FilterType::Mitchell => (Plain(mitchell_filter), 2.0),
+        FilterType::Lanczos3 => (Plain(lanczos_filter), 3.0),
+        FilterType::Custom(filter) => (FilterFunc::Custom(filter), filter.support),
+        FilterType::Gaussian(params) => (FilterFunc::Gaussian(params), 4. * params.sigma),
+        FilterType::Lanczos2 => (Plain(lanczos2_filter), 2.0),
+        FilterType::Lanczos4 => (Plain(lanczos4_filter), 4.0),
+        FilterType::BSpline => (Plain(bspline_filter), 2.0),
+        FilterType::Hermite => (Plain(hermite_filter), 1.0),
+        FilterType::Spline16 => (Plain(spline16_filter), 2.0),
+        FilterType::Spline36 => (Plain(spline36_filter), 3.0),
+        FilterType::Blackman => (Plain(blackman_filter), 3.0),
+        FilterType::MagicKernelSharp2013 => (Plain(magic_kernel_sharp_2013_filter), 2.5),
+        FilterType::MagicKernelSharp2021 => (Plain(magic_kernel_sharp_2021_filter), 4.5),
+        FilterType::Bicubic(params) => (FilterFunc::Bicubic(params), 2.0),
     }
 }
 
@@ -131,3 +421,156 @@ fn lanczos_filter(x: f64) -> f64 {
         0.0
     }
 }
+
+#[inline]
+fn lanczos2_filter(x: f64) -> f64 {
+    if (-2.0..2.0).contains(&x) {
+        sinc_filter(x) * sinc_filter(x / 2.)
+    } else {
+        0.0
+    }
+}
+
+#[inline]
+fn lanczos4_filter(x: f64) -> f64 {
+    if (-4.0..4.0).contains(&x) {
+        sinc_filter(x) * sinc_filter(x / 4.)
+    } else {
+        0.0
+    }
+}
+
+/// Gaussian function truncated by `4 * sigma`
+#[inline]
+fn gaussian_filter(x: f64, sigma: f64) -> f64 {
+    if x.abs() < 4. * sigma {
+        (-x * x / (2. * sigma * sigma)).exp() / (sigma * (2. * PI).sqrt())
+    } else {
+        0.0
+    }
+}
+
+/// Cubic B-spline filter (B = 1, C = 0)
+/// https://en.wikipedia.org/wiki/Mitchell%E2%80%93Netravali_filters
+#[inline]
+fn bspline_filter(mut x: f64) -> f64 {
+    x = x.abs();
+    if x < 1.0 {
+        (0.5 * x - 1.) * x * x + 2. / 3.
+    } else if x < 2.0 {
+        let t = 2. - x;
+        t * t * t / 6.
+    } else {
+        0.0
+    }
+}
+
+/// Mitchell–Netravali family of bicubic filters
+/// https://en.wikipedia.org/wiki/Mitchell%E2%80%93Netravali_filters
+#[inline]
+fn bicubic_filter(mut x: f64, b: f64, c: f64) -> f64 {
+    x = x.abs();
+    if x < 1.0 {
+        ((12. - 9. * b - 6. * c) * x * x * x + (-18. + 12. * b + 6. * c) * x * x + (6. - 2. * b))
+            / 6.
+    } else if x < 2.0 {
+        ((-b - 6. * c) * x * x * x
+            + (6. * b + 30. * c) * x * x
+            + (-12. * b - 48. * c) * x
+            + (8. * b + 24. * c))
+            / 6.
+    } else {
+        0.0
+    }
+}
+
+/// Hermite filter (B = 0, C = 0)
+#[inline]
+fn hermite_filter(mut x: f64) -> f64 {
+    x = x.abs();
+    if x < 1.0 {
+        (2. * x - 3.) * x * x + 1.
+    } else {
+        0.0
+    }
+}
+
+/// Spline16 filter from Panorama Tools
+#[inline]
+fn spline16_filter(mut x: f64) -> f64 {
+    x = x.abs();
+    if x < 1.0 {
+        ((x - 9. / 5.) * x - 1. / 5.) * x + 1.
+    } else if x < 2.0 {
+        x -= 1.;
+        ((-1. / 3. * x + 4. / 5.) * x - 7. / 15.) * x
+    } else {
+        0.0
+    }
+}
+
+/// Spline36 filter from Panorama Tools
+#[inline]
+fn spline36_filter(mut x: f64) -> f64 {
+    x = x.abs();
+    if x < 1.0 {
+        ((13. / 11. * x - 453. / 209.) * x - 3. / 209.) * x + 1.
+    } else if x < 2.0 {
+        x -= 1.;
+        ((-6. / 11. * x + 270. / 209.) * x - 156. / 209.) * x
+    } else if x < 3.0 {
+        x -= 2.;
+        ((1. / 11. * x - 45. / 209.) * x + 26. / 209.) * x
+    } else {
+        0.0
+    }
+}
+
+#[inline]
+fn blackman_filter(x: f64) -> f64 {
+    // sinc windowed by Blackman window
+    if (-3.0..3.0).contains(&x) {
+        let t = PI * x / 3.;
+        sinc_filter(x) * (0.42 + 0.5 * t.cos() + 0.08 * (2. * t).cos())
+    } else {
+        0.0
+    }
+}
+
+/// Magic Kernel Sharp 2013
+/// https://johncostella.com/magic/
+#[inline]
+fn magic_kernel_sharp_2013_filter(mut x: f64) -> f64 {
+    x = x.abs();
+    if x <= 0.5 {
+        17. / 16. - 7. / 4. * x * x
+    } else if x <= 1.5 {
+        (1. - x) * (7. / 4. - x)
+    } else if x <= 2.5 {
+        let t = x - 5. / 2.;
+        -1. / 8. * t * t
+    } else {
+        0.0
+    }
+}
+
+/// Magic Kernel Sharp 2021
+/// https://johncostella.com/magic/
+#[inline]
+fn magic_kernel_sharp_2021_filter(mut x: f64) -> f64 {
+    x = x.abs();
+    if x <= 0.5 {
+        577. / 576. - 239. / 144. * x * x
+    } else if x <= 1.5 {
+        (140. * x * x - 379. * x + 239.) / 144.
+    } else if x <= 2.5 {
+        -(24. * x * x - 113. * x + 130.) / 144.
+    } else if x <= 3.5 {
+        (4. * x * x - 27. * x + 45.) / 144.
+    } else if x <= 4.5 {
+        let t = 2. * x - 9.;
+        -t * t / 1152.
+    } else {
+        0.0
+    }
+}
@@ -0,0 +1,144 @@
+use std::arch::x86_64::*;
+
+use super::sse4;
+use crate::convolution::{CoefficientsChunk, CoefficientsChunks, CoefficientsView};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::I32;
+use crate::simd_utils;
+
+#[inline]
+pub(crate) fn horiz_convolution(
+    src_image: TypedImageView<I32>,
+    mut dst_image: TypedImageViewMut<I32>,
+    offset: u32,
+    coeffs: CoefficientsView<f64>,
+) {
+    let coefficients_chunks = coeffs.chunks();
+    let dst_rows = dst_image.iter_rows_mut();
+    for (y_dst, dst_row) in dst_rows.enumerate() {
+        if let Some(src_row) = src_image.get_row(y_dst as u32 + offset) {
+            unsafe {
+                horiz_convolution_row(src_row, dst_row, coefficients_chunks.clone());
+            }
+        }
+    }
+}
+
+#[inline]
+pub(crate) fn vert_convolution(
+    src_image: TypedImageView<I32>,
+    mut dst_image: TypedImageViewMut<I32>,
+    coeffs: CoefficientsView<f64>,
+) {
+    let coefficients_chunks = coeffs.chunks();
+    let dst_rows = dst_image.iter_rows_mut();
+    for (dst_row, coeffs_chunk) in dst_rows.zip(coefficients_chunks) {
+        unsafe {
+            vert_convolution_row(&src_image, dst_row, coeffs_chunk);
+        }
+    }
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - coefficients_chunks.len() == dst_row.len()
+/// - max(chunk.start + chunk.values.len() for chunk in coefficients_chunks) <= src_row.len()
+#[target_feature(enable = "avx2,fma")]
+unsafe fn horiz_convolution_row(
+    src_row: &[i32],
+    dst_row: &mut [i32],
+    coefficients_chunks: CoefficientsChunks<f64>,
+) {
+    for (dst_pixel, coeffs_chunk) in dst_row.iter_mut().zip(coefficients_chunks) {
+        let first_x_src = coeffs_chunk.start as usize;
+        let src_pixels = src_row.get_unchecked(first_x_src..);
+        let coeffs = coeffs_chunk.values;
+        let mut x: usize = 0;
+
+        // Pixels are accumulated as two quads of `f64` values.
+        let mut sum0 = _mm256_setzero_pd();
+        let mut sum1 = _mm256_setzero_pd();
+        while x + 8 <= coeffs.len() {
+            let pixels0 = _mm256_cvtepi32_pd(simd_utils::loadu_si128(src_pixels, x));
+            let pixels1 = _mm256_cvtepi32_pd(simd_utils::loadu_si128(src_pixels, x + 4));
+            let mmk0 = _mm256_loadu_pd(coeffs.get_unchecked(x..).as_ptr());
+            let mmk1 = _mm256_loadu_pd(coeffs.get_unchecked(x + 4..).as_ptr());
+            sum0 = _mm256_fmadd_pd(pixels0, mmk0, sum0);
+            sum1 = _mm256_fmadd_pd(pixels1, mmk1, sum1);
+            x += 8;
+        }
+
+        if x + 4 <= coeffs.len() {
+            let pixels = _mm256_cvtepi32_pd(simd_utils::loadu_si128(src_pixels, x));
+            let mmk = _mm256_loadu_pd(coeffs.get_unchecked(x..).as_ptr());
+            sum0 = _mm256_fmadd_pd(pixels, mmk, sum0);
+            x += 4;
+        }
+
+        let mut ss = simd_utils::hsum_256_pd(_mm256_add_pd(sum0, sum1));
+        for (&k, &pixel) in coeffs
+            .get_unchecked(x..)
+            .iter()
+            .zip(src_pixels.get_unchecked(x..))
+        {
+            ss += pixel as f64 * k;
+        }
+        *dst_pixel = ss.round() as i32;
+    }
+}
+
+/// Pixels are accumulated as `f64` values, sixteen pixels
+/// per iteration of the main loop.
+#[target_feature(enable = "avx2,fma")]
+unsafe fn vert_convolution_row(
+    src_img: &TypedImageView<I32>,
+    dst_row: &mut [i32],
+    coeffs_chunk: CoefficientsChunk,
+) {
+    let y_start = coeffs_chunk.start;
+    let max_y = y_start + coeffs_chunk.values.len() as u32;
+
+    let mut x: usize = 0;
+    while x + 16 <= dst_row.len() {
+        let mut sss = [_mm256_setzero_pd(); 4];
+
+        let src_rows = src_img.iter_rows(y_start, max_y);
+        for (src_row, &k) in src_rows.zip(coeffs_chunk.values) {
+            let mmk = _mm256_set1_pd(k);
+            for (i, sum) in sss.iter_mut().enumerate() {
+                let source = simd_utils::loadu_si128(src_row, x + i * 4);
+                *sum = _mm256_fmadd_pd(_mm256_cvtepi32_pd(source), mmk, *sum);
+            }
+        }
+
+        for (i, &sum) in sss.iter().enumerate() {
+            store_rounded(dst_row.get_unchecked_mut(x + i * 4..x + i * 4 + 4), sum);
+        }
+        x += 16;
+    }
+
+    while x + 4 <= dst_row.len() {
+        let mut sum = _mm256_setzero_pd();
+
+        let src_rows = src_img.iter_rows(y_start, max_y);
+        for (src_row, &k) in src_rows.zip(coeffs_chunk.values) {
+            let source = simd_utils::loadu_si128(src_row, x);
+            sum = _mm256_fmadd_pd(_mm256_cvtepi32_pd(source), _mm256_set1_pd(k), sum);
+        }
+
+        store_rounded(dst_row.get_unchecked_mut(x..x + 4), sum);
+        x += 4;
+    }
+
+    sse4::vert_convolution_tail(src_img, dst_row, x, coeffs_chunk);
+}
+
+/// Rounds four sums to the nearest integers in the same way as native code.
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn store_rounded(dst_pixels: &mut [i32], sum: __m256d) {
+    let mut sums = [0f64; 4];
+    _mm256_storeu_pd(sums.as_mut_ptr(), sum);
+    for (dst_pixel, &ss) in dst_pixels.iter_mut().zip(sums.iter()) {
+        *dst_pixel = ss.round() as i32;
+    }
+}
@@ -0,0 +1,140 @@
+use std::arch::x86_64::*;
+
+use crate::convolution::{CoefficientsChunk, CoefficientsChunks, CoefficientsView};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::I32;
+use crate::simd_utils;
+
+#[inline]
+pub(crate) fn horiz_convolution(
+    src_image: TypedImageView<I32>,
+    mut dst_image: TypedImageViewMut<I32>,
+    offset: u32,
+    coeffs: CoefficientsView<f64>,
+) {
+    let coefficients_chunks = coeffs.chunks();
+    let dst_rows = dst_image.iter_rows_mut();
+    for (y_dst, dst_row) in dst_rows.enumerate() {
+        if let Some(src_row) = src_image.get_row(y_dst as u32 + offset) {
+            unsafe {
+                horiz_convolution_row(src_row, dst_row, coefficients_chunks.clone());
+            }
+        }
+    }
+}
+
+#[inline]
+pub(crate) fn vert_convolution(
+    src_image: TypedImageView<I32>,
+    mut dst_image: TypedImageViewMut<I32>,
+    coeffs: CoefficientsView<f64>,
+) {
+    let coefficients_chunks = coeffs.chunks();
+    let dst_rows = dst_image.iter_rows_mut();
+    for (dst_row, coeffs_chunk) in dst_rows.zip(coefficients_chunks) {
+        unsafe {
+            vert_convolution_row(&src_image, dst_row, coeffs_chunk);
+        }
+    }
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - coefficients_chunks.len() == dst_row.len()
+/// - max(chunk.start + chunk.values.len() for chunk in coefficients_chunks) <= src_row.len()
+#[target_feature(enable = "sse4.1")]
+unsafe fn horiz_convolution_row(
+    src_row: &[i32],
+    dst_row: &mut [i32],
+    coefficients_chunks: CoefficientsChunks<f64>,
+) {
+    for (dst_pixel, coeffs_chunk) in dst_row.iter_mut().zip(coefficients_chunks) {
+        let first_x_src = coeffs_chunk.start as usize;
+        let src_pixels = src_row.get_unchecked(first_x_src..);
+        let coeffs = coeffs_chunk.values;
+        let mut x: usize = 0;
+
+        // Pixels are accumulated as two pairs of `f64` values.
+        let mut sum_lo = _mm_setzero_pd();
+        let mut sum_hi = _mm_setzero_pd();
+        while x + 4 <= coeffs.len() {
+            let pixels = simd_utils::loadu_si128(src_pixels, x);
+            let lo = _mm_cvtepi32_pd(pixels);
+            let hi = _mm_cvtepi32_pd(_mm_unpackhi_epi64(pixels, pixels));
+            let mmk_lo = _mm_loadu_pd(coeffs.get_unchecked(x..).as_ptr());
+            let mmk_hi = _mm_loadu_pd(coeffs.get_unchecked(x + 2..).as_ptr());
+            sum_lo = _mm_add_pd(sum_lo, _mm_mul_pd(lo, mmk_lo));
+            sum_hi = _mm_add_pd(sum_hi, _mm_mul_pd(hi, mmk_hi));
+            x += 4;
+        }
+
+        let mut ss = simd_utils::hsum_pd(_mm_add_pd(sum_lo, sum_hi));
+        for (&k, &pixel) in coeffs
+            .get_unchecked(x..)
+            .iter()
+            .zip(src_pixels.get_unchecked(x..))
+        {
+            ss += pixel as f64 * k;
+        }
+        *dst_pixel = ss.round() as i32;
+    }
+}
+
+/// Pixels are accumulated as `f64` values, four pixels
+/// per iteration of the inner loop.
+#[target_feature(enable = "sse4.1")]
+unsafe fn vert_convolution_row(
+    src_img: &TypedImageView<I32>,
+    dst_row: &mut [i32],
+    coeffs_chunk: CoefficientsChunk,
+) {
+    let y_start = coeffs_chunk.start;
+    let max_y = y_start + coeffs_chunk.values.len() as u32;
+
+    let mut x: usize = 0;
+    while x + 4 <= dst_row.len() {
+        let mut sum_lo = _mm_setzero_pd();
+        let mut sum_hi = _mm_setzero_pd();
+
+        let src_rows = src_img.iter_rows(y_start, max_y);
+        for (src_row, &k) in src_rows.zip(coeffs_chunk.values) {
+            let mmk = _mm_set1_pd(k);
+            let source = simd_utils::loadu_si128(src_row, x);
+            let lo = _mm_cvtepi32_pd(source);
+            let hi = _mm_cvtepi32_pd(_mm_unpackhi_epi64(source, source));
+            sum_lo = _mm_add_pd(sum_lo, _mm_mul_pd(lo, mmk));
+            sum_hi = _mm_add_pd(sum_hi, _mm_mul_pd(hi, mmk));
+        }
+
+        let mut sums = [0f64; 4];
+        _mm_storeu_pd(sums.as_mut_ptr(), sum_lo);
+        _mm_storeu_pd(sums.as_mut_ptr().add(2), sum_hi);
+        let dst_pixels = dst_row.get_unchecked_mut(x..x + 4);
+        for (dst_pixel, &ss) in dst_pixels.iter_mut().zip(sums.iter()) {
+            *dst_pixel = ss.round() as i32;
+        }
+        x += 4;
+    }
+
+    vert_convolution_tail(src_img, dst_row, x, coeffs_chunk);
+}
+
+/// Calculates pixels of destination row starting from `x_start`
+/// without SIMD instructions.
+#[inline(always)]
+pub(crate) unsafe fn vert_convolution_tail(
+    src_img: &TypedImageView<I32>,
+    dst_row: &mut [i32],
+    x_start: usize,
+    coeffs_chunk: CoefficientsChunk,
+) {
+    let y_start = coeffs_chunk.start;
+    let max_y = y_start + coeffs_chunk.values.len() as u32;
+    for (x, dst_pixel) in dst_row.iter_mut().enumerate().skip(x_start) {
+        let mut ss = 0.;
+        let src_rows = src_img.iter_rows(y_start, max_y);
+        for (src_row, &k) in src_rows.zip(coeffs_chunk.values) {
+            ss += *src_row.get_unchecked(x) as f64 * k;
+        }
+        *dst_pixel = ss.round() as i32;
+    }
+}
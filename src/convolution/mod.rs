@@ -1,36 +1,52 @@
 use std::num::NonZeroU32;
+use std::ops::Range;
+use std::slice::{self, ChunksExact};
+use std::sync::Arc;
 
 use crate::image_view::{TypedImageView, TypedImageViewMut};
-use crate::pixels::Pixel;
+use crate::pixels::{Pixel, PixelType};
 use crate::CpuExtensions;
-pub use filters::{get_filter_func, FilterType};
+pub use filters::{get_filter_func, BicubicParams, Filter, FilterFunc, FilterType, GaussianParams};
 
+#[cfg(target_arch = "x86_64")]
 #[macro_use]
 mod macros;
 
 mod f32x1;
+mod f32x3;
+mod f32x4;
 mod filters;
 mod i32x1;
 mod optimisations;
+mod u16x1;
+mod u16x3;
+mod u16x4;
 mod u8x1;
+mod u8x2;
+mod u8x3;
 mod u8x4;
+mod vertical_f32;
+mod vertical_u8;
 
 pub(crate) trait Convolution
 where
     Self: Pixel + Sized,
 {
+    /// Type of values of coefficients used by convolution of pixels.
+    type Coefficient: CoefficientValue;
+
     fn horiz_convolution(
         src_image: TypedImageView<Self>,
         dst_image: TypedImageViewMut<Self>,
         offset: u32,
-        coeffs: Coefficients,
+        coeffs: CoefficientsView<Self::Coefficient>,
         cpu_extensions: CpuExtensions,
     );
 
     fn vert_convolution(
         src_image: TypedImageView<Self>,
         dst_image: TypedImageViewMut<Self>,
-        coeffs: Coefficients,
+        coeffs: CoefficientsView<Self::Coefficient>,
         cpu_extensions: CpuExtensions,
     );
 }
@@ -41,33 +57,287 @@ pub struct Bound {
     pub size: u32,
 }
 
+/// Coefficients of convolution for one axis of image.
+///
+/// Values of coefficients of every destination pixel take `window_size`
+/// items of `values`. Values of coefficients with fixed point have
+/// `precision` fractional bits (zero for `f64` values).
 #[derive(Debug, Clone)]
-pub struct Coefficients {
-    pub values: Vec<f64>,
+pub struct Coefficients<T = f64> {
+    pub values: Vec<T>,
     pub window_size: usize,
     pub bounds: Vec<Bound>,
+    pub precision: u8,
 }
 
-#[derive(Debug, Clone, Copy)]
-pub struct CoefficientsChunk<'a> {
+impl<T> Coefficients<T> {
+    #[inline]
+    pub fn view(&self) -> CoefficientsView<'_, T> {
+        CoefficientsView {
+            values: &self.values,
+            window_size: self.window_size,
+            bounds: &self.bounds,
+            precision: self.precision,
+            offset: 0,
+        }
+    }
+
+    /// Returns the size of coefficients in bytes.
+    fn size(&self) -> usize {
+        self.values.capacity() * std::mem::size_of::<T>()
+            + self.bounds.capacity() * std::mem::size_of::<Bound>()
+    }
+}
+
+/// Borrowed coefficients of some range of destination pixels.
+#[derive(Debug)]
+pub struct CoefficientsView<'a, T> {
+    values: &'a [T],
+    window_size: usize,
+    bounds: &'a [Bound],
+    precision: u8,
+    offset: u32,
+}
+
+// Implemented manually because derive requires `T: Clone`.
+impl<'a, T> Clone for CoefficientsView<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for CoefficientsView<'a, T> {}
+
+impl<'a, T> CoefficientsView<'a, T> {
+    /// Returns coefficients of given range of destination pixels.
+    #[inline]
+    pub fn slice(self, range: Range<usize>) -> Self {
+        Self {
+            values: &self.values[range.start * self.window_size..range.end * self.window_size],
+            bounds: &self.bounds[range],
+            ..self
+        }
+    }
+
+    /// Returns coefficients for source image that starts with
+    /// `offset`-th pixel of the original one.
+    #[inline]
+    pub fn with_offset(self, offset: u32) -> Self {
+        Self {
+            offset: self.offset + offset,
+            ..self
+        }
+    }
+
+    #[inline(always)]
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    #[inline]
+    pub fn chunks(&self) -> CoefficientsChunks<'a, T> {
+        CoefficientsChunks {
+            values: self.values.chunks_exact(self.window_size),
+            bounds: self.bounds.iter(),
+            offset: self.offset,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CoefficientsChunk<'a, T = f64> {
     pub start: u32,
-    pub values: &'a [f64],
-}
-
-impl Coefficients {
-    pub fn get_chunks(&self) -> Vec<CoefficientsChunk> {
-        let mut coeffs = self.values.as_slice();
-        let mut res = Vec::with_capacity(self.bounds.len());
-        for bound in &self.bounds {
-            let (left, right) = coeffs.split_at(self.window_size);
-            coeffs = right;
-            let size = bound.size as usize;
-            res.push(CoefficientsChunk {
-                start: bound.start,
-                values: &left[0..size],
-            });
+    pub values: &'a [T],
+}
+
+// Implemented manually because derive requires `T: Clone`.
+impl<'a, T> Clone for CoefficientsChunk<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for CoefficientsChunk<'a, T> {}
+
+impl<'a, T> CoefficientsChunk<'a, T> {
+    #[cfg(any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        all(target_arch = "wasm32", target_feature = "simd128")
+    ))]
+    #[inline(always)]
+    pub fn bound(&self) -> Bound {
+        Bound {
+            start: self.start,
+            size: self.values.len() as u32,
+        }
+    }
+}
+
+/// Iterator over coefficients of destination pixels.
+#[derive(Debug, Clone)]
+pub struct CoefficientsChunks<'a, T> {
+    values: ChunksExact<'a, T>,
+    bounds: slice::Iter<'a, Bound>,
+    offset: u32,
+}
+
+impl<'a, T> Iterator for CoefficientsChunks<'a, T> {
+    type Item = CoefficientsChunk<'a, T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let bound = self.bounds.next()?;
+        let values = self.values.next()?;
+        Some(CoefficientsChunk {
+            start: bound.start - self.offset,
+            values: &values[..bound.size as usize],
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.bounds.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for CoefficientsChunks<'a, T> {}
+
+/// Types of values of coefficients used by convolution: `f64` or integers
+/// with fixed point (see [optimisations]).
+pub(crate) trait CoefficientValue: Copy + Send + Sync + Sized + 'static {
+    /// Converts coefficients with `f64` values into values of this type.
+    fn from_f64(coeffs: Coefficients) -> Coefficients<Self>;
+
+    fn into_any(coeffs: Arc<Coefficients<Self>>) -> AnyCoefficients;
+
+    fn from_any(coeffs: &AnyCoefficients) -> Option<&Arc<Coefficients<Self>>>;
+}
+
+/// Coefficients with values of any type supported by convolution.
+#[derive(Debug, Clone)]
+pub(crate) enum AnyCoefficients {
+    F64(Arc<Coefficients<f64>>),
+    I16(Arc<Coefficients<i16>>),
+    I32(Arc<Coefficients<i32>>),
+}
+
+impl AnyCoefficients {
+    fn size(&self) -> usize {
+        match self {
+            Self::F64(coeffs) => coeffs.size(),
+            Self::I16(coeffs) => coeffs.size(),
+            Self::I32(coeffs) => coeffs.size(),
         }
-        res
+    }
+}
+
+macro_rules! coefficient_value {
+    ($type:ty, $variant:ident, $from_f64:expr) => {
+        impl CoefficientValue for $type {
+            #[inline]
+            fn from_f64(coeffs: Coefficients) -> Coefficients<Self> {
+                $from_f64(coeffs)
+            }
+
+            #[inline]
+            fn into_any(coeffs: Arc<Coefficients<Self>>) -> AnyCoefficients {
+                AnyCoefficients::$variant(coeffs)
+            }
+
+            #[inline]
+            fn from_any(coeffs: &AnyCoefficients) -> Option<&Arc<Coefficients<Self>>> {
+                match coeffs {
+                    AnyCoefficients::$variant(coeffs) => Some(coeffs),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+coefficient_value!(f64, F64, |coeffs| coeffs);
+coefficient_value!(i16, I16, optimisations::to_i16_coefficients);
+coefficient_value!(i32, I32, optimisations::to_i32_coefficients);
+
+/// Parameters of [precompute_coefficients] that define
+/// coefficients for one axis of image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CoefficientsKey {
+    in_size: NonZeroU32,
+    in0: f64,
+    in1: f64,
+    out_size: NonZeroU32,
+    filter_type: FilterType,
+    /// Type of pixels defines type and precision of values of coefficients.
+    pixel_type: PixelType,
+}
+
+/// Cache of coefficients of the last used horizontal and vertical passes.
+///
+/// Coefficients depend only on the sizes of images, the crop box,
+/// the filter and the type of pixels, so resizing of many images with
+/// the same parameters (e.g. frames of video) doesn't evaluate the filter
+/// function and doesn't convert coefficients into values with fixed
+/// point again. The cache holds several entries, so resizing of images
+/// with alternating sizes (e.g. by [Resizer::resize_many](crate::Resizer::resize_many))
+/// doesn't evict coefficients which will be used again.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CoefficientsCache {
+    /// Entries ordered from the least to the most recently used.
+    entries: Vec<(CoefficientsKey, AnyCoefficients)>,
+}
+
+impl CoefficientsCache {
+    const MAX_ENTRIES: usize = 8;
+
+    /// Returns coefficients for one axis of image. Horizontal and vertical
+    /// passes with the same parameters share the same coefficients.
+    pub fn coefficients<P: Convolution>(
+        &mut self,
+        in_size: NonZeroU32,
+        in0: f64,
+        in1: f64,
+        out_size: NonZeroU32,
+        filter_type: FilterType,
+    ) -> Arc<Coefficients<P::Coefficient>> {
+        let key = CoefficientsKey {
+            in_size,
+            in0,
+            in1,
+            out_size,
+            filter_type,
+            pixel_type: P::pixel_type(),
+        };
+        if let Some(pos) = self.entries.iter().position(|(k, _)| *k == key) {
+            let entry = self.entries.remove(pos);
+            if let Some(coeffs) = P::Coefficient::from_any(&entry.1).cloned() {
+                self.entries.push(entry);
+                return coeffs;
+            }
+        }
+
+        let (filter_fn, filter_support) = get_filter_func(key.filter_type);
+        let coeffs = precompute_coefficients(
+            key.in_size,
+            key.in0,
+            key.in1,
+            key.out_size,
+            filter_fn,
+            filter_support,
+        );
+        let coeffs = Arc::new(P::Coefficient::from_f64(coeffs));
+        if self.entries.len() == Self::MAX_ENTRIES {
+            self.entries.remove(0);
+        }
+        self.entries
+            .push((key, P::Coefficient::into_any(coeffs.clone())));
+        coeffs
+    }
+
+    /// Returns the size of cached coefficients in bytes.
+    pub fn size(&self) -> usize {
+        self.entries.iter().map(|(_, coeffs)| coeffs.size()).sum()
     }
 }
 
@@ -76,7 +346,7 @@ pub fn precompute_coefficients(
     in0: f64, // Left border for cropping
     in1: f64, // Right border for cropping
     out_size: NonZeroU32,
-    filter: &dyn Fn(f64) -> f64,
+    filter: FilterFunc,
     filter_support: f64,
 ) -> Coefficients {
     let in_size = in_size.get();
@@ -117,7 +387,7 @@ pub fn precompute_coefficients(
         let center = in_center - 0.5;
 
         for x in x_min..x_max {
-            let w: f64 = filter((x as f64 - center) * recip_filter_scale);
+            let w: f64 = filter.call((x as f64 - center) * recip_filter_scale);
             coeffs.push(w);
             ww += w;
         }
@@ -136,5 +406,6 @@ pub fn precompute_coefficients(
         values: coeffs,
         window_size,
         bounds,
+        precision: 0,
     }
 }
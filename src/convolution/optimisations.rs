@@ -1,6 +1,4 @@
-use std::slice;
-
-use super::Bound;
+use super::Coefficients;
 
 // This code is based on C-implementation from Pillow-SIMD package for Python
 // https://github.com/uploadcare/pillow-simd
@@ -70,6 +68,14 @@ const PRECISION_BITS: u8 = 32 - 8 - 2;
 // We use i16 type to store coefficients.
 const MAX_COEFS_PRECISION: u8 = 16 - 1;
 
+// 16 bits for result. Filter can have negative areas.
+// In one cases the sum of the coefficients will be negative,
+// in the other it will be more than 1.0. That is why we need
+// two extra bits for overflow and i64 type.
+const PRECISION16_BITS: u8 = 64 - 16 - 2;
+// We use i32 type to store coefficients.
+const MAX_COEFS_PRECISION16: u8 = 32 - 1;
+
 /// # Safety
 /// The function must be used with the `v` and `precision` values
 /// such that the expression `v >> precision`
@@ -81,81 +87,64 @@ pub(crate) unsafe fn clip8(v: i32, precision: u8) -> u8 {
     *CLIP8_LOOKUPS.get_unchecked(index)
 }
 
-/// Converts `Vec<f64>` into `&[i16]` without additional memory allocations.
-/// The memory buffer from `Vec<f64>` uses as `[i16]` .
-pub struct NormalizerGuard {
-    values: Vec<f64>,
-    precision: u8,
-}
-
-#[derive(Debug, Clone, Copy)]
-pub struct CoefficientsI16Chunk<'a> {
-    pub start: u32,
-    pub values: &'a [i16],
+/// Converts coefficients into `i16` values with fixed point.
+/// It is used for convolution of pixels with 8-bit components.
+pub(crate) fn to_i16_coefficients(coeffs: Coefficients) -> Coefficients<i16> {
+    let precision = calc_precision(&coeffs.values, PRECISION_BITS, MAX_COEFS_PRECISION);
+    let scale = (1 << precision) as f64;
+    Coefficients {
+        values: coeffs
+            .values
+            .iter()
+            .map(|&v| (v * scale).round() as i16)
+            .collect(),
+        window_size: coeffs.window_size,
+        bounds: coeffs.bounds,
+        precision,
+    }
 }
 
-impl NormalizerGuard {
-    #[inline]
-    pub fn new(mut values: Vec<f64>) -> Self {
-        let max_weight = values
+/// Converts coefficients into `i32` values with fixed point.
+/// It is used for convolution of pixels with 16-bit components.
+pub(crate) fn to_i32_coefficients(coeffs: Coefficients) -> Coefficients<i32> {
+    let precision = calc_precision(&coeffs.values, PRECISION16_BITS, MAX_COEFS_PRECISION16);
+    let scale = (1i64 << precision) as f64;
+    Coefficients {
+        values: coeffs
+            .values
             .iter()
-            .max_by(|&x, &y| x.partial_cmp(&y).unwrap())
-            .unwrap_or(&0.0)
-            .to_owned();
-
-        let mut precision = 0u8;
-        for cur_precision in 0..PRECISION_BITS {
-            precision = cur_precision;
-            let next_value: i32 = (max_weight * (1 << (precision + 1)) as f64).round() as i32;
-            // The next value will be outside of the range, so just stop
-            if next_value >= (1 << MAX_COEFS_PRECISION) {
-                break;
-            }
-        }
-
-        let len = values.len();
-        let ptr = values.as_mut_ptr();
-        // Size of `[i16]` always will be not greater than `[f64]` with same number of items
-        let values_i16 = unsafe { slice::from_raw_parts_mut(ptr as *mut i16, len) };
-
-        let scale = (1 << precision) as f64;
-        for (&src, dst) in values.iter().zip(values_i16.iter_mut()) {
-            *dst = (src * scale).round() as i16;
-        }
-        Self { values, precision }
+            .map(|&v| (v * scale).round() as i32)
+            .collect(),
+        window_size: coeffs.window_size,
+        bounds: coeffs.bounds,
+        precision,
     }
+}
 
-    #[inline]
-    pub fn normalized_i16(&self) -> &[i16] {
-        let len = self.values.len();
-        let ptr = self.values.as_ptr();
-        unsafe { slice::from_raw_parts(ptr as *const i16, len) }
-    }
+/// Converts sum of products of 16-bit components and coefficients
+/// with fixed point into component.
+#[inline(always)]
+pub(crate) fn clip16(v: i64, precision: u8) -> u16 {
+    (v >> precision).clamp(0, u16::MAX as i64) as u16
+}
 
-    #[inline]
-    pub fn normalized_i16_chunks(
-        &self,
-        window_size: usize,
-        bounds: &[Bound],
-    ) -> Vec<CoefficientsI16Chunk> {
-        let len = self.values.len();
-        let ptr = self.values.as_ptr();
-        let mut cooefs = unsafe { slice::from_raw_parts(ptr as *const i16, len) };
-        let mut res = Vec::with_capacity(bounds.len());
-        for bound in bounds {
-            let (left, right) = cooefs.split_at(window_size);
-            cooefs = right;
-            let size = bound.size as usize;
-            res.push(CoefficientsI16Chunk {
-                start: bound.start,
-                values: &left[0..size],
-            });
-        }
-        res
-    }
+/// Returns max precision of coefficients with fixed point
+/// which doesn't overflow `max_coefs_precision` bits.
+fn calc_precision(values: &[f64], precision_bits: u8, max_coefs_precision: u8) -> u8 {
+    let max_weight = values
+        .iter()
+        .max_by(|&x, &y| x.partial_cmp(y).unwrap())
+        .unwrap_or(&0.0)
+        .to_owned();
 
-    #[inline]
-    pub fn precision(&self) -> u8 {
-        self.precision
+    let mut precision = 0u8;
+    for cur_precision in 0..precision_bits {
+        precision = cur_precision;
+        let next_value: i64 = (max_weight * (1i64 << (precision + 1)) as f64).round() as i64;
+        // The next value will be outside of the range, so just stop
+        if next_value >= (1i64 << max_coefs_precision) {
+            break;
+        }
     }
+    precision
 }
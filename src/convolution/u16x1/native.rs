@@ -0,0 +1,56 @@
+use crate::convolution::{optimisations, CoefficientsView};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::U16;
+
+pub(crate) fn horiz_convolution(
+    src_image: TypedImageView<U16>,
+    mut dst_image: TypedImageViewMut<U16>,
+    offset: u32,
+    coeffs: CoefficientsView<i32>,
+) {
+    let precision = coeffs.precision();
+    let coefficients_chunks = coeffs.chunks();
+    let initial = 1 << (precision - 1);
+
+    let dst_rows = dst_image.iter_rows_mut();
+    for (y_dst, dst_row) in dst_rows.enumerate() {
+        let y_src = y_dst as u32 + offset;
+
+        for (coeffs_chunk, dst_pixel) in coefficients_chunks.clone().zip(dst_row.iter_mut()) {
+            let first_x_src = coeffs_chunk.start;
+            let ks = coeffs_chunk.values;
+
+            let mut ss = initial;
+            let src_pixels = src_image.iter_horiz(first_x_src, y_src);
+            for (&k, &src_pixel) in ks.iter().zip(src_pixels) {
+                ss += src_pixel as i64 * (k as i64);
+            }
+            *dst_pixel = optimisations::clip16(ss, precision);
+        }
+    }
+}
+
+pub(crate) fn vert_convolution(
+    src_image: TypedImageView<U16>,
+    mut dst_image: TypedImageViewMut<U16>,
+    coeffs: CoefficientsView<i32>,
+) {
+    let precision = coeffs.precision();
+    let coefficients_chunks = coeffs.chunks();
+    let initial = 1 << (precision - 1);
+
+    let dst_rows = dst_image.iter_rows_mut();
+    for (coeffs_chunk, dst_row) in coefficients_chunks.zip(dst_rows) {
+        let first_y_src = coeffs_chunk.start;
+        let ks = coeffs_chunk.values;
+
+        for (x_src, dst_pixel) in dst_row.iter_mut().enumerate() {
+            let mut ss = initial;
+            for (dy, &k) in ks.iter().enumerate() {
+                let src_pixel = src_image.get_pixel(x_src as u32, first_y_src + dy as u32);
+                ss += src_pixel as i64 * (k as i64);
+            }
+            *dst_pixel = optimisations::clip16(ss, precision);
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use super::{CoefficientsView, Convolution};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::U16x4;
+use crate::CpuExtensions;
+
+mod native;
+
+impl Convolution for U16x4 {
+    type Coefficient = i32;
+
+    fn horiz_convolution(
+        src_image: TypedImageView<Self>,
+        dst_image: TypedImageViewMut<Self>,
+        offset: u32,
+        coeffs: CoefficientsView<i32>,
+        _cpu_extensions: CpuExtensions,
+    ) {
+        native::horiz_convolution(src_image, dst_image, offset, coeffs);
+    }
+
+    fn vert_convolution(
+        src_image: TypedImageView<Self>,
+        dst_image: TypedImageViewMut<Self>,
+        coeffs: CoefficientsView<i32>,
+        _cpu_extensions: CpuExtensions,
+    ) {
+        native::vert_convolution(src_image, dst_image, coeffs);
+    }
+}
@@ -1,27 +1,63 @@
-use super::{Coefficients, Convolution};
+#[cfg(target_arch = "x86_64")]
+use super::vertical_u8;
+use super::{CoefficientsView, Convolution};
 use crate::image_view::{TypedImageView, TypedImageViewMut};
 use crate::pixels::U8;
 use crate::CpuExtensions;
 
+#[cfg(target_arch = "x86_64")]
+mod avx2;
 mod native;
+#[cfg(target_arch = "aarch64")]
+mod neon;
+#[cfg(target_arch = "x86_64")]
+mod sse4;
 
 impl Convolution for U8 {
+    type Coefficient = i16;
+
     fn horiz_convolution(
         src_image: TypedImageView<Self>,
         dst_image: TypedImageViewMut<Self>,
         offset: u32,
-        coeffs: Coefficients,
-        _cpu_extensions: CpuExtensions,
+        coeffs: CoefficientsView<i16>,
+        cpu_extensions: CpuExtensions,
     ) {
-        native::horiz_convolution(src_image, dst_image, offset, coeffs);
+        match cpu_extensions {
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Avx2 => avx2::horiz_convolution(src_image, dst_image, offset, coeffs),
+            #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+            CpuExtensions::Avx512 => avx2::horiz_convolution(src_image, dst_image, offset, coeffs),
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Sse4_1 => sse4::horiz_convolution(src_image, dst_image, offset, coeffs),
+            #[cfg(target_arch = "aarch64")]
+            CpuExtensions::Neon => neon::horiz_convolution(src_image, dst_image, offset, coeffs),
+            _ => native::horiz_convolution(src_image, dst_image, offset, coeffs),
+        }
     }
 
     fn vert_convolution(
         src_image: TypedImageView<Self>,
         dst_image: TypedImageViewMut<Self>,
-        coeffs: Coefficients,
-        _cpu_extensions: CpuExtensions,
+        coeffs: CoefficientsView<i16>,
+        cpu_extensions: CpuExtensions,
     ) {
-        native::vert_convolution(src_image, dst_image, coeffs);
+        match cpu_extensions {
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Avx2 => {
+                vertical_u8::avx2::vert_convolution(src_image, dst_image, coeffs)
+            }
+            #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+            CpuExtensions::Avx512 => {
+                vertical_u8::avx2::vert_convolution(src_image, dst_image, coeffs)
+            }
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Sse4_1 => {
+                vertical_u8::sse4::vert_convolution(src_image, dst_image, coeffs)
+            }
+            #[cfg(target_arch = "aarch64")]
+            CpuExtensions::Neon => neon::vert_convolution(src_image, dst_image, coeffs),
+            _ => native::vert_convolution(src_image, dst_image, coeffs),
+        }
     }
 }
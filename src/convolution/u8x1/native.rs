@@ -1,4 +1,4 @@
-use crate::convolution::{optimisations, Coefficients};
+use crate::convolution::{optimisations, CoefficientsView};
 use crate::image_view::{TypedImageView, TypedImageViewMut};
 use crate::pixels::U8;
 
@@ -6,19 +6,16 @@ pub(crate) fn horiz_convolution(
     src_image: TypedImageView<U8>,
     mut dst_image: TypedImageViewMut<U8>,
     offset: u32,
-    coeffs: Coefficients,
+    coeffs: CoefficientsView<i16>,
 ) {
-    let (values, window_size, bounds) = (coeffs.values, coeffs.window_size, coeffs.bounds);
-
-    let normalizer_guard = optimisations::NormalizerGuard::new(values);
-    let precision = normalizer_guard.precision();
-    let coefficients_chunks = normalizer_guard.normalized_i16_chunks(window_size, &bounds);
+    let precision = coeffs.precision();
+    let coefficients_chunks = coeffs.chunks();
 
     let dst_rows = dst_image.iter_rows_mut();
     for (y_dst, dst_row) in dst_rows.enumerate() {
         let y_src = y_dst as u32 + offset;
 
-        for (&coeffs_chunk, dst_pixel) in coefficients_chunks.iter().zip(dst_row.iter_mut()) {
+        for (coeffs_chunk, dst_pixel) in coefficients_chunks.clone().zip(dst_row.iter_mut()) {
             let first_x_src = coeffs_chunk.start;
             let ks = coeffs_chunk.values;
 
@@ -35,16 +32,13 @@ pub(crate) fn horiz_convolution(
 pub(crate) fn vert_convolution(
     src_image: TypedImageView<U8>,
     mut dst_image: TypedImageViewMut<U8>,
-    coeffs: Coefficients,
+    coeffs: CoefficientsView<i16>,
 ) {
-    let (values, window_size, bounds) = (coeffs.values, coeffs.window_size, coeffs.bounds);
-
-    let normalizer_guard = optimisations::NormalizerGuard::new(values);
-    let precision = normalizer_guard.precision();
-    let coefficients_chunks = normalizer_guard.normalized_i16_chunks(window_size, &bounds);
+    let precision = coeffs.precision();
+    let coefficients_chunks = coeffs.chunks();
 
     let dst_rows = dst_image.iter_rows_mut();
-    for (&coeffs_chunk, dst_row) in coefficients_chunks.iter().zip(dst_rows) {
+    for (coeffs_chunk, dst_row) in coefficients_chunks.zip(dst_rows) {
         let first_y_src = coeffs_chunk.start;
         let ks = coeffs_chunk.values;
 
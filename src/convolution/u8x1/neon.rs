@@ -0,0 +1,70 @@
+use std::arch::aarch64::*;
+
+use crate::convolution::{optimisations, vertical_u8, CoefficientsChunks, CoefficientsView};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::U8;
+
+#[inline]
+pub(crate) fn horiz_convolution(
+    src_image: TypedImageView<U8>,
+    mut dst_image: TypedImageViewMut<U8>,
+    offset: u32,
+    coeffs: CoefficientsView<i16>,
+) {
+    let precision = coeffs.precision();
+    let coefficients_chunks = coeffs.chunks();
+
+    let dst_rows = dst_image.iter_rows_mut();
+    for (y_dst, dst_row) in dst_rows.enumerate() {
+        if let Some(src_row) = src_image.get_row(y_dst as u32 + offset) {
+            unsafe {
+                horiz_convolution_8u(src_row, dst_row, coefficients_chunks.clone(), precision);
+            }
+        }
+    }
+}
+
+#[inline]
+pub(crate) fn vert_convolution(
+    src_image: TypedImageView<U8>,
+    dst_image: TypedImageViewMut<U8>,
+    coeffs: CoefficientsView<i16>,
+) {
+    vertical_u8::neon::vert_convolution(src_image, dst_image, coeffs);
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - coefficients_chunks.len() == dst_row.len()
+/// - max(chunk.start + chunk.values.len() for chunk in coefficients_chunks) <= src_row.len()
+/// - precision <= MAX_COEFS_PRECISION
+#[target_feature(enable = "neon")]
+unsafe fn horiz_convolution_8u(
+    src_row: &[u8],
+    dst_row: &mut [u8],
+    coefficients_chunks: CoefficientsChunks<i16>,
+    precision: u8,
+) {
+    for (dst_x, coeffs_chunk) in coefficients_chunks.enumerate() {
+        let x_start = coeffs_chunk.start as usize;
+        let ks = coeffs_chunk.values;
+        let mut sss = vdupq_n_s32(0);
+        let mut x: usize = 0;
+
+        // Eight pixels per iteration
+        while x < ks.len().saturating_sub(7) {
+            let src_ptr = src_row.get_unchecked(x_start + x..).as_ptr();
+            let pix = vreinterpretq_s16_u16(vmovl_u8(vld1_u8(src_ptr)));
+            let mmk = vld1q_s16(ks.get_unchecked(x..).as_ptr());
+            sss = vmlal_s16(sss, vget_low_s16(pix), vget_low_s16(mmk));
+            sss = vmlal_s16(sss, vget_high_s16(pix), vget_high_s16(mmk));
+            x += 8;
+        }
+
+        let mut ss0 = vaddvq_s32(sss) + (1 << (precision - 1));
+        let src_pixels = src_row.get_unchecked(x_start + x..);
+        for (&k, &pixel) in ks.get_unchecked(x..).iter().zip(src_pixels) {
+            ss0 += pixel as i32 * (k as i32);
+        }
+        *dst_row.get_unchecked_mut(dst_x) = optimisations::clip8(ss0, precision);
+    }
+}
@@ -0,0 +1,203 @@
+use std::arch::x86_64::*;
+
+use crate::convolution::{optimisations, CoefficientsChunks, CoefficientsView};
+use crate::image_view::{FourRows, FourRowsMut, TypedImageView, TypedImageViewMut};
+use crate::pixels::U8;
+use crate::simd_utils;
+
+#[inline]
+pub(crate) fn horiz_convolution(
+    src_image: TypedImageView<U8>,
+    mut dst_image: TypedImageViewMut<U8>,
+    offset: u32,
+    coeffs: CoefficientsView<i16>,
+) {
+    let precision = coeffs.precision();
+    let coefficients_chunks = coeffs.chunks();
+    let dst_height = dst_image.height().get();
+
+    let src_iter = src_image.iter_4_rows(offset, dst_height + offset);
+    let dst_iter = dst_image.iter_4_rows_mut();
+    for (src_rows, dst_rows) in src_iter.zip(dst_iter) {
+        unsafe {
+            horiz_convolution_8u4x(src_rows, dst_rows, coefficients_chunks.clone(), precision);
+        }
+    }
+
+    let mut yy = dst_height - dst_height % 4;
+    while yy < dst_height {
+        unsafe {
+            horiz_convolution_8u(
+                src_image.get_row(yy + offset).unwrap(),
+                dst_image.get_row_mut(yy).unwrap(),
+                coefficients_chunks.clone(),
+                precision,
+            );
+        }
+        yy += 1;
+    }
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - length of all rows in src_rows must be equal
+/// - length of all rows in dst_rows must be equal
+/// - coefficients_chunks.len() == dst_rows.0.len()
+/// - max(chunk.start + chunk.values.len() for chunk in coefficients_chunks) <= src_row.0.len()
+/// - precision <= MAX_COEFS_PRECISION
+#[target_feature(enable = "sse4.1")]
+unsafe fn horiz_convolution_8u4x(
+    src_rows: FourRows<u8>,
+    dst_rows: FourRowsMut<u8>,
+    coefficients_chunks: CoefficientsChunks<i16>,
+    precision: u8,
+) {
+    let (s_row0, s_row1, s_row2, s_row3) = src_rows;
+    let (d_row0, d_row1, d_row2, d_row3) = dst_rows;
+    let initial = _mm_set1_epi32(1 << (precision - 1));
+
+    for (dst_x, coeffs_chunk) in coefficients_chunks.enumerate() {
+        let x_start = coeffs_chunk.start as usize;
+        let coeffs = coeffs_chunk.values;
+        let mut x: usize = 0;
+
+        let mut sss0 = _mm_setzero_si128();
+        let mut sss1 = _mm_setzero_si128();
+        let mut sss2 = _mm_setzero_si128();
+        let mut sss3 = _mm_setzero_si128();
+
+        while x + 8 <= coeffs.len() {
+            // [16] k7 k6 k5 k4 k3 k2 k1 k0
+            let mmk = simd_utils::loadu_si128(coeffs, x);
+
+            // [16] p7 p6 p5 p4 p3 p2 p1 p0
+            let mut pix = _mm_cvtepu8_epi16(simd_utils::loadl_epi64(s_row0, x_start + x));
+            sss0 = _mm_add_epi32(sss0, _mm_madd_epi16(pix, mmk));
+
+            pix = _mm_cvtepu8_epi16(simd_utils::loadl_epi64(s_row1, x_start + x));
+            sss1 = _mm_add_epi32(sss1, _mm_madd_epi16(pix, mmk));
+
+            pix = _mm_cvtepu8_epi16(simd_utils::loadl_epi64(s_row2, x_start + x));
+            sss2 = _mm_add_epi32(sss2, _mm_madd_epi16(pix, mmk));
+
+            pix = _mm_cvtepu8_epi16(simd_utils::loadl_epi64(s_row3, x_start + x));
+            sss3 = _mm_add_epi32(sss3, _mm_madd_epi16(pix, mmk));
+
+            x += 8;
+        }
+
+        if x + 4 <= coeffs.len() {
+            // [16] 0 0 0 0 k3 k2 k1 k0
+            let mmk = simd_utils::loadl_epi64(coeffs, x);
+
+            // [16] 0 0 0 0 p3 p2 p1 p0
+            let mut pix = _mm_cvtepu8_epi16(simd_utils::load_four_u8(s_row0, x_start + x));
+            sss0 = _mm_add_epi32(sss0, _mm_madd_epi16(pix, mmk));
+
+            pix = _mm_cvtepu8_epi16(simd_utils::load_four_u8(s_row1, x_start + x));
+            sss1 = _mm_add_epi32(sss1, _mm_madd_epi16(pix, mmk));
+
+            pix = _mm_cvtepu8_epi16(simd_utils::load_four_u8(s_row2, x_start + x));
+            sss2 = _mm_add_epi32(sss2, _mm_madd_epi16(pix, mmk));
+
+            pix = _mm_cvtepu8_epi16(simd_utils::load_four_u8(s_row3, x_start + x));
+            sss3 = _mm_add_epi32(sss3, _mm_madd_epi16(pix, mmk));
+
+            x += 4;
+        }
+
+        // [32] s3 s2 s1 s0 - one sum per row
+        let mut sss = _mm_hadd_epi32(_mm_hadd_epi32(sss0, sss1), _mm_hadd_epi32(sss2, sss3));
+        sss = _mm_add_epi32(sss, initial);
+        sss = add_last_pixels_4x(sss, src_rows, x_start + x, coeffs.get_unchecked(x..));
+        let [p0, p1, p2, p3] = pack_sums(sss, precision);
+
+        *d_row0.get_unchecked_mut(dst_x) = p0;
+        *d_row1.get_unchecked_mut(dst_x) = p1;
+        *d_row2.get_unchecked_mut(dst_x) = p2;
+        *d_row3.get_unchecked_mut(dst_x) = p3;
+    }
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - coefficients_chunks.len() == dst_row.len()
+/// - max(chunk.start + chunk.values.len() for chunk in coefficients_chunks) <= src_row.len()
+/// - precision <= MAX_COEFS_PRECISION
+#[target_feature(enable = "sse4.1")]
+pub(crate) unsafe fn horiz_convolution_8u(
+    src_row: &[u8],
+    dst_row: &mut [u8],
+    coefficients_chunks: CoefficientsChunks<i16>,
+    precision: u8,
+) {
+    for (dst_x, coeffs_chunk) in coefficients_chunks.enumerate() {
+        let x_start = coeffs_chunk.start as usize;
+        let coeffs = coeffs_chunk.values;
+        let mut x: usize = 0;
+
+        let mut sss = _mm_setzero_si128();
+
+        while x + 8 <= coeffs.len() {
+            let mmk = simd_utils::loadu_si128(coeffs, x);
+            let pix = _mm_cvtepu8_epi16(simd_utils::loadl_epi64(src_row, x_start + x));
+            sss = _mm_add_epi32(sss, _mm_madd_epi16(pix, mmk));
+            x += 8;
+        }
+
+        if x + 4 <= coeffs.len() {
+            let mmk = simd_utils::loadl_epi64(coeffs, x);
+            let pix = _mm_cvtepu8_epi16(simd_utils::load_four_u8(src_row, x_start + x));
+            sss = _mm_add_epi32(sss, _mm_madd_epi16(pix, mmk));
+            x += 4;
+        }
+
+        sss = _mm_hadd_epi32(sss, sss);
+        sss = _mm_hadd_epi32(sss, sss);
+        let mut ss0 = _mm_cvtsi128_si32(sss) + (1 << (precision - 1));
+
+        let src_pixels = src_row.get_unchecked(x_start + x..);
+        for (&k, &pixel) in coeffs.get_unchecked(x..).iter().zip(src_pixels) {
+            ss0 += pixel as i32 * (k as i32);
+        }
+        *dst_row.get_unchecked_mut(dst_x) = optimisations::clip8(ss0, precision);
+    }
+}
+
+/// Adds products of the last (less than four) coefficients
+/// and pixels to sums of four rows.
+#[inline]
+#[target_feature(enable = "sse4.1")]
+pub(crate) unsafe fn add_last_pixels_4x(
+    mut sss: __m128i,
+    src_rows: FourRows<u8>,
+    x_start: usize,
+    coeffs: &[i16],
+) -> __m128i {
+    let (s_row0, s_row1, s_row2, s_row3) = src_rows;
+    for (i, &k) in coeffs.iter().enumerate() {
+        let x = x_start + i;
+        let pix = _mm_set_epi32(
+            *s_row3.get_unchecked(x) as i32,
+            *s_row2.get_unchecked(x) as i32,
+            *s_row1.get_unchecked(x) as i32,
+            *s_row0.get_unchecked(x) as i32,
+        );
+        sss = _mm_add_epi32(sss, _mm_mullo_epi32(pix, _mm_set1_epi32(k as i32)));
+    }
+    sss
+}
+
+/// Shifts four sums to the right by `precision` bits and packs
+/// them into bytes with saturation.
+#[inline]
+#[target_feature(enable = "sse4.1")]
+pub(crate) unsafe fn pack_sums(mut sss: __m128i, precision: u8) -> [u8; 4] {
+    macro_rules! call {
+        ($imm8:expr) => {{
+            sss = _mm_srai_epi32::<$imm8>(sss);
+        }};
+    }
+    constify_imm8!(precision, call);
+
+    sss = _mm_packs_epi32(sss, sss);
+    (_mm_cvtsi128_si32(_mm_packus_epi16(sss, sss)) as u32).to_le_bytes()
+}
@@ -0,0 +1,179 @@
+use std::arch::x86_64::*;
+
+use super::sse4;
+use crate::convolution::{CoefficientsChunks, CoefficientsView};
+use crate::image_view::{FourRows, FourRowsMut, TypedImageView, TypedImageViewMut};
+use crate::pixels::U8x2;
+use crate::simd_utils;
+
+#[inline]
+pub(crate) fn horiz_convolution(
+    src_image: TypedImageView<U8x2>,
+    mut dst_image: TypedImageViewMut<U8x2>,
+    offset: u32,
+    coeffs: CoefficientsView<i16>,
+) {
+    let precision = coeffs.precision();
+    let coefficients_chunks = coeffs.chunks();
+    let dst_height = dst_image.height().get();
+
+    let src_iter = src_image.iter_4_rows(offset, dst_height + offset);
+    let dst_iter = dst_image.iter_4_rows_mut();
+    for (src_rows, dst_rows) in src_iter.zip(dst_iter) {
+        unsafe {
+            horiz_convolution_8u4x(src_rows, dst_rows, coefficients_chunks.clone(), precision);
+        }
+    }
+
+    let mut yy = dst_height - dst_height % 4;
+    while yy < dst_height {
+        unsafe {
+            sse4::horiz_convolution_8u(
+                src_image.get_row(yy + offset).unwrap(),
+                dst_image.get_row_mut(yy).unwrap(),
+                coefficients_chunks.clone(),
+                precision,
+            );
+        }
+        yy += 1;
+    }
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - length of all rows in src_rows must be equal
+/// - length of all rows in dst_rows must be equal
+/// - coefficients_chunks.len() == dst_rows.0.len()
+/// - max(chunk.start + chunk.values.len() for chunk in coefficients_chunks) <= src_row.0.len()
+/// - precision <= MAX_COEFS_PRECISION
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn horiz_convolution_8u4x(
+    src_rows: FourRows<[u8; 2]>,
+    dst_rows: FourRowsMut<[u8; 2]>,
+    coefficients_chunks: CoefficientsChunks<i16>,
+    precision: u8,
+) {
+    let (s_row0, s_row1, s_row2, s_row3) = src_rows;
+    let (d_row0, d_row1, d_row2, d_row3) = dst_rows;
+    let half = 1 << (precision - 1);
+    let initial = _mm256_set_epi32(0, 0, half, half, 0, 0, half, half);
+
+    #[rustfmt::skip]
+    let mask_lo = _mm256_set_epi8(
+        -1, 7, -1, 5, -1, 6, -1, 4, -1, 3, -1, 1, -1, 2, -1, 0,
+        -1, 7, -1, 5, -1, 6, -1, 4, -1, 3, -1, 1, -1, 2, -1, 0,
+    );
+    #[rustfmt::skip]
+    let mask_hi = _mm256_set_epi8(
+        -1, 15, -1, 13, -1, 14, -1, 12, -1, 11, -1, 9, -1, 10, -1, 8,
+        -1, 15, -1, 13, -1, 14, -1, 12, -1, 11, -1, 9, -1, 10, -1, 8,
+    );
+
+    for (dst_x, coeffs_chunk) in coefficients_chunks.enumerate() {
+        let x_start = coeffs_chunk.start as usize;
+        let coeffs = coeffs_chunk.values;
+        let mut x: usize = 0;
+
+        // Every register contains sums for two rows.
+        let mut sss0 = initial;
+        let mut sss1 = initial;
+
+        while x + 8 <= coeffs.len() {
+            let mmk_lo = _mm256_broadcastsi128_si256(sse4::coeffs_4x(coeffs, x));
+            let mmk_hi = _mm256_broadcastsi128_si256(sse4::coeffs_4x(coeffs, x + 4));
+
+            let mut source = _mm256_inserti128_si256::<1>(
+                _mm256_castsi128_si256(simd_utils::loadu_si128(s_row0, x + x_start)),
+                simd_utils::loadu_si128(s_row1, x + x_start),
+            );
+            let mut pix = _mm256_shuffle_epi8(source, mask_lo);
+            sss0 = _mm256_add_epi32(sss0, _mm256_madd_epi16(pix, mmk_lo));
+            pix = _mm256_shuffle_epi8(source, mask_hi);
+            sss0 = _mm256_add_epi32(sss0, _mm256_madd_epi16(pix, mmk_hi));
+
+            source = _mm256_inserti128_si256::<1>(
+                _mm256_castsi128_si256(simd_utils::loadu_si128(s_row2, x + x_start)),
+                simd_utils::loadu_si128(s_row3, x + x_start),
+            );
+            pix = _mm256_shuffle_epi8(source, mask_lo);
+            sss1 = _mm256_add_epi32(sss1, _mm256_madd_epi16(pix, mmk_lo));
+            pix = _mm256_shuffle_epi8(source, mask_hi);
+            sss1 = _mm256_add_epi32(sss1, _mm256_madd_epi16(pix, mmk_hi));
+
+            x += 8;
+        }
+
+        if x + 4 <= coeffs.len() {
+            let mmk = _mm256_broadcastsi128_si256(sse4::coeffs_4x(coeffs, x));
+
+            let mut pix = _mm256_inserti128_si256::<1>(
+                _mm256_castsi128_si256(simd_utils::loadl_epi64(s_row0, x + x_start)),
+                simd_utils::loadl_epi64(s_row1, x + x_start),
+            );
+            pix = _mm256_shuffle_epi8(pix, mask_lo);
+            sss0 = _mm256_add_epi32(sss0, _mm256_madd_epi16(pix, mmk));
+
+            pix = _mm256_inserti128_si256::<1>(
+                _mm256_castsi128_si256(simd_utils::loadl_epi64(s_row2, x + x_start)),
+                simd_utils::loadl_epi64(s_row3, x + x_start),
+            );
+            pix = _mm256_shuffle_epi8(pix, mask_lo);
+            sss1 = _mm256_add_epi32(sss1, _mm256_madd_epi16(pix, mmk));
+
+            x += 4;
+        }
+
+        if x + 2 <= coeffs.len() {
+            let mmk = simd_utils::ptr_i16_to_256set1_epi32(coeffs, x);
+
+            let mut pix = _mm256_inserti128_si256::<1>(
+                _mm256_castsi128_si256(simd_utils::load_two_u8x2(s_row0, x + x_start)),
+                simd_utils::load_two_u8x2(s_row1, x + x_start),
+            );
+            pix = _mm256_shuffle_epi8(pix, mask_lo);
+            sss0 = _mm256_add_epi32(sss0, _mm256_madd_epi16(pix, mmk));
+
+            pix = _mm256_inserti128_si256::<1>(
+                _mm256_castsi128_si256(simd_utils::load_two_u8x2(s_row2, x + x_start)),
+                simd_utils::load_two_u8x2(s_row3, x + x_start),
+            );
+            pix = _mm256_shuffle_epi8(pix, mask_lo);
+            sss1 = _mm256_add_epi32(sss1, _mm256_madd_epi16(pix, mmk));
+
+            x += 2;
+        }
+
+        if let Some(&k) = coeffs.get(x) {
+            let mmk = _mm256_set1_epi32(k as i32);
+
+            let mut pix = _mm256_inserti128_si256::<1>(
+                _mm256_castsi128_si256(simd_utils::mm_cvtepu8_epi32_u8x2(s_row0, x + x_start)),
+                simd_utils::mm_cvtepu8_epi32_u8x2(s_row1, x + x_start),
+            );
+            sss0 = _mm256_add_epi32(sss0, _mm256_madd_epi16(pix, mmk));
+
+            pix = _mm256_inserti128_si256::<1>(
+                _mm256_castsi128_si256(simd_utils::mm_cvtepu8_epi32_u8x2(s_row2, x + x_start)),
+                simd_utils::mm_cvtepu8_epi32_u8x2(s_row3, x + x_start),
+            );
+            sss1 = _mm256_add_epi32(sss1, _mm256_madd_epi16(pix, mmk));
+        }
+
+        // Fold sums of pixel pairs inside of each 128-bit lane.
+        sss0 = _mm256_add_epi32(sss0, _mm256_srli_si256::<8>(sss0));
+        sss1 = _mm256_add_epi32(sss1, _mm256_srli_si256::<8>(sss1));
+
+        macro_rules! call {
+            ($imm8:expr) => {{
+                sss0 = _mm256_srai_epi32::<$imm8>(sss0);
+                sss1 = _mm256_srai_epi32::<$imm8>(sss1);
+            }};
+        }
+        constify_imm8!(precision, call);
+
+        *d_row0.get_unchecked_mut(dst_x) = sse4::pack_to_pixel(_mm256_extracti128_si256::<0>(sss0));
+        *d_row1.get_unchecked_mut(dst_x) = sse4::pack_to_pixel(_mm256_extracti128_si256::<1>(sss0));
+        *d_row2.get_unchecked_mut(dst_x) = sse4::pack_to_pixel(_mm256_extracti128_si256::<0>(sss1));
+        *d_row3.get_unchecked_mut(dst_x) = sse4::pack_to_pixel(_mm256_extracti128_si256::<1>(sss1));
+    }
+}
@@ -0,0 +1,289 @@
+use std::arch::x86_64::*;
+
+use crate::convolution::{CoefficientsChunks, CoefficientsView};
+use crate::image_view::{FourRows, FourRowsMut, TypedImageView, TypedImageViewMut};
+use crate::pixels::U8x2;
+use crate::simd_utils;
+
+#[inline]
+pub(crate) fn horiz_convolution(
+    src_image: TypedImageView<U8x2>,
+    mut dst_image: TypedImageViewMut<U8x2>,
+    offset: u32,
+    coeffs: CoefficientsView<i16>,
+) {
+    let precision = coeffs.precision();
+    let coefficients_chunks = coeffs.chunks();
+    let dst_height = dst_image.height().get();
+
+    let src_iter = src_image.iter_4_rows(offset, dst_height + offset);
+    let dst_iter = dst_image.iter_4_rows_mut();
+    for (src_rows, dst_rows) in src_iter.zip(dst_iter) {
+        unsafe {
+            horiz_convolution_8u4x(src_rows, dst_rows, coefficients_chunks.clone(), precision);
+        }
+    }
+
+    let mut yy = dst_height - dst_height % 4;
+    while yy < dst_height {
+        unsafe {
+            horiz_convolution_8u(
+                src_image.get_row(yy + offset).unwrap(),
+                dst_image.get_row_mut(yy).unwrap(),
+                coefficients_chunks.clone(),
+                precision,
+            );
+        }
+        yy += 1;
+    }
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - length of all rows in src_rows must be equal
+/// - length of all rows in dst_rows must be equal
+/// - coefficients_chunks.len() == dst_rows.0.len()
+/// - max(chunk.start + chunk.values.len() for chunk in coefficients_chunks) <= src_row.0.len()
+/// - precision <= MAX_COEFS_PRECISION
+#[target_feature(enable = "sse4.1")]
+unsafe fn horiz_convolution_8u4x(
+    src_rows: FourRows<[u8; 2]>,
+    dst_rows: FourRowsMut<[u8; 2]>,
+    coefficients_chunks: CoefficientsChunks<i16>,
+    precision: u8,
+) {
+    let (s_row0, s_row1, s_row2, s_row3) = src_rows;
+    let (d_row0, d_row1, d_row2, d_row3) = dst_rows;
+    // Rounding term is added only into the lanes that stay
+    // after folding of the accumulator.
+    let initial = _mm_set_epi32(0, 0, 1 << (precision - 1), 1 << (precision - 1));
+    let mask_lo = _mm_set_epi8(-1, 7, -1, 5, -1, 6, -1, 4, -1, 3, -1, 1, -1, 2, -1, 0);
+    let mask_hi = _mm_set_epi8(-1, 15, -1, 13, -1, 14, -1, 12, -1, 11, -1, 9, -1, 10, -1, 8);
+
+    for (dst_x, coeffs_chunk) in coefficients_chunks.enumerate() {
+        let x_start = coeffs_chunk.start as usize;
+        let coeffs = coeffs_chunk.values;
+        let mut x: usize = 0;
+
+        let mut sss0 = initial;
+        let mut sss1 = initial;
+        let mut sss2 = initial;
+        let mut sss3 = initial;
+
+        while x + 8 <= coeffs.len() {
+            // [16] k3 k2 k3 k2 k1 k0 k1 k0
+            let mmk_lo = coeffs_4x(coeffs, x);
+            // [16] k7 k6 k7 k6 k5 k4 k5 k4
+            let mmk_hi = coeffs_4x(coeffs, x + 4);
+
+            // [8] a7 l7 a6 l6 a5 l5 a4 l4 a3 l3 a2 l2 a1 l1 a0 l0
+            let mut source = simd_utils::loadu_si128(s_row0, x + x_start);
+            // [16] a3 a2 l3 l2 a1 a0 l1 l0
+            let mut pix = _mm_shuffle_epi8(source, mask_lo);
+            sss0 = _mm_add_epi32(sss0, _mm_madd_epi16(pix, mmk_lo));
+            // [16] a7 a6 l7 l6 a5 a4 l5 l4
+            pix = _mm_shuffle_epi8(source, mask_hi);
+            sss0 = _mm_add_epi32(sss0, _mm_madd_epi16(pix, mmk_hi));
+
+            source = simd_utils::loadu_si128(s_row1, x + x_start);
+            pix = _mm_shuffle_epi8(source, mask_lo);
+            sss1 = _mm_add_epi32(sss1, _mm_madd_epi16(pix, mmk_lo));
+            pix = _mm_shuffle_epi8(source, mask_hi);
+            sss1 = _mm_add_epi32(sss1, _mm_madd_epi16(pix, mmk_hi));
+
+            source = simd_utils::loadu_si128(s_row2, x + x_start);
+            pix = _mm_shuffle_epi8(source, mask_lo);
+            sss2 = _mm_add_epi32(sss2, _mm_madd_epi16(pix, mmk_lo));
+            pix = _mm_shuffle_epi8(source, mask_hi);
+            sss2 = _mm_add_epi32(sss2, _mm_madd_epi16(pix, mmk_hi));
+
+            source = simd_utils::loadu_si128(s_row3, x + x_start);
+            pix = _mm_shuffle_epi8(source, mask_lo);
+            sss3 = _mm_add_epi32(sss3, _mm_madd_epi16(pix, mmk_lo));
+            pix = _mm_shuffle_epi8(source, mask_hi);
+            sss3 = _mm_add_epi32(sss3, _mm_madd_epi16(pix, mmk_hi));
+
+            x += 8;
+        }
+
+        if x + 4 <= coeffs.len() {
+            let mmk = coeffs_4x(coeffs, x);
+
+            // [8] 0 0 0 0 0 0 0 0 a3 l3 a2 l2 a1 l1 a0 l0
+            let mut pix = simd_utils::loadl_epi64(s_row0, x + x_start);
+            pix = _mm_shuffle_epi8(pix, mask_lo);
+            sss0 = _mm_add_epi32(sss0, _mm_madd_epi16(pix, mmk));
+
+            pix = simd_utils::loadl_epi64(s_row1, x + x_start);
+            pix = _mm_shuffle_epi8(pix, mask_lo);
+            sss1 = _mm_add_epi32(sss1, _mm_madd_epi16(pix, mmk));
+
+            pix = simd_utils::loadl_epi64(s_row2, x + x_start);
+            pix = _mm_shuffle_epi8(pix, mask_lo);
+            sss2 = _mm_add_epi32(sss2, _mm_madd_epi16(pix, mmk));
+
+            pix = simd_utils::loadl_epi64(s_row3, x + x_start);
+            pix = _mm_shuffle_epi8(pix, mask_lo);
+            sss3 = _mm_add_epi32(sss3, _mm_madd_epi16(pix, mmk));
+
+            x += 4;
+        }
+
+        if x + 2 <= coeffs.len() {
+            // [16] k1 k0 k1 k0 k1 k0 k1 k0
+            let mmk = simd_utils::ptr_i16_to_set1_epi32(coeffs, x);
+
+            // [8] 0 0 0 0 0 0 0 0 0 0 0 0 a1 l1 a0 l0
+            let mut pix = simd_utils::load_two_u8x2(s_row0, x + x_start);
+            // [16] 0 0 0 0 a1 a0 l1 l0
+            pix = _mm_shuffle_epi8(pix, mask_lo);
+            sss0 = _mm_add_epi32(sss0, _mm_madd_epi16(pix, mmk));
+
+            pix = simd_utils::load_two_u8x2(s_row1, x + x_start);
+            pix = _mm_shuffle_epi8(pix, mask_lo);
+            sss1 = _mm_add_epi32(sss1, _mm_madd_epi16(pix, mmk));
+
+            pix = simd_utils::load_two_u8x2(s_row2, x + x_start);
+            pix = _mm_shuffle_epi8(pix, mask_lo);
+            sss2 = _mm_add_epi32(sss2, _mm_madd_epi16(pix, mmk));
+
+            pix = simd_utils::load_two_u8x2(s_row3, x + x_start);
+            pix = _mm_shuffle_epi8(pix, mask_lo);
+            sss3 = _mm_add_epi32(sss3, _mm_madd_epi16(pix, mmk));
+
+            x += 2;
+        }
+
+        if let Some(&k) = coeffs.get(x) {
+            // [16] xx k0 xx k0 xx k0 xx k0
+            let mmk = _mm_set1_epi32(k as i32);
+            // [16] xx 0 xx 0 xx a0 xx l0
+            let mut pix = simd_utils::mm_cvtepu8_epi32_u8x2(s_row0, x + x_start);
+            sss0 = _mm_add_epi32(sss0, _mm_madd_epi16(pix, mmk));
+
+            pix = simd_utils::mm_cvtepu8_epi32_u8x2(s_row1, x + x_start);
+            sss1 = _mm_add_epi32(sss1, _mm_madd_epi16(pix, mmk));
+
+            pix = simd_utils::mm_cvtepu8_epi32_u8x2(s_row2, x + x_start);
+            sss2 = _mm_add_epi32(sss2, _mm_madd_epi16(pix, mmk));
+
+            pix = simd_utils::mm_cvtepu8_epi32_u8x2(s_row3, x + x_start);
+            sss3 = _mm_add_epi32(sss3, _mm_madd_epi16(pix, mmk));
+        }
+
+        sss0 = fold_sums(sss0);
+        sss1 = fold_sums(sss1);
+        sss2 = fold_sums(sss2);
+        sss3 = fold_sums(sss3);
+
+        macro_rules! call {
+            ($imm8:expr) => {{
+                sss0 = _mm_srai_epi32::<$imm8>(sss0);
+                sss1 = _mm_srai_epi32::<$imm8>(sss1);
+                sss2 = _mm_srai_epi32::<$imm8>(sss2);
+                sss3 = _mm_srai_epi32::<$imm8>(sss3);
+            }};
+        }
+        constify_imm8!(precision, call);
+
+        *d_row0.get_unchecked_mut(dst_x) = pack_to_pixel(sss0);
+        *d_row1.get_unchecked_mut(dst_x) = pack_to_pixel(sss1);
+        *d_row2.get_unchecked_mut(dst_x) = pack_to_pixel(sss2);
+        *d_row3.get_unchecked_mut(dst_x) = pack_to_pixel(sss3);
+    }
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - coefficients_chunks.len() == dst_row.len()
+/// - max(chunk.start + chunk.values.len() for chunk in coefficients_chunks) <= src_row.len()
+/// - precision <= MAX_COEFS_PRECISION
+#[target_feature(enable = "sse4.1")]
+pub(crate) unsafe fn horiz_convolution_8u(
+    src_row: &[[u8; 2]],
+    dst_row: &mut [[u8; 2]],
+    coefficients_chunks: CoefficientsChunks<i16>,
+    precision: u8,
+) {
+    let initial = _mm_set_epi32(0, 0, 1 << (precision - 1), 1 << (precision - 1));
+    let mask_lo = _mm_set_epi8(-1, 7, -1, 5, -1, 6, -1, 4, -1, 3, -1, 1, -1, 2, -1, 0);
+    let mask_hi = _mm_set_epi8(-1, 15, -1, 13, -1, 14, -1, 12, -1, 11, -1, 9, -1, 10, -1, 8);
+
+    for (dst_x, coeffs_chunk) in coefficients_chunks.enumerate() {
+        let x_start = coeffs_chunk.start as usize;
+        let coeffs = coeffs_chunk.values;
+        let mut x: usize = 0;
+
+        let mut sss = initial;
+
+        while x + 8 <= coeffs.len() {
+            let mmk_lo = coeffs_4x(coeffs, x);
+            let mmk_hi = coeffs_4x(coeffs, x + 4);
+
+            let source = simd_utils::loadu_si128(src_row, x + x_start);
+            let mut pix = _mm_shuffle_epi8(source, mask_lo);
+            sss = _mm_add_epi32(sss, _mm_madd_epi16(pix, mmk_lo));
+            pix = _mm_shuffle_epi8(source, mask_hi);
+            sss = _mm_add_epi32(sss, _mm_madd_epi16(pix, mmk_hi));
+
+            x += 8;
+        }
+
+        if x + 4 <= coeffs.len() {
+            let mmk = coeffs_4x(coeffs, x);
+            let source = simd_utils::loadl_epi64(src_row, x + x_start);
+            let pix = _mm_shuffle_epi8(source, mask_lo);
+            sss = _mm_add_epi32(sss, _mm_madd_epi16(pix, mmk));
+
+            x += 4;
+        }
+
+        if x + 2 <= coeffs.len() {
+            let mmk = simd_utils::ptr_i16_to_set1_epi32(coeffs, x);
+            let source = simd_utils::load_two_u8x2(src_row, x + x_start);
+            let pix = _mm_shuffle_epi8(source, mask_lo);
+            sss = _mm_add_epi32(sss, _mm_madd_epi16(pix, mmk));
+
+            x += 2;
+        }
+
+        if let Some(&k) = coeffs.get(x) {
+            let pix = simd_utils::mm_cvtepu8_epi32_u8x2(src_row, x + x_start);
+            let mmk = _mm_set1_epi32(k as i32);
+            sss = _mm_add_epi32(sss, _mm_madd_epi16(pix, mmk));
+        }
+
+        sss = fold_sums(sss);
+
+        macro_rules! call {
+            ($imm8:expr) => {{
+                sss = _mm_srai_epi32::<$imm8>(sss);
+            }};
+        }
+        constify_imm8!(precision, call);
+
+        *dst_row.get_unchecked_mut(dst_x) = pack_to_pixel(sss);
+    }
+}
+
+/// Loads four coefficients as pairs for two pixels per pair:
+/// [16] k3 k2 k3 k2 k1 k0 k1 k0
+#[inline]
+#[target_feature(enable = "sse4.1")]
+pub(crate) unsafe fn coeffs_4x(coeffs: &[i16], index: usize) -> __m128i {
+    _mm_shuffle_epi32::<0b01_01_00_00>(simd_utils::loadl_epi64(coeffs, index))
+}
+
+/// Adds sums of the odd pair of pixels to sums of the even pair.
+#[inline]
+#[target_feature(enable = "sse4.1")]
+pub(crate) unsafe fn fold_sums(sss: __m128i) -> __m128i {
+    _mm_add_epi32(sss, _mm_srli_si128::<8>(sss))
+}
+
+/// Packs the first two i32 values into one pixel with saturation.
+#[inline]
+#[target_feature(enable = "sse4.1")]
+pub(crate) unsafe fn pack_to_pixel(sss: __m128i) -> [u8; 2] {
+    let sss = _mm_packs_epi32(sss, sss);
+    let [l, a, _, _] = (_mm_cvtsi128_si32(_mm_packus_epi16(sss, sss)) as u32).to_le_bytes();
+    [l, a]
+}
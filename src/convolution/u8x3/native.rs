@@ -0,0 +1,74 @@
+use crate::convolution::{optimisations, CoefficientsView};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::U8x3;
+
+pub(crate) fn horiz_convolution(
+    src_image: TypedImageView<U8x3>,
+    mut dst_image: TypedImageViewMut<U8x3>,
+    offset: u32,
+    coeffs: CoefficientsView<i16>,
+) {
+    let precision = coeffs.precision();
+    let coefficients_chunks = coeffs.chunks();
+
+    let dst_rows = dst_image.iter_rows_mut();
+    for (y_dst, dst_row) in dst_rows.enumerate() {
+        let y_src = y_dst as u32 + offset;
+
+        for (coeffs_chunk, dst_pixel) in coefficients_chunks.clone().zip(dst_row.iter_mut()) {
+            let first_x_src = coeffs_chunk.start;
+            let ks = coeffs_chunk.values;
+
+            let mut ss0 = 1 << (precision - 1);
+            let mut ss1 = ss0;
+            let mut ss2 = ss0;
+            let src_pixels = src_image.iter_horiz(first_x_src, y_src);
+            for (&k, &src_pixel) in ks.iter().zip(src_pixels) {
+                ss0 += src_pixel[0] as i32 * (k as i32);
+                ss1 += src_pixel[1] as i32 * (k as i32);
+                ss2 += src_pixel[2] as i32 * (k as i32);
+            }
+            *dst_pixel = unsafe {
+                [
+                    optimisations::clip8(ss0, precision),
+                    optimisations::clip8(ss1, precision),
+                    optimisations::clip8(ss2, precision),
+                ]
+            };
+        }
+    }
+}
+
+pub(crate) fn vert_convolution(
+    src_image: TypedImageView<U8x3>,
+    mut dst_image: TypedImageViewMut<U8x3>,
+    coeffs: CoefficientsView<i16>,
+) {
+    let precision = coeffs.precision();
+    let coefficients_chunks = coeffs.chunks();
+
+    let dst_rows = dst_image.iter_rows_mut();
+    for (coeffs_chunk, dst_row) in coefficients_chunks.zip(dst_rows) {
+        let first_y_src = coeffs_chunk.start;
+        let ks = coeffs_chunk.values;
+
+        for (x_src, out_pixel) in dst_row.iter_mut().enumerate() {
+            let mut ss0 = 1 << (precision - 1);
+            let mut ss1 = ss0;
+            let mut ss2 = ss0;
+            for (dy, &k) in ks.iter().enumerate() {
+                let pixel = src_image.get_pixel(x_src as u32, first_y_src + dy as u32);
+                ss0 += pixel[0] as i32 * (k as i32);
+                ss1 += pixel[1] as i32 * (k as i32);
+                ss2 += pixel[2] as i32 * (k as i32);
+            }
+            *out_pixel = unsafe {
+                [
+                    optimisations::clip8(ss0, precision),
+                    optimisations::clip8(ss1, precision),
+                    optimisations::clip8(ss2, precision),
+                ]
+            };
+        }
+    }
+}
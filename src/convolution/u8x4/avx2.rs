@@ -1,8 +1,6 @@
 use std::arch::x86_64::*;
-use std::intrinsics::transmute;
 
-use crate::convolution::optimisations::CoefficientsI16Chunk;
-use crate::convolution::{optimisations, Bound, Coefficients};
+use crate::convolution::{Bound, CoefficientsChunks, CoefficientsView};
 use crate::image_view::{FourRows, FourRowsMut, TypedImageView, TypedImageViewMut};
 use crate::pixels::U8x4;
 use crate::simd_utils;
@@ -15,22 +13,17 @@ pub(crate) fn horiz_convolution(
     src_image: TypedImageView<U8x4>,
     mut dst_image: TypedImageViewMut<U8x4>,
     offset: u32,
-    coeffs: Coefficients,
+    coeffs: CoefficientsView<i16>,
 ) {
-    let (values, window_size, bounds_per_pixel) =
-        (coeffs.values, coeffs.window_size, coeffs.bounds);
-
-    let normalizer_guard = optimisations::NormalizerGuard::new(values);
-    let precision = normalizer_guard.precision();
-    let coefficients_chunks =
-        normalizer_guard.normalized_i16_chunks(window_size, &bounds_per_pixel);
+    let precision = coeffs.precision();
+    let coefficients_chunks = coeffs.chunks();
     let dst_height = dst_image.height().get();
 
     let src_iter = src_image.iter_4_rows(offset, dst_height + offset);
     let dst_iter = dst_image.iter_4_rows_mut();
     for (src_rows, dst_rows) in src_iter.zip(dst_iter) {
         unsafe {
-            horiz_convolution_8u4x(src_rows, dst_rows, &coefficients_chunks, precision);
+            horiz_convolution_8u4x(src_rows, dst_rows, coefficients_chunks.clone(), precision);
         }
     }
 
@@ -40,7 +33,7 @@ pub(crate) fn horiz_convolution(
             horiz_convolution_8u(
                 src_image.get_row(yy + offset).unwrap(),
                 dst_image.get_row_mut(yy).unwrap(),
-                &coefficients_chunks,
+                coefficients_chunks.clone(),
                 precision,
             );
         }
@@ -52,17 +45,13 @@ pub(crate) fn horiz_convolution(
 pub(crate) fn vert_convolution(
     src_image: TypedImageView<U8x4>,
     mut dst_image: TypedImageViewMut<U8x4>,
-    coeffs: Coefficients,
+    coeffs: CoefficientsView<i16>,
 ) {
-    let (values, window_size, bounds) = (coeffs.values, coeffs.window_size, coeffs.bounds);
-
-    let normalizer_guard = optimisations::NormalizerGuard::new(values);
-    let precision = normalizer_guard.precision();
-    let coeffs_i16 = normalizer_guard.normalized_i16();
-    let coeffs_chunks = coeffs_i16.chunks(window_size);
+    let precision = coeffs.precision();
 
     let dst_rows = dst_image.iter_rows_mut();
-    for ((&bound, k), dst_row) in bounds.iter().zip(coeffs_chunks).zip(dst_rows) {
+    for (coeffs_chunk, dst_row) in coeffs.chunks().zip(dst_rows) {
+        let (k, bound) = (coeffs_chunk.values, coeffs_chunk.bound());
         unsafe {
             vert_convolution_8u(&src_image, dst_row, k, bound, precision);
         }
@@ -80,7 +69,7 @@ pub(crate) fn vert_convolution(
 unsafe fn horiz_convolution_8u4x(
     src_rows: FourRows<u32>,
     dst_rows: FourRowsMut<u32>,
-    coefficients_chunks: &[CoefficientsI16Chunk],
+    coefficients_chunks: CoefficientsChunks<i16>,
     precision: u8,
 ) {
     let (s_row0, s_row1, s_row2, s_row3) = src_rows;
@@ -99,7 +88,7 @@ unsafe fn horiz_convolution_8u4x(
         -1, 15, -1, 11, -1, 14, -1, 10, -1, 13, -1, 9, -1, 12, -1, 8,
     );
 
-    for (dst_x, coeffs_chunk) in coefficients_chunks.iter().enumerate() {
+    for (dst_x, coeffs_chunk) in coefficients_chunks.enumerate() {
         let x_start = coeffs_chunk.start as usize;
         let mut x: usize = 0;
 
@@ -190,13 +179,13 @@ unsafe fn horiz_convolution_8u4x(
         sss0 = _mm256_packus_epi16(sss0, zero);
         sss1 = _mm256_packus_epi16(sss1, zero);
         *d_row0.get_unchecked_mut(dst_x) =
-            transmute(_mm_cvtsi128_si32(_mm256_extracti128_si256::<0>(sss0)));
+            _mm_cvtsi128_si32(_mm256_extracti128_si256::<0>(sss0)) as u32;
         *d_row1.get_unchecked_mut(dst_x) =
-            transmute(_mm_cvtsi128_si32(_mm256_extracti128_si256::<1>(sss0)));
+            _mm_cvtsi128_si32(_mm256_extracti128_si256::<1>(sss0)) as u32;
         *d_row2.get_unchecked_mut(dst_x) =
-            transmute(_mm_cvtsi128_si32(_mm256_extracti128_si256::<0>(sss1)));
+            _mm_cvtsi128_si32(_mm256_extracti128_si256::<0>(sss1)) as u32;
         *d_row3.get_unchecked_mut(dst_x) =
-            transmute(_mm_cvtsi128_si32(_mm256_extracti128_si256::<1>(sss1)));
+            _mm_cvtsi128_si32(_mm256_extracti128_si256::<1>(sss1)) as u32;
     }
 }
 
@@ -207,10 +196,10 @@ unsafe fn horiz_convolution_8u4x(
 /// - precision <= MAX_COEFS_PRECISION
 #[inline]
 #[target_feature(enable = "avx2")]
-unsafe fn horiz_convolution_8u(
+pub(crate) unsafe fn horiz_convolution_8u(
     src_row: &[u32],
     dst_row: &mut [u32],
-    coefficients_chunks: &[CoefficientsI16Chunk],
+    coefficients_chunks: CoefficientsChunks<i16>,
     precision: u8,
 ) {
     #[rustfmt::skip]
@@ -245,7 +234,7 @@ unsafe fn horiz_convolution_8u(
     );
     let sh7 = _mm_set_epi8(-1, 7, -1, 3, -1, 6, -1, 2, -1, 5, -1, 1, -1, 4, -1, 0);
 
-    for (dst_x, &coeffs_chunk) in coefficients_chunks.iter().enumerate() {
+    for (dst_x, coeffs_chunk) in coefficients_chunks.enumerate() {
         let x_start = coeffs_chunk.start as usize;
         let mut x: usize = 0;
         let mut coeffs = coeffs_chunk.values;
@@ -327,8 +316,7 @@ unsafe fn horiz_convolution_8u(
         constify_imm8!(precision, call);
 
         sss = _mm_packs_epi32(sss, sss);
-        *dst_row.get_unchecked_mut(dst_x) =
-            transmute(_mm_cvtsi128_si32(_mm_packus_epi16(sss, sss)));
+        *dst_row.get_unchecked_mut(dst_x) = _mm_cvtsi128_si32(_mm_packus_epi16(sss, sss)) as u32;
     }
 }
 
@@ -504,7 +492,7 @@ pub(crate) unsafe fn vert_convolution_8u(
         constify_imm8!(precision, call);
 
         sss = _mm_packs_epi32(sss, sss);
-        *dst_row.get_unchecked_mut(x) = transmute(_mm_cvtsi128_si32(_mm_packus_epi16(sss, sss)));
+        *dst_row.get_unchecked_mut(x) = _mm_cvtsi128_si32(_mm_packus_epi16(sss, sss)) as u32;
 
         x += 1;
     }
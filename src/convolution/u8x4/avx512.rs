@@ -0,0 +1,162 @@
+use std::arch::x86_64::*;
+
+use crate::convolution::{vertical_u8, CoefficientsChunks, CoefficientsView};
+use crate::image_view::{FourRows, FourRowsMut, TypedImageView, TypedImageViewMut};
+use crate::pixels::U8x4;
+use crate::simd_utils;
+
+#[inline]
+pub(crate) fn horiz_convolution(
+    src_image: TypedImageView<U8x4>,
+    mut dst_image: TypedImageViewMut<U8x4>,
+    offset: u32,
+    coeffs: CoefficientsView<i16>,
+) {
+    let precision = coeffs.precision();
+    let coefficients_chunks = coeffs.chunks();
+    let dst_height = dst_image.height().get();
+
+    let src_iter = src_image.iter_4_rows(offset, dst_height + offset);
+    let dst_iter = dst_image.iter_4_rows_mut();
+    for (src_rows, dst_rows) in src_iter.zip(dst_iter) {
+        unsafe {
+            horiz_convolution_8u4x(src_rows, dst_rows, coefficients_chunks.clone(), precision);
+        }
+    }
+
+    let mut yy = dst_height - dst_height % 4;
+    while yy < dst_height {
+        unsafe {
+            super::avx2::horiz_convolution_8u(
+                src_image.get_row(yy + offset).unwrap(),
+                dst_image.get_row_mut(yy).unwrap(),
+                coefficients_chunks.clone(),
+                precision,
+            );
+        }
+        yy += 1;
+    }
+}
+
+#[inline]
+pub(crate) fn vert_convolution(
+    src_image: TypedImageView<U8x4>,
+    dst_image: TypedImageViewMut<U8x4>,
+    coeffs: CoefficientsView<i16>,
+) {
+    vertical_u8::avx512::vert_convolution(src_image, dst_image, coeffs);
+}
+
+/// Loads 128 bits from the same position of four rows
+/// into four 128-bit lanes of the result.
+#[inline]
+#[target_feature(enable = "avx512f")]
+unsafe fn load_4x128(src_rows: FourRows<u32>, x: usize) -> __m512i {
+    let (s_row0, s_row1, s_row2, s_row3) = src_rows;
+    let mut res = _mm512_castsi128_si512(simd_utils::loadu_si128(s_row0, x));
+    res = _mm512_inserti32x4::<1>(res, simd_utils::loadu_si128(s_row1, x));
+    res = _mm512_inserti32x4::<2>(res, simd_utils::loadu_si128(s_row2, x));
+    _mm512_inserti32x4::<3>(res, simd_utils::loadu_si128(s_row3, x))
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - length of all rows in src_rows must be equal
+/// - length of all rows in dst_rows must be equal
+/// - coefficients_chunks.len() == dst_rows.0.len()
+/// - max(chunk.start + chunk.values.len() for chunk in coefficients_chunks) <= src_row.0.len()
+/// - precision <= MAX_COEFS_PRECISION
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw")]
+unsafe fn horiz_convolution_8u4x(
+    src_rows: FourRows<u32>,
+    dst_rows: FourRowsMut<u32>,
+    coefficients_chunks: CoefficientsChunks<i16>,
+    precision: u8,
+) {
+    let (s_row0, s_row1, s_row2, s_row3) = src_rows;
+    let (d_row0, d_row1, d_row2, d_row3) = dst_rows;
+    let zero = _mm512_setzero_si512();
+    let initial = _mm512_set1_epi32(1 << (precision - 1));
+
+    // Every 128-bit lane of registers contains pixels of one row.
+    let sh1 = _mm512_broadcast_i32x4(_mm_set_epi8(
+        -1, 7, -1, 3, -1, 6, -1, 2, -1, 5, -1, 1, -1, 4, -1, 0,
+    ));
+    let sh2 = _mm512_broadcast_i32x4(_mm_set_epi8(
+        -1, 15, -1, 11, -1, 14, -1, 10, -1, 13, -1, 9, -1, 12, -1, 8,
+    ));
+
+    for (dst_x, coeffs_chunk) in coefficients_chunks.enumerate() {
+        let x_start = coeffs_chunk.start as usize;
+        let mut x: usize = 0;
+
+        let mut sss = initial;
+        let coeffs = coeffs_chunk.values;
+
+        let coeffs_by_4 = coeffs.chunks_exact(4);
+        let reminder1 = coeffs_by_4.remainder();
+
+        for k in coeffs_by_4 {
+            let mmk0 = simd_utils::ptr_i16_to_512set1_epi32(k, 0);
+            let mmk1 = simd_utils::ptr_i16_to_512set1_epi32(k, 2);
+
+            let source = load_4x128(src_rows, x + x_start);
+            let mut pix = _mm512_shuffle_epi8(source, sh1);
+            sss = _mm512_add_epi32(sss, _mm512_madd_epi16(pix, mmk0));
+            pix = _mm512_shuffle_epi8(source, sh2);
+            sss = _mm512_add_epi32(sss, _mm512_madd_epi16(pix, mmk1));
+
+            x += 4;
+        }
+
+        let coeffs_by_2 = reminder1.chunks_exact(2);
+        let reminder2 = coeffs_by_2.remainder();
+
+        for k in coeffs_by_2 {
+            let mmk = simd_utils::ptr_i16_to_512set1_epi32(k, 0);
+
+            let src_x = x + x_start;
+            let mut pix = _mm512_castsi128_si512(simd_utils::loadl_epi64(s_row0, src_x));
+            pix = _mm512_inserti32x4::<1>(pix, simd_utils::loadl_epi64(s_row1, src_x));
+            pix = _mm512_inserti32x4::<2>(pix, simd_utils::loadl_epi64(s_row2, src_x));
+            pix = _mm512_inserti32x4::<3>(pix, simd_utils::loadl_epi64(s_row3, src_x));
+            pix = _mm512_shuffle_epi8(pix, sh1);
+            sss = _mm512_add_epi32(sss, _mm512_madd_epi16(pix, mmk));
+
+            x += 2;
+        }
+
+        for &k in reminder2 {
+            // [16] xx k0 xx k0 xx k0 xx k0 ...
+            let mmk = _mm512_set1_epi32(k as i32);
+
+            // [16] xx a0 xx b0 xx g0 xx r0 ...
+            let src_x = x + x_start;
+            let mut pix = _mm512_castsi128_si512(simd_utils::mm_cvtepu8_epi32(s_row0, src_x));
+            pix = _mm512_inserti32x4::<1>(pix, simd_utils::mm_cvtepu8_epi32(s_row1, src_x));
+            pix = _mm512_inserti32x4::<2>(pix, simd_utils::mm_cvtepu8_epi32(s_row2, src_x));
+            pix = _mm512_inserti32x4::<3>(pix, simd_utils::mm_cvtepu8_epi32(s_row3, src_x));
+            sss = _mm512_add_epi32(sss, _mm512_madd_epi16(pix, mmk));
+
+            x += 1;
+        }
+
+        macro_rules! call {
+            ($imm8:expr) => {{
+                sss = _mm512_srai_epi32::<$imm8>(sss);
+            }};
+        }
+        constify_imm8!(precision, call);
+
+        sss = _mm512_packs_epi32(sss, zero);
+        sss = _mm512_packus_epi16(sss, zero);
+        *d_row0.get_unchecked_mut(dst_x) =
+            _mm_cvtsi128_si32(_mm512_extracti32x4_epi32::<0>(sss)) as u32;
+        *d_row1.get_unchecked_mut(dst_x) =
+            _mm_cvtsi128_si32(_mm512_extracti32x4_epi32::<1>(sss)) as u32;
+        *d_row2.get_unchecked_mut(dst_x) =
+            _mm_cvtsi128_si32(_mm512_extracti32x4_epi32::<2>(sss)) as u32;
+        *d_row3.get_unchecked_mut(dst_x) =
+            _mm_cvtsi128_si32(_mm512_extracti32x4_epi32::<3>(sss)) as u32;
+    }
+}
@@ -1,27 +1,45 @@
-use super::{Coefficients, Convolution};
+use super::{CoefficientsView, Convolution};
 use crate::image_view::{TypedImageView, TypedImageViewMut};
 use crate::pixels::U8x4;
 use crate::CpuExtensions;
 
 #[cfg(target_arch = "x86_64")]
 mod avx2;
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+mod avx512;
 mod native;
+#[cfg(target_arch = "aarch64")]
+mod neon;
 #[cfg(target_arch = "x86_64")]
 mod sse4;
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+mod wasm32;
 
 impl Convolution for U8x4 {
+    type Coefficient = i16;
+
     fn horiz_convolution(
         src_image: TypedImageView<Self>,
         dst_image: TypedImageViewMut<Self>,
         offset: u32,
-        coeffs: Coefficients,
+        coeffs: CoefficientsView<i16>,
         cpu_extensions: CpuExtensions,
     ) {
         match cpu_extensions {
             #[cfg(target_arch = "x86_64")]
             CpuExtensions::Avx2 => avx2::horiz_convolution(src_image, dst_image, offset, coeffs),
+            #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+            CpuExtensions::Avx512 => {
+                avx512::horiz_convolution(src_image, dst_image, offset, coeffs)
+            }
             #[cfg(target_arch = "x86_64")]
             CpuExtensions::Sse4_1 => sse4::horiz_convolution(src_image, dst_image, offset, coeffs),
+            #[cfg(target_arch = "aarch64")]
+            CpuExtensions::Neon => neon::horiz_convolution(src_image, dst_image, offset, coeffs),
+            #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+            CpuExtensions::Simd128 => {
+                wasm32::horiz_convolution(src_image, dst_image, offset, coeffs)
+            }
             _ => native::horiz_convolution(src_image, dst_image, offset, coeffs),
         }
     }
@@ -29,14 +47,20 @@ impl Convolution for U8x4 {
     fn vert_convolution(
         src_image: TypedImageView<Self>,
         dst_image: TypedImageViewMut<Self>,
-        coeffs: Coefficients,
+        coeffs: CoefficientsView<i16>,
         cpu_extensions: CpuExtensions,
     ) {
         match cpu_extensions {
             #[cfg(target_arch = "x86_64")]
             CpuExtensions::Avx2 => avx2::vert_convolution(src_image, dst_image, coeffs),
+            #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+            CpuExtensions::Avx512 => avx512::vert_convolution(src_image, dst_image, coeffs),
             #[cfg(target_arch = "x86_64")]
             CpuExtensions::Sse4_1 => sse4::vert_convolution(src_image, dst_image, coeffs),
+            #[cfg(target_arch = "aarch64")]
+            CpuExtensions::Neon => neon::vert_convolution(src_image, dst_image, coeffs),
+            #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+            CpuExtensions::Simd128 => wasm32::vert_convolution(src_image, dst_image, coeffs),
             _ => native::vert_convolution(src_image, dst_image, coeffs),
         }
     }
@@ -0,0 +1,76 @@
+use std::arch::aarch64::*;
+
+use crate::convolution::{vertical_u8, CoefficientsChunks, CoefficientsView};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::U8x4;
+
+#[inline]
+pub(crate) fn horiz_convolution(
+    src_image: TypedImageView<U8x4>,
+    mut dst_image: TypedImageViewMut<U8x4>,
+    offset: u32,
+    coeffs: CoefficientsView<i16>,
+) {
+    let precision = coeffs.precision();
+    let coefficients_chunks = coeffs.chunks();
+
+    let dst_rows = dst_image.iter_rows_mut();
+    for (y_dst, dst_row) in dst_rows.enumerate() {
+        if let Some(src_row) = src_image.get_row(y_dst as u32 + offset) {
+            unsafe {
+                horiz_convolution_8u(src_row, dst_row, coefficients_chunks.clone(), precision);
+            }
+        }
+    }
+}
+
+#[inline]
+pub(crate) fn vert_convolution(
+    src_image: TypedImageView<U8x4>,
+    dst_image: TypedImageViewMut<U8x4>,
+    coeffs: CoefficientsView<i16>,
+) {
+    vertical_u8::neon::vert_convolution(src_image, dst_image, coeffs);
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - coefficients_chunks.len() == dst_row.len()
+/// - max(chunk.start + chunk.values.len() for chunk in coefficients_chunks) <= src_row.len()
+/// - precision <= MAX_COEFS_PRECISION
+#[target_feature(enable = "neon")]
+unsafe fn horiz_convolution_8u(
+    src_row: &[u32],
+    dst_row: &mut [u32],
+    coefficients_chunks: CoefficientsChunks<i16>,
+    precision: u8,
+) {
+    let initial = vdupq_n_s32(1 << (precision - 1));
+    // Shifting to the left by negative value is shifting to the right.
+    let shift = vdupq_n_s32(-(precision as i32));
+
+    for (dst_x, coeffs_chunk) in coefficients_chunks.enumerate() {
+        let x_start = coeffs_chunk.start as usize;
+        let ks = coeffs_chunk.values;
+        let mut sss = initial;
+        let mut x: usize = 0;
+
+        // Two pixels per iteration
+        while x < ks.len().saturating_sub(1) {
+            let src_ptr = src_row.get_unchecked(x_start + x..).as_ptr() as *const u8;
+            let pix = vreinterpretq_s16_u16(vmovl_u8(vld1_u8(src_ptr)));
+            sss = vmlal_n_s16(sss, vget_low_s16(pix), *ks.get_unchecked(x));
+            sss = vmlal_n_s16(sss, vget_high_s16(pix), *ks.get_unchecked(x + 1));
+            x += 2;
+        }
+
+        if let Some(&k) = ks.get(x) {
+            let pixel = *src_row.get_unchecked(x_start + x);
+            let pix = vreinterpretq_s16_u16(vmovl_u8(vcreate_u8(pixel as u64)));
+            sss = vmlal_n_s16(sss, vget_low_s16(pix), k);
+        }
+
+        let res = vqmovun_s32(vshlq_s32(sss, shift));
+        let res = vqmovn_u16(vcombine_u16(res, res));
+        *dst_row.get_unchecked_mut(dst_x) = vget_lane_u32::<0>(vreinterpret_u32_u8(res));
+    }
+}
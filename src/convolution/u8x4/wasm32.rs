@@ -0,0 +1,112 @@
+use std::arch::wasm32::*;
+
+use crate::convolution::{vertical_u8, CoefficientsChunks, CoefficientsView};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::U8x4;
+
+#[inline]
+pub(crate) fn horiz_convolution(
+    src_image: TypedImageView<U8x4>,
+    mut dst_image: TypedImageViewMut<U8x4>,
+    offset: u32,
+    coeffs: CoefficientsView<i16>,
+) {
+    let precision = coeffs.precision();
+    let coefficients_chunks = coeffs.chunks();
+
+    let dst_rows = dst_image.iter_rows_mut();
+    for (y_dst, dst_row) in dst_rows.enumerate() {
+        if let Some(src_row) = src_image.get_row(y_dst as u32 + offset) {
+            unsafe {
+                horiz_convolution_8u(src_row, dst_row, coefficients_chunks.clone(), precision);
+            }
+        }
+    }
+}
+
+#[inline]
+pub(crate) fn vert_convolution(
+    src_image: TypedImageView<U8x4>,
+    dst_image: TypedImageViewMut<U8x4>,
+    coeffs: CoefficientsView<i16>,
+) {
+    vertical_u8::wasm32::vert_convolution(src_image, dst_image, coeffs);
+}
+
+/// Returns two adjacent coefficients as one 32-bit value
+/// replicated to all lanes.
+#[inline]
+#[target_feature(enable = "simd128")]
+unsafe fn coeffs_pair(coeffs: &[i16], index: usize) -> v128 {
+    i32x4_splat((coeffs.get_unchecked(index..).as_ptr() as *const i32).read_unaligned())
+}
+
+/// For safety, it is necessary to ensure the following conditions:
+/// - coefficients_chunks.len() == dst_row.len()
+/// - max(chunk.start + chunk.values.len() for chunk in coefficients_chunks) <= src_row.len()
+/// - precision <= MAX_COEFS_PRECISION
+#[target_feature(enable = "simd128")]
+unsafe fn horiz_convolution_8u(
+    src_row: &[u32],
+    dst_row: &mut [u32],
+    coefficients_chunks: CoefficientsChunks<i16>,
+    precision: u8,
+) {
+    let zero = i32x4_splat(0);
+    let initial = i32x4_splat(1 << (precision - 1));
+
+    for (dst_x, coeffs_chunk) in coefficients_chunks.enumerate() {
+        let x_start = coeffs_chunk.start as usize;
+        let ks = coeffs_chunk.values;
+        let mut sss = initial;
+        let mut x: usize = 0;
+
+        // Four pixels per iteration
+        while x < ks.len().saturating_sub(3) {
+            let src_ptr = src_row.get_unchecked(x_start + x..).as_ptr() as *const v128;
+            let source = v128_load(src_ptr);
+
+            // [16] a1 a0 b1 b0 g1 g0 r1 r0
+            let pix = u8x16_shuffle::<0, 16, 4, 16, 1, 16, 5, 16, 2, 16, 6, 16, 3, 16, 7, 16>(
+                source, zero,
+            );
+            sss = i32x4_add(sss, i32x4_dot_i16x8(pix, coeffs_pair(ks, x)));
+
+            // [16] a3 a2 b3 b2 g3 g2 r3 r2
+            let pix = u8x16_shuffle::<8, 16, 12, 16, 9, 16, 13, 16, 10, 16, 14, 16, 11, 16, 15, 16>(
+                source, zero,
+            );
+            sss = i32x4_add(sss, i32x4_dot_i16x8(pix, coeffs_pair(ks, x + 2)));
+
+            x += 4;
+        }
+
+        // Two pixels per iteration
+        while x < ks.len().saturating_sub(1) {
+            let src_ptr = src_row.get_unchecked(x_start + x..).as_ptr() as *const u64;
+            let source = v128_load64_zero(src_ptr);
+
+            let pix = u8x16_shuffle::<0, 16, 4, 16, 1, 16, 5, 16, 2, 16, 6, 16, 3, 16, 7, 16>(
+                source, zero,
+            );
+            sss = i32x4_add(sss, i32x4_dot_i16x8(pix, coeffs_pair(ks, x)));
+
+            x += 2;
+        }
+
+        if let Some(&k) = ks.get(x) {
+            let src_ptr = src_row.get_unchecked(x_start + x..).as_ptr();
+            // [16] xx a0 xx b0 xx g0 xx r0
+            let pix = u8x16_shuffle::<0, 16, 16, 16, 1, 16, 16, 16, 2, 16, 16, 16, 3, 16, 16, 16>(
+                v128_load32_zero(src_ptr),
+                zero,
+            );
+            sss = i32x4_add(sss, i32x4_dot_i16x8(pix, i32x4_splat(k as i32)));
+        }
+
+        sss = i32x4_shr(sss, precision as u32);
+        let res = i16x8_narrow_i32x4(sss, sss);
+        let res = u8x16_narrow_i16x8(res, res);
+        *dst_row.get_unchecked_mut(dst_x) = i32x4_extract_lane::<0>(res) as u32;
+    }
+}
@@ -0,0 +1,75 @@
+use std::arch::x86_64::*;
+
+use super::{row_as_floats, row_as_floats_mut, vert_convolution_tail};
+use crate::convolution::{CoefficientsChunk, CoefficientsView};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::Pixel;
+
+/// Caller must guarantee that components of `P::Type` are `f32` values.
+#[inline]
+pub(crate) fn vert_convolution<P: Pixel>(
+    src_image: TypedImageView<P>,
+    mut dst_image: TypedImageViewMut<P>,
+    coeffs: CoefficientsView<f64>,
+) {
+    let coefficients_chunks = coeffs.chunks();
+    let dst_rows = dst_image.iter_rows_mut();
+    for (dst_row, coeffs_chunk) in dst_rows.zip(coefficients_chunks) {
+        unsafe {
+            vert_convolution_row(&src_image, dst_row, coeffs_chunk);
+        }
+    }
+}
+
+/// Components of pixels are accumulated as `f64` values,
+/// sixteen components per iteration of the main loop.
+#[target_feature(enable = "avx2,fma")]
+unsafe fn vert_convolution_row<P: Pixel>(
+    src_img: &TypedImageView<P>,
+    dst_row: &mut [P::Type],
+    coeffs_chunk: CoefficientsChunk,
+) {
+    let dst_row = row_as_floats_mut(dst_row);
+    let y_start = coeffs_chunk.start;
+    let max_y = y_start + coeffs_chunk.values.len() as u32;
+
+    let mut x: usize = 0;
+    while x + 16 <= dst_row.len() {
+        let mut sss = [_mm256_setzero_pd(); 4];
+
+        let src_rows = src_img.iter_rows(y_start, max_y);
+        for (src_row, &k) in src_rows.zip(coeffs_chunk.values) {
+            let src_row = row_as_floats(src_row);
+            let mmk = _mm256_set1_pd(k);
+            for (i, sum) in sss.iter_mut().enumerate() {
+                let source = _mm_loadu_ps(src_row.get_unchecked(x + i * 4..).as_ptr());
+                *sum = _mm256_fmadd_pd(_mm256_cvtps_pd(source), mmk, *sum);
+            }
+        }
+
+        for (i, &sum) in sss.iter().enumerate() {
+            let dst_ptr = dst_row.get_unchecked_mut(x + i * 4..).as_mut_ptr();
+            _mm_storeu_ps(dst_ptr, _mm256_cvtpd_ps(sum));
+        }
+        x += 16;
+    }
+
+    while x + 4 <= dst_row.len() {
+        let mut sum = _mm256_setzero_pd();
+
+        let src_rows = src_img.iter_rows(y_start, max_y);
+        for (src_row, &k) in src_rows.zip(coeffs_chunk.values) {
+            let src_row = row_as_floats(src_row);
+            let source = _mm_loadu_ps(src_row.get_unchecked(x..).as_ptr());
+            sum = _mm256_fmadd_pd(_mm256_cvtps_pd(source), _mm256_set1_pd(k), sum);
+        }
+
+        _mm_storeu_ps(
+            dst_row.get_unchecked_mut(x..).as_mut_ptr(),
+            _mm256_cvtpd_ps(sum),
+        );
+        x += 4;
+    }
+
+    vert_convolution_tail(src_img, dst_row, x, coeffs_chunk);
+}
@@ -0,0 +1,54 @@
+//! Vertical convolution doesn't mix components of pixels, so for all types
+//! of pixels with f32 components rows of image can be processed
+//! as rows of floats.
+#[cfg(target_arch = "x86_64")]
+pub(crate) mod avx2;
+#[cfg(target_arch = "x86_64")]
+pub(crate) mod sse4;
+
+#[cfg(target_arch = "x86_64")]
+use crate::convolution::CoefficientsChunk;
+#[cfg(target_arch = "x86_64")]
+use crate::image_view::TypedImageView;
+#[cfg(target_arch = "x86_64")]
+use crate::pixels::Pixel;
+
+/// Returns components of pixels from the row as a slice of floats.
+///
+/// Caller must guarantee that `T` consists only of `f32` values.
+#[cfg(target_arch = "x86_64")]
+#[inline(always)]
+unsafe fn row_as_floats<T>(row: &[T]) -> &[f32] {
+    let len = std::mem::size_of_val(row) / std::mem::size_of::<f32>();
+    std::slice::from_raw_parts(row.as_ptr() as *const f32, len)
+}
+
+/// Caller must guarantee that `T` consists only of `f32` values.
+#[cfg(target_arch = "x86_64")]
+#[inline(always)]
+unsafe fn row_as_floats_mut<T>(row: &mut [T]) -> &mut [f32] {
+    let len = std::mem::size_of_val(row) / std::mem::size_of::<f32>();
+    std::slice::from_raw_parts_mut(row.as_mut_ptr() as *mut f32, len)
+}
+
+/// Calculates components of destination row starting from `x_start`
+/// without SIMD instructions.
+#[cfg(target_arch = "x86_64")]
+#[inline(always)]
+unsafe fn vert_convolution_tail<P: Pixel>(
+    src_img: &TypedImageView<P>,
+    dst_row: &mut [f32],
+    x_start: usize,
+    coeffs_chunk: CoefficientsChunk,
+) {
+    let y_start = coeffs_chunk.start;
+    let max_y = y_start + coeffs_chunk.values.len() as u32;
+    for (x, dst_value) in dst_row.iter_mut().enumerate().skip(x_start) {
+        let mut ss = 0.;
+        let src_rows = src_img.iter_rows(y_start, max_y);
+        for (src_row, &k) in src_rows.zip(coeffs_chunk.values) {
+            ss += *row_as_floats(src_row).get_unchecked(x) as f64 * k;
+        }
+        *dst_value = ss as f32;
+    }
+}
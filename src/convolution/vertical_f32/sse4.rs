@@ -0,0 +1,58 @@
+use std::arch::x86_64::*;
+
+use super::{row_as_floats, row_as_floats_mut, vert_convolution_tail};
+use crate::convolution::{CoefficientsChunk, CoefficientsView};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::Pixel;
+
+/// Caller must guarantee that components of `P::Type` are `f32` values.
+#[inline]
+pub(crate) fn vert_convolution<P: Pixel>(
+    src_image: TypedImageView<P>,
+    mut dst_image: TypedImageViewMut<P>,
+    coeffs: CoefficientsView<f64>,
+) {
+    let coefficients_chunks = coeffs.chunks();
+    let dst_rows = dst_image.iter_rows_mut();
+    for (dst_row, coeffs_chunk) in dst_rows.zip(coefficients_chunks) {
+        unsafe {
+            vert_convolution_row(&src_image, dst_row, coeffs_chunk);
+        }
+    }
+}
+
+/// Components of pixels are accumulated as `f64` values,
+/// four components per iteration of the inner loop.
+#[target_feature(enable = "sse4.1")]
+unsafe fn vert_convolution_row<P: Pixel>(
+    src_img: &TypedImageView<P>,
+    dst_row: &mut [P::Type],
+    coeffs_chunk: CoefficientsChunk,
+) {
+    let dst_row = row_as_floats_mut(dst_row);
+    let y_start = coeffs_chunk.start;
+    let max_y = y_start + coeffs_chunk.values.len() as u32;
+
+    let mut x: usize = 0;
+    while x + 4 <= dst_row.len() {
+        let mut sum_lo = _mm_setzero_pd();
+        let mut sum_hi = _mm_setzero_pd();
+
+        let src_rows = src_img.iter_rows(y_start, max_y);
+        for (src_row, &k) in src_rows.zip(coeffs_chunk.values) {
+            let src_row = row_as_floats(src_row);
+            let mmk = _mm_set1_pd(k);
+            let source = _mm_loadu_ps(src_row.get_unchecked(x..).as_ptr());
+            let lo = _mm_cvtps_pd(source);
+            let hi = _mm_cvtps_pd(_mm_movehl_ps(source, source));
+            sum_lo = _mm_add_pd(sum_lo, _mm_mul_pd(lo, mmk));
+            sum_hi = _mm_add_pd(sum_hi, _mm_mul_pd(hi, mmk));
+        }
+
+        let res = _mm_movelh_ps(_mm_cvtpd_ps(sum_lo), _mm_cvtpd_ps(sum_hi));
+        _mm_storeu_ps(dst_row.get_unchecked_mut(x..).as_mut_ptr(), res);
+        x += 4;
+    }
+
+    vert_convolution_tail(src_img, dst_row, x, coeffs_chunk);
+}
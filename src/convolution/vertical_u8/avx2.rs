@@ -0,0 +1,173 @@
+use std::arch::x86_64::*;
+
+use super::{row_as_bytes, row_as_bytes_mut};
+use crate::convolution::{Bound, CoefficientsView};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::Pixel;
+use crate::simd_utils;
+
+#[inline]
+pub(crate) fn vert_convolution<P: Pixel>(
+    src_image: TypedImageView<P>,
+    mut dst_image: TypedImageViewMut<P>,
+    coeffs: CoefficientsView<i16>,
+) {
+    let precision = coeffs.precision();
+
+    let dst_rows = dst_image.iter_rows_mut();
+    for (coeffs_chunk, dst_row) in coeffs.chunks().zip(dst_rows) {
+        let (k, bound) = (coeffs_chunk.values, coeffs_chunk.bound());
+        unsafe {
+            vert_convolution_8u(&src_image, dst_row, k, bound, precision);
+        }
+    }
+}
+
+/// Vertical convolution doesn't mix components of pixels,
+/// so rows of image are processed as rows of bytes.
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn vert_convolution_8u<P: Pixel>(
+    src_img: &TypedImageView<P>,
+    dst_row: &mut [P::Type],
+    coeffs: &[i16],
+    bound: Bound,
+    precision: u8,
+) {
+    let dst_row = row_as_bytes_mut(dst_row);
+    let src_width = dst_row.len();
+    let y_start = bound.start;
+    let y_size = bound.size;
+
+    let initial = _mm_set1_epi32(1 << (precision - 1));
+    let initial_256 = _mm256_set1_epi32(1 << (precision - 1));
+
+    let mut x: usize = 0;
+    while x < src_width.saturating_sub(31) {
+        let mut sss0 = initial_256;
+        let mut sss1 = initial_256;
+        let mut sss2 = initial_256;
+        let mut sss3 = initial_256;
+
+        let mut y: u32 = 0;
+
+        for (s_row1, s_row2) in src_img.iter_2_rows(y_start, y_start + y_size) {
+            let s_row1 = row_as_bytes(s_row1);
+            let s_row2 = row_as_bytes(s_row2);
+            // Load two coefficients at once
+            let mmk = simd_utils::ptr_i16_to_256set1_epi32(coeffs, y as usize);
+
+            let source1 = simd_utils::loadu_si256(s_row1, x); // top line
+            let source2 = simd_utils::loadu_si256(s_row2, x); // bottom line
+
+            let mut source = _mm256_unpacklo_epi8(source1, source2);
+            let mut pix = _mm256_unpacklo_epi8(source, _mm256_setzero_si256());
+            sss0 = _mm256_add_epi32(sss0, _mm256_madd_epi16(pix, mmk));
+            pix = _mm256_unpackhi_epi8(source, _mm256_setzero_si256());
+            sss1 = _mm256_add_epi32(sss1, _mm256_madd_epi16(pix, mmk));
+
+            source = _mm256_unpackhi_epi8(source1, source2);
+            pix = _mm256_unpacklo_epi8(source, _mm256_setzero_si256());
+            sss2 = _mm256_add_epi32(sss2, _mm256_madd_epi16(pix, mmk));
+            pix = _mm256_unpackhi_epi8(source, _mm256_setzero_si256());
+            sss3 = _mm256_add_epi32(sss3, _mm256_madd_epi16(pix, mmk));
+
+            y += 2;
+        }
+
+        for s_row in src_img.iter_rows(y_start + y, y_start + y_size) {
+            let s_row = row_as_bytes(s_row);
+            let mmk = _mm256_set1_epi32(*coeffs.get_unchecked(y as usize) as i32);
+
+            let source1 = simd_utils::loadu_si256(s_row, x); // top line
+
+            let mut source = _mm256_unpacklo_epi8(source1, _mm256_setzero_si256());
+            let mut pix = _mm256_unpacklo_epi8(source, _mm256_setzero_si256());
+            sss0 = _mm256_add_epi32(sss0, _mm256_madd_epi16(pix, mmk));
+            pix = _mm256_unpackhi_epi8(source, _mm256_setzero_si256());
+            sss1 = _mm256_add_epi32(sss1, _mm256_madd_epi16(pix, mmk));
+
+            source = _mm256_unpackhi_epi8(source1, _mm256_setzero_si256());
+            pix = _mm256_unpacklo_epi8(source, _mm256_setzero_si256());
+            sss2 = _mm256_add_epi32(sss2, _mm256_madd_epi16(pix, mmk));
+            pix = _mm256_unpackhi_epi8(source, _mm256_setzero_si256());
+            sss3 = _mm256_add_epi32(sss3, _mm256_madd_epi16(pix, mmk));
+
+            y += 1;
+        }
+
+        macro_rules! call {
+            ($imm8:expr) => {{
+                sss0 = _mm256_srai_epi32::<$imm8>(sss0);
+                sss1 = _mm256_srai_epi32::<$imm8>(sss1);
+                sss2 = _mm256_srai_epi32::<$imm8>(sss2);
+                sss3 = _mm256_srai_epi32::<$imm8>(sss3);
+            }};
+        }
+        constify_imm8!(precision, call);
+
+        sss0 = _mm256_packs_epi32(sss0, sss1);
+        sss2 = _mm256_packs_epi32(sss2, sss3);
+        sss0 = _mm256_packus_epi16(sss0, sss2);
+        let dst_ptr = dst_row.get_unchecked_mut(x..).as_mut_ptr() as *mut __m256i;
+        _mm256_storeu_si256(dst_ptr, sss0);
+
+        x += 32;
+    }
+
+    while x < src_width.saturating_sub(7) {
+        let mut sss0 = initial;
+        let mut sss1 = initial;
+        let mut y: u32 = 0;
+
+        for (s_row1, s_row2) in src_img.iter_2_rows(y_start, y_start + y_size) {
+            let s_row1 = row_as_bytes(s_row1);
+            let s_row2 = row_as_bytes(s_row2);
+            // Load two coefficients at once
+            let mmk = simd_utils::ptr_i16_to_set1_epi32(coeffs, y as usize);
+
+            let source1 = simd_utils::loadl_epi64(s_row1, x); // top line
+            let source2 = simd_utils::loadl_epi64(s_row2, x); // bottom line
+
+            let source = _mm_unpacklo_epi8(source1, source2);
+            let mut pix = _mm_unpacklo_epi8(source, _mm_setzero_si128());
+            sss0 = _mm_add_epi32(sss0, _mm_madd_epi16(pix, mmk));
+            pix = _mm_unpackhi_epi8(source, _mm_setzero_si128());
+            sss1 = _mm_add_epi32(sss1, _mm_madd_epi16(pix, mmk));
+
+            y += 2;
+        }
+
+        for s_row in src_img.iter_rows(y_start + y, y_start + y_size) {
+            let s_row = row_as_bytes(s_row);
+            let mmk = _mm_set1_epi32(*coeffs.get_unchecked(y as usize) as i32);
+
+            let source1 = simd_utils::loadl_epi64(s_row, x); // top line
+
+            let source = _mm_unpacklo_epi8(source1, _mm_setzero_si128());
+            let mut pix = _mm_unpacklo_epi8(source, _mm_setzero_si128());
+            sss0 = _mm_add_epi32(sss0, _mm_madd_epi16(pix, mmk));
+            pix = _mm_unpackhi_epi8(source, _mm_setzero_si128());
+            sss1 = _mm_add_epi32(sss1, _mm_madd_epi16(pix, mmk));
+
+            y += 1;
+        }
+
+        macro_rules! call {
+            ($imm8:expr) => {{
+                sss0 = _mm_srai_epi32::<$imm8>(sss0);
+                sss1 = _mm_srai_epi32::<$imm8>(sss1);
+            }};
+        }
+        constify_imm8!(precision, call);
+
+        sss0 = _mm_packs_epi32(sss0, sss1);
+        sss0 = _mm_packus_epi16(sss0, sss0);
+        let dst_ptr = dst_row.get_unchecked_mut(x..).as_mut_ptr() as *mut __m128i;
+        _mm_storel_epi64(dst_ptr, sss0);
+
+        x += 8;
+    }
+
+    super::sse4::vert_convolution_8u_tail(src_img, &mut dst_row[x..], x, coeffs, bound, precision);
+}
@@ -0,0 +1,133 @@
+use std::arch::x86_64::*;
+
+use super::{row_as_bytes, row_as_bytes_mut};
+use crate::convolution::{Bound, CoefficientsView};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::Pixel;
+use crate::simd_utils;
+
+#[inline]
+pub(crate) fn vert_convolution<P: Pixel>(
+    src_image: TypedImageView<P>,
+    mut dst_image: TypedImageViewMut<P>,
+    coeffs: CoefficientsView<i16>,
+) {
+    let precision = coeffs.precision();
+
+    let dst_rows = dst_image.iter_rows_mut();
+    for (coeffs_chunk, dst_row) in coeffs.chunks().zip(dst_rows) {
+        let (k, bound) = (coeffs_chunk.values, coeffs_chunk.bound());
+        unsafe {
+            vert_convolution_8u(&src_image, dst_row, k, bound, precision);
+        }
+    }
+}
+
+/// Vertical convolution doesn't mix components of pixels,
+/// so rows of image are processed as rows of bytes.
+/// The last bytes of rows are processed with help of masked loads and stores.
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw")]
+unsafe fn vert_convolution_8u<P: Pixel>(
+    src_img: &TypedImageView<P>,
+    dst_row: &mut [P::Type],
+    coeffs: &[i16],
+    bound: Bound,
+    precision: u8,
+) {
+    let dst_row = row_as_bytes_mut(dst_row);
+    let src_width = dst_row.len();
+    let y_start = bound.start;
+    let y_size = bound.size;
+
+    let zero = _mm512_setzero_si512();
+    let initial = _mm512_set1_epi32(1 << (precision - 1));
+
+    let mut x: usize = 0;
+    while x < src_width {
+        let rest = src_width - x;
+        let mask: __mmask64 = if rest >= 64 {
+            u64::MAX
+        } else {
+            (1u64 << rest) - 1
+        };
+
+        let mut sss0 = initial;
+        let mut sss1 = initial;
+        let mut sss2 = initial;
+        let mut sss3 = initial;
+
+        let mut y: u32 = 0;
+
+        for (s_row1, s_row2) in src_img.iter_2_rows(y_start, y_start + y_size) {
+            let s_row1 = row_as_bytes(s_row1);
+            let s_row2 = row_as_bytes(s_row2);
+            // Load two coefficients at once
+            let mmk = simd_utils::ptr_i16_to_512set1_epi32(coeffs, y as usize);
+
+            let source1 = load_bytes(s_row1, x, mask); // top line
+            let source2 = load_bytes(s_row2, x, mask); // bottom line
+
+            let mut source = _mm512_unpacklo_epi8(source1, source2);
+            let mut pix = _mm512_unpacklo_epi8(source, zero);
+            sss0 = _mm512_add_epi32(sss0, _mm512_madd_epi16(pix, mmk));
+            pix = _mm512_unpackhi_epi8(source, zero);
+            sss1 = _mm512_add_epi32(sss1, _mm512_madd_epi16(pix, mmk));
+
+            source = _mm512_unpackhi_epi8(source1, source2);
+            pix = _mm512_unpacklo_epi8(source, zero);
+            sss2 = _mm512_add_epi32(sss2, _mm512_madd_epi16(pix, mmk));
+            pix = _mm512_unpackhi_epi8(source, zero);
+            sss3 = _mm512_add_epi32(sss3, _mm512_madd_epi16(pix, mmk));
+
+            y += 2;
+        }
+
+        for s_row in src_img.iter_rows(y_start + y, y_start + y_size) {
+            let s_row = row_as_bytes(s_row);
+            let mmk = _mm512_set1_epi32(*coeffs.get_unchecked(y as usize) as i32);
+
+            let source1 = load_bytes(s_row, x, mask); // top line
+
+            let mut source = _mm512_unpacklo_epi8(source1, zero);
+            let mut pix = _mm512_unpacklo_epi8(source, zero);
+            sss0 = _mm512_add_epi32(sss0, _mm512_madd_epi16(pix, mmk));
+            pix = _mm512_unpackhi_epi8(source, zero);
+            sss1 = _mm512_add_epi32(sss1, _mm512_madd_epi16(pix, mmk));
+
+            source = _mm512_unpackhi_epi8(source1, zero);
+            pix = _mm512_unpacklo_epi8(source, zero);
+            sss2 = _mm512_add_epi32(sss2, _mm512_madd_epi16(pix, mmk));
+            pix = _mm512_unpackhi_epi8(source, zero);
+            sss3 = _mm512_add_epi32(sss3, _mm512_madd_epi16(pix, mmk));
+
+            y += 1;
+        }
+
+        macro_rules! call {
+            ($imm8:expr) => {{
+                sss0 = _mm512_srai_epi32::<$imm8>(sss0);
+                sss1 = _mm512_srai_epi32::<$imm8>(sss1);
+                sss2 = _mm512_srai_epi32::<$imm8>(sss2);
+                sss3 = _mm512_srai_epi32::<$imm8>(sss3);
+            }};
+        }
+        constify_imm8!(precision, call);
+
+        sss0 = _mm512_packs_epi32(sss0, sss1);
+        sss2 = _mm512_packs_epi32(sss2, sss3);
+        sss0 = _mm512_packus_epi16(sss0, sss2);
+        let dst_ptr = dst_row.get_unchecked_mut(x..).as_mut_ptr() as *mut i8;
+        _mm512_mask_storeu_epi8(dst_ptr, mask, sss0);
+
+        x += 64;
+    }
+}
+
+/// Loads bytes selected by the mask. Bytes beyond the mask are not read
+/// from memory and are set to zero.
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw")]
+unsafe fn load_bytes(row: &[u8], index: usize, mask: __mmask64) -> __m512i {
+    _mm512_maskz_loadu_epi8(mask, row.get_unchecked(index..).as_ptr() as *const i8)
+}
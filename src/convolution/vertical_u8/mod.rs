@@ -0,0 +1,35 @@
+//! Vertical convolution doesn't mix components of pixels, so for all types
+//! of pixels with 8-bit components rows of image can be processed
+//! as rows of bytes.
+#[cfg(target_arch = "x86_64")]
+pub(crate) mod avx2;
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+pub(crate) mod avx512;
+#[cfg(target_arch = "aarch64")]
+pub(crate) mod neon;
+#[cfg(target_arch = "x86_64")]
+pub(crate) mod sse4;
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+pub(crate) mod wasm32;
+
+/// Returns components of pixels from the row as a slice of bytes.
+#[cfg(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    all(target_arch = "wasm32", target_feature = "simd128")
+))]
+#[inline(always)]
+fn row_as_bytes<T>(row: &[T]) -> &[u8] {
+    unsafe { std::slice::from_raw_parts(row.as_ptr() as *const u8, std::mem::size_of_val(row)) }
+}
+
+#[cfg(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    all(target_arch = "wasm32", target_feature = "simd128")
+))]
+#[inline(always)]
+fn row_as_bytes_mut<T>(row: &mut [T]) -> &mut [u8] {
+    let size = std::mem::size_of_val(row);
+    unsafe { std::slice::from_raw_parts_mut(row.as_mut_ptr() as *mut u8, size) }
+}
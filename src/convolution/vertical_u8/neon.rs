@@ -0,0 +1,109 @@
+use std::arch::aarch64::*;
+
+use super::{row_as_bytes, row_as_bytes_mut};
+use crate::convolution::{optimisations, Bound, CoefficientsView};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::Pixel;
+
+#[inline]
+pub(crate) fn vert_convolution<P: Pixel>(
+    src_image: TypedImageView<P>,
+    mut dst_image: TypedImageViewMut<P>,
+    coeffs: CoefficientsView<i16>,
+) {
+    let precision = coeffs.precision();
+
+    let dst_rows = dst_image.iter_rows_mut();
+    for (coeffs_chunk, dst_row) in coeffs.chunks().zip(dst_rows) {
+        let (k, bound) = (coeffs_chunk.values, coeffs_chunk.bound());
+        unsafe {
+            vert_convolution_8u(&src_image, dst_row, k, bound, precision);
+        }
+    }
+}
+
+/// Vertical convolution doesn't mix components of pixels,
+/// so rows of image are processed as rows of bytes.
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn vert_convolution_8u<P: Pixel>(
+    src_img: &TypedImageView<P>,
+    dst_row: &mut [P::Type],
+    coeffs: &[i16],
+    bound: Bound,
+    precision: u8,
+) {
+    let dst_row = row_as_bytes_mut(dst_row);
+    let src_width = dst_row.len();
+    let y_start = bound.start;
+    let y_end = bound.start + bound.size;
+
+    let initial = vdupq_n_s32(1 << (precision - 1));
+    // Shifting to the left by negative value is shifting to the right.
+    let shift = vdupq_n_s32(-(precision as i32));
+
+    let mut xx: usize = 0;
+    while xx < src_width.saturating_sub(15) {
+        let mut sss0 = initial;
+        let mut sss1 = initial;
+        let mut sss2 = initial;
+        let mut sss3 = initial;
+
+        for (s_row, &k) in src_img.iter_rows(y_start, y_end).zip(coeffs) {
+            let s_row = row_as_bytes(s_row);
+            let source = vld1q_u8(s_row.get_unchecked(xx..).as_ptr());
+
+            let pix = vreinterpretq_s16_u16(vmovl_u8(vget_low_u8(source)));
+            sss0 = vmlal_n_s16(sss0, vget_low_s16(pix), k);
+            sss1 = vmlal_n_s16(sss1, vget_high_s16(pix), k);
+
+            let pix = vreinterpretq_s16_u16(vmovl_u8(vget_high_u8(source)));
+            sss2 = vmlal_n_s16(sss2, vget_low_s16(pix), k);
+            sss3 = vmlal_n_s16(sss3, vget_high_s16(pix), k);
+        }
+
+        let res = vcombine_u8(pack_to_u8(sss0, sss1, shift), pack_to_u8(sss2, sss3, shift));
+        vst1q_u8(dst_row.get_unchecked_mut(xx..).as_mut_ptr(), res);
+
+        xx += 16;
+    }
+
+    while xx < src_width.saturating_sub(7) {
+        let mut sss0 = initial;
+        let mut sss1 = initial;
+
+        for (s_row, &k) in src_img.iter_rows(y_start, y_end).zip(coeffs) {
+            let s_row = row_as_bytes(s_row);
+            let source = vld1_u8(s_row.get_unchecked(xx..).as_ptr());
+
+            let pix = vreinterpretq_s16_u16(vmovl_u8(source));
+            sss0 = vmlal_n_s16(sss0, vget_low_s16(pix), k);
+            sss1 = vmlal_n_s16(sss1, vget_high_s16(pix), k);
+        }
+
+        vst1_u8(
+            dst_row.get_unchecked_mut(xx..).as_mut_ptr(),
+            pack_to_u8(sss0, sss1, shift),
+        );
+
+        xx += 8;
+    }
+
+    for (x, dst_value) in dst_row.iter_mut().enumerate().skip(xx) {
+        let mut ss0 = 1 << (precision - 1);
+        for (s_row, &k) in src_img.iter_rows(y_start, y_end).zip(coeffs) {
+            let s_row = row_as_bytes(s_row);
+            ss0 += *s_row.get_unchecked(x) as i32 * (k as i32);
+        }
+        *dst_value = optimisations::clip8(ss0, precision);
+    }
+}
+
+/// Shifts sums to the right by `precision` bits (`shift` must contain
+/// negative value of precision) and packs them into bytes with saturation.
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn pack_to_u8(sss0: int32x4_t, sss1: int32x4_t, shift: int32x4_t) -> uint8x8_t {
+    let res0 = vqmovun_s32(vshlq_s32(sss0, shift));
+    let res1 = vqmovun_s32(vshlq_s32(sss1, shift));
+    vqmovn_u16(vcombine_u16(res0, res1))
+}
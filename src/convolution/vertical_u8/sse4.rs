@@ -0,0 +1,236 @@
+use std::arch::x86_64::*;
+
+use super::{row_as_bytes, row_as_bytes_mut};
+use crate::convolution::{optimisations, Bound, CoefficientsView};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::Pixel;
+use crate::simd_utils;
+
+#[inline]
+pub(crate) fn vert_convolution<P: Pixel>(
+    src_image: TypedImageView<P>,
+    mut dst_image: TypedImageViewMut<P>,
+    coeffs: CoefficientsView<i16>,
+) {
+    let precision = coeffs.precision();
+
+    let dst_rows = dst_image.iter_rows_mut();
+    for (coeffs_chunk, dst_row) in coeffs.chunks().zip(dst_rows) {
+        let (k, bound) = (coeffs_chunk.values, coeffs_chunk.bound());
+        unsafe {
+            vert_convolution_8u(&src_image, dst_row, k, bound, precision);
+        }
+    }
+}
+
+/// Vertical convolution doesn't mix components of pixels,
+/// so rows of image are processed as rows of bytes.
+#[target_feature(enable = "sse4.1")]
+pub(crate) unsafe fn vert_convolution_8u<P: Pixel>(
+    src_img: &TypedImageView<P>,
+    dst_row: &mut [P::Type],
+    coeffs: &[i16],
+    bound: Bound,
+    precision: u8,
+) {
+    let dst_row = row_as_bytes_mut(dst_row);
+    let src_width = dst_row.len();
+    let y_start = bound.start;
+    let y_size = bound.size;
+
+    let initial = _mm_set1_epi32(1 << (precision - 1));
+
+    let mut xx: usize = 0;
+    while xx < src_width.saturating_sub(31) {
+        let mut sss0 = initial;
+        let mut sss1 = initial;
+        let mut sss2 = initial;
+        let mut sss3 = initial;
+        let mut sss4 = initial;
+        let mut sss5 = initial;
+        let mut sss6 = initial;
+        let mut sss7 = initial;
+
+        let mut y: u32 = 0;
+
+        for (s_row1, s_row2) in src_img.iter_2_rows(y_start, y_start + y_size) {
+            let s_row1 = row_as_bytes(s_row1);
+            let s_row2 = row_as_bytes(s_row2);
+            // Load two coefficients at once
+            let mmk = simd_utils::ptr_i16_to_set1_epi32(coeffs, y as usize);
+
+            let mut source1 = simd_utils::loadu_si128(s_row1, xx); // top line
+            let mut source2 = simd_utils::loadu_si128(s_row2, xx); // bottom line
+
+            let mut source = _mm_unpacklo_epi8(source1, source2);
+            let mut pix = _mm_unpacklo_epi8(source, _mm_setzero_si128());
+            sss0 = _mm_add_epi32(sss0, _mm_madd_epi16(pix, mmk));
+            pix = _mm_unpackhi_epi8(source, _mm_setzero_si128());
+            sss1 = _mm_add_epi32(sss1, _mm_madd_epi16(pix, mmk));
+
+            source = _mm_unpackhi_epi8(source1, source2);
+            pix = _mm_unpacklo_epi8(source, _mm_setzero_si128());
+            sss2 = _mm_add_epi32(sss2, _mm_madd_epi16(pix, mmk));
+            pix = _mm_unpackhi_epi8(source, _mm_setzero_si128());
+            sss3 = _mm_add_epi32(sss3, _mm_madd_epi16(pix, mmk));
+
+            source1 = simd_utils::loadu_si128(s_row1, xx + 16); // top line
+            source2 = simd_utils::loadu_si128(s_row2, xx + 16); // bottom line
+
+            source = _mm_unpacklo_epi8(source1, source2);
+            pix = _mm_unpacklo_epi8(source, _mm_setzero_si128());
+            sss4 = _mm_add_epi32(sss4, _mm_madd_epi16(pix, mmk));
+            pix = _mm_unpackhi_epi8(source, _mm_setzero_si128());
+            sss5 = _mm_add_epi32(sss5, _mm_madd_epi16(pix, mmk));
+
+            source = _mm_unpackhi_epi8(source1, source2);
+            pix = _mm_unpacklo_epi8(source, _mm_setzero_si128());
+            sss6 = _mm_add_epi32(sss6, _mm_madd_epi16(pix, mmk));
+            pix = _mm_unpackhi_epi8(source, _mm_setzero_si128());
+            sss7 = _mm_add_epi32(sss7, _mm_madd_epi16(pix, mmk));
+
+            y += 2;
+        }
+
+        for s_row in src_img.iter_rows(y_start + y, y_start + y_size) {
+            let s_row = row_as_bytes(s_row);
+            let mmk = _mm_set1_epi32(*coeffs.get_unchecked(y as usize) as i32);
+
+            let mut source1 = simd_utils::loadu_si128(s_row, xx); // top line
+
+            let mut source = _mm_unpacklo_epi8(source1, _mm_setzero_si128());
+            let mut pix = _mm_unpacklo_epi8(source, _mm_setzero_si128());
+            sss0 = _mm_add_epi32(sss0, _mm_madd_epi16(pix, mmk));
+            pix = _mm_unpackhi_epi8(source, _mm_setzero_si128());
+            sss1 = _mm_add_epi32(sss1, _mm_madd_epi16(pix, mmk));
+
+            source = _mm_unpackhi_epi8(source1, _mm_setzero_si128());
+            pix = _mm_unpacklo_epi8(source, _mm_setzero_si128());
+            sss2 = _mm_add_epi32(sss2, _mm_madd_epi16(pix, mmk));
+            pix = _mm_unpackhi_epi8(source, _mm_setzero_si128());
+            sss3 = _mm_add_epi32(sss3, _mm_madd_epi16(pix, mmk));
+
+            source1 = simd_utils::loadu_si128(s_row, xx + 16); // top line
+
+            source = _mm_unpacklo_epi8(source1, _mm_setzero_si128());
+            pix = _mm_unpacklo_epi8(source, _mm_setzero_si128());
+            sss4 = _mm_add_epi32(sss4, _mm_madd_epi16(pix, mmk));
+            pix = _mm_unpackhi_epi8(source, _mm_setzero_si128());
+            sss5 = _mm_add_epi32(sss5, _mm_madd_epi16(pix, mmk));
+
+            source = _mm_unpackhi_epi8(source1, _mm_setzero_si128());
+            pix = _mm_unpacklo_epi8(source, _mm_setzero_si128());
+            sss6 = _mm_add_epi32(sss6, _mm_madd_epi16(pix, mmk));
+            pix = _mm_unpackhi_epi8(source, _mm_setzero_si128());
+            sss7 = _mm_add_epi32(sss7, _mm_madd_epi16(pix, mmk));
+
+            y += 1;
+        }
+
+        macro_rules! call {
+            ($imm8:expr) => {{
+                sss0 = _mm_srai_epi32::<$imm8>(sss0);
+                sss1 = _mm_srai_epi32::<$imm8>(sss1);
+                sss2 = _mm_srai_epi32::<$imm8>(sss2);
+                sss3 = _mm_srai_epi32::<$imm8>(sss3);
+                sss4 = _mm_srai_epi32::<$imm8>(sss4);
+                sss5 = _mm_srai_epi32::<$imm8>(sss5);
+                sss6 = _mm_srai_epi32::<$imm8>(sss6);
+                sss7 = _mm_srai_epi32::<$imm8>(sss7);
+            }};
+        }
+        constify_imm8!(precision, call);
+
+        sss0 = _mm_packs_epi32(sss0, sss1);
+        sss2 = _mm_packs_epi32(sss2, sss3);
+        sss0 = _mm_packus_epi16(sss0, sss2);
+        let dst_ptr = dst_row.get_unchecked_mut(xx..).as_mut_ptr() as *mut __m128i;
+        _mm_storeu_si128(dst_ptr, sss0);
+        sss4 = _mm_packs_epi32(sss4, sss5);
+        sss6 = _mm_packs_epi32(sss6, sss7);
+        sss4 = _mm_packus_epi16(sss4, sss6);
+        let dst_ptr = dst_row.get_unchecked_mut(xx + 16..).as_mut_ptr() as *mut __m128i;
+        _mm_storeu_si128(dst_ptr, sss4);
+
+        xx += 32;
+    }
+
+    while xx < src_width.saturating_sub(7) {
+        let mut sss0 = initial;
+        let mut sss1 = initial;
+        let mut y: u32 = 0;
+
+        for (s_row1, s_row2) in src_img.iter_2_rows(y_start, y_start + y_size) {
+            let s_row1 = row_as_bytes(s_row1);
+            let s_row2 = row_as_bytes(s_row2);
+            // Load two coefficients at once
+            let mmk = simd_utils::ptr_i16_to_set1_epi32(coeffs, y as usize);
+
+            let source1 = simd_utils::loadl_epi64(s_row1, xx); // top line
+            let source2 = simd_utils::loadl_epi64(s_row2, xx); // bottom line
+
+            let source = _mm_unpacklo_epi8(source1, source2);
+            let mut pix = _mm_unpacklo_epi8(source, _mm_setzero_si128());
+            sss0 = _mm_add_epi32(sss0, _mm_madd_epi16(pix, mmk));
+            pix = _mm_unpackhi_epi8(source, _mm_setzero_si128());
+            sss1 = _mm_add_epi32(sss1, _mm_madd_epi16(pix, mmk));
+
+            y += 2;
+        }
+
+        for s_row in src_img.iter_rows(y_start + y, y_start + y_size) {
+            let s_row = row_as_bytes(s_row);
+            let mmk = _mm_set1_epi32(*coeffs.get_unchecked(y as usize) as i32);
+
+            let source1 = simd_utils::loadl_epi64(s_row, xx); // top line
+
+            let source = _mm_unpacklo_epi8(source1, _mm_setzero_si128());
+            let mut pix = _mm_unpacklo_epi8(source, _mm_setzero_si128());
+            sss0 = _mm_add_epi32(sss0, _mm_madd_epi16(pix, mmk));
+            pix = _mm_unpackhi_epi8(source, _mm_setzero_si128());
+            sss1 = _mm_add_epi32(sss1, _mm_madd_epi16(pix, mmk));
+
+            y += 1;
+        }
+
+        macro_rules! call {
+            ($imm8:expr) => {{
+                sss0 = _mm_srai_epi32::<$imm8>(sss0);
+                sss1 = _mm_srai_epi32::<$imm8>(sss1);
+            }};
+        }
+        constify_imm8!(precision, call);
+
+        sss0 = _mm_packs_epi32(sss0, sss1);
+        sss0 = _mm_packus_epi16(sss0, sss0);
+        let dst_ptr = dst_row.get_unchecked_mut(xx..).as_mut_ptr() as *mut __m128i;
+        _mm_storel_epi64(dst_ptr, sss0);
+
+        xx += 8;
+    }
+
+    vert_convolution_8u_tail(src_img, &mut dst_row[xx..], xx, coeffs, bound, precision);
+}
+
+/// Calculates the last bytes of row that not fit into SIMD registers.
+#[inline(always)]
+pub(crate) fn vert_convolution_8u_tail<P: Pixel>(
+    src_img: &TypedImageView<P>,
+    dst_bytes: &mut [u8],
+    start_x: usize,
+    coeffs: &[i16],
+    bound: Bound,
+    precision: u8,
+) {
+    let y_start = bound.start;
+    let y_size = bound.size;
+    for (x, dst_byte) in dst_bytes.iter_mut().enumerate() {
+        let x = start_x + x;
+        let mut ss = 1 << (precision - 1);
+        let src_rows = src_img.iter_rows(y_start, y_start + y_size);
+        for (&k, s_row) in coeffs.iter().zip(src_rows) {
+            ss += row_as_bytes(s_row)[x] as i32 * (k as i32);
+        }
+        *dst_byte = unsafe { optimisations::clip8(ss, precision) };
+    }
+}
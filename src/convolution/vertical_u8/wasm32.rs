@@ -0,0 +1,118 @@
+use std::arch::wasm32::*;
+
+use super::{row_as_bytes, row_as_bytes_mut};
+use crate::convolution::{optimisations, Bound, CoefficientsView};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::Pixel;
+
+#[inline]
+pub(crate) fn vert_convolution<P: Pixel>(
+    src_image: TypedImageView<P>,
+    mut dst_image: TypedImageViewMut<P>,
+    coeffs: CoefficientsView<i16>,
+) {
+    let precision = coeffs.precision();
+
+    let dst_rows = dst_image.iter_rows_mut();
+    for (coeffs_chunk, dst_row) in coeffs.chunks().zip(dst_rows) {
+        let (k, bound) = (coeffs_chunk.values, coeffs_chunk.bound());
+        unsafe {
+            vert_convolution_8u(&src_image, dst_row, k, bound, precision);
+        }
+    }
+}
+
+/// Vertical convolution doesn't mix components of pixels,
+/// so rows of image are processed as rows of bytes.
+#[target_feature(enable = "simd128")]
+pub(crate) unsafe fn vert_convolution_8u<P: Pixel>(
+    src_img: &TypedImageView<P>,
+    dst_row: &mut [P::Type],
+    coeffs: &[i16],
+    bound: Bound,
+    precision: u8,
+) {
+    let dst_row = row_as_bytes_mut(dst_row);
+    let src_width = dst_row.len();
+    let y_start = bound.start;
+    let y_end = bound.start + bound.size;
+
+    let initial = i32x4_splat(1 << (precision - 1));
+
+    let mut xx: usize = 0;
+    while xx < src_width.saturating_sub(15) {
+        let mut sss0 = initial;
+        let mut sss1 = initial;
+        let mut sss2 = initial;
+        let mut sss3 = initial;
+
+        for (s_row, &k) in src_img.iter_rows(y_start, y_end).zip(coeffs) {
+            let s_row = row_as_bytes(s_row);
+            let source = v128_load(s_row.get_unchecked(xx..).as_ptr() as *const v128);
+            let mmk = i16x8_splat(k);
+
+            let pix = u16x8_extend_low_u8x16(source);
+            sss0 = i32x4_add(sss0, i32x4_extmul_low_i16x8(pix, mmk));
+            sss1 = i32x4_add(sss1, i32x4_extmul_high_i16x8(pix, mmk));
+
+            let pix = u16x8_extend_high_u8x16(source);
+            sss2 = i32x4_add(sss2, i32x4_extmul_low_i16x8(pix, mmk));
+            sss3 = i32x4_add(sss3, i32x4_extmul_high_i16x8(pix, mmk));
+        }
+
+        let res = u8x16_narrow_i16x8(
+            pack_to_i16(sss0, sss1, precision),
+            pack_to_i16(sss2, sss3, precision),
+        );
+        v128_store(
+            dst_row.get_unchecked_mut(xx..).as_mut_ptr() as *mut v128,
+            res,
+        );
+
+        xx += 16;
+    }
+
+    while xx < src_width.saturating_sub(7) {
+        let mut sss0 = initial;
+        let mut sss1 = initial;
+
+        for (s_row, &k) in src_img.iter_rows(y_start, y_end).zip(coeffs) {
+            let s_row = row_as_bytes(s_row);
+            let source = v128_load64_zero(s_row.get_unchecked(xx..).as_ptr() as *const u64);
+            let mmk = i16x8_splat(k);
+
+            let pix = u16x8_extend_low_u8x16(source);
+            sss0 = i32x4_add(sss0, i32x4_extmul_low_i16x8(pix, mmk));
+            sss1 = i32x4_add(sss1, i32x4_extmul_high_i16x8(pix, mmk));
+        }
+
+        let res = pack_to_i16(sss0, sss1, precision);
+        let res = u8x16_narrow_i16x8(res, res);
+        v128_store64_lane::<0>(
+            res,
+            dst_row.get_unchecked_mut(xx..).as_mut_ptr() as *mut u64,
+        );
+
+        xx += 8;
+    }
+
+    for (x, dst_value) in dst_row.iter_mut().enumerate().skip(xx) {
+        let mut ss0 = 1 << (precision - 1);
+        for (s_row, &k) in src_img.iter_rows(y_start, y_end).zip(coeffs) {
+            let s_row = row_as_bytes(s_row);
+            ss0 += *s_row.get_unchecked(x) as i32 * (k as i32);
+        }
+        *dst_value = optimisations::clip8(ss0, precision);
+    }
+}
+
+/// Shifts sums to the right by `precision` bits and packs them
+/// into 16-bit values with saturation.
+#[inline]
+#[target_feature(enable = "simd128")]
+unsafe fn pack_to_i16(sss0: v128, sss1: v128, precision: u8) -> v128 {
+    i16x8_narrow_i32x4(
+        i32x4_shr(sss0, precision as u32),
+        i32x4_shr(sss1, precision as u32),
+    )
+}
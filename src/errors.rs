@@ -18,6 +18,8 @@ pub enum ImageBufferError {
     InvalidBufferSize,
     #[error("Alignment of buffer don't match to alignment of u32")]
     InvalidBufferAlignment,
+    #[error("Stride of rows is less than size of row")]
+    InvalidStride,
 }
 
 #[derive(Error, Debug, Clone, Copy)]
@@ -26,8 +28,40 @@ pub enum CropBoxError {
     PositionIsOutOfImageBoundaries,
     #[error("Size of the crop box is out of the image boundaries")]
     SizeIsOutOfImageBoundaries,
+    #[error("Width or height of the crop box is less than or equal to zero")]
+    WidthOrHeightLessOrEqualToZero,
 }
 
+#[derive(Error, Debug, Clone, Copy)]
+#[error("Support of filter must be a finite positive number")]
+pub struct InvalidFilterSupportError;
+
+#[derive(Error, Debug, Clone, Copy)]
+#[error("Parameters of filter are out of the allowed range")]
+pub struct InvalidFilterParamsError;
+
 #[derive(Error, Debug, Clone, Copy)]
 #[error("Type of pixels of the source image is not equal to pixel type of the destination image.")]
 pub struct DifferentTypesOfPixelsError;
+
+#[derive(Error, Debug, Clone, Copy)]
+pub enum StreamingResizeError {
+    #[error("Type of pixels of the source rows is not equal to pixel type of the resizer")]
+    DifferentTypesOfPixels,
+    #[error("Width of the source rows is not equal to width of the source image")]
+    InvalidRowsWidth,
+    #[error("Count of pushed rows is greater than height of the source image")]
+    TooManyRows,
+}
+
+#[derive(Error, Debug, Clone, Copy)]
+pub enum MipmapsError {
+    #[error(
+        "Type of pixels of the source image is not equal to pixel type of the destination image."
+    )]
+    DifferentTypesOfPixels,
+    #[error("Count of destination images is not equal to count of levels of mipmaps")]
+    InvalidCountOfLevels,
+    #[error("Size of destination image is not equal to size of level of mipmaps")]
+    InvalidLevelSize,
+}
@@ -1,6 +1,7 @@
+use std::mem::size_of_val;
 use std::num::NonZeroU32;
 
-use crate::image_view::{ImageRows, ImageRowsMut, TypedImageView, TypedImageViewMut};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
 use crate::pixels::Pixel;
 use crate::{ImageBufferError, ImageView, ImageViewMut, InvalidBufferSizeError, PixelType};
 
@@ -9,6 +10,7 @@ enum PixelsContainer<'a> {
     MutU32(&'a mut [u32]),
     MutU8(&'a mut [u8]),
     VecU32(Vec<u32>),
+    VecU16(Vec<u16>),
     VecU8(Vec<u8>),
 }
 
@@ -19,22 +21,29 @@ pub struct Image<'a> {
     height: NonZeroU32,
     pixels: PixelsContainer<'a>,
     pixel_type: PixelType,
+    /// Distance in bytes between beginnings of adjacent rows.
+    stride: usize,
 }
 
 impl<'a> Image<'a> {
     /// Create empty image with given dimensions and pixel type.
     pub fn new(width: NonZeroU32, height: NonZeroU32, pixel_type: PixelType) -> Self {
         let size = (width.get() * height.get()) as usize;
-        let pixels = if let PixelType::U8 = pixel_type {
-            PixelsContainer::VecU8(vec![0; size])
-        } else {
-            PixelsContainer::VecU32(vec![0; size])
+        let pixels = match pixel_type {
+            PixelType::U8 | PixelType::U8x2 | PixelType::U8x3 => {
+                PixelsContainer::VecU8(vec![0; size * pixel_type.size()])
+            }
+            PixelType::U16 | PixelType::U16x3 | PixelType::U16x4 => {
+                PixelsContainer::VecU16(vec![0; size * pixel_type.size() / 2])
+            }
+            _ => PixelsContainer::VecU32(vec![0; size * pixel_type.size() / 4]),
         };
         Self {
             width,
             height,
             pixels,
             pixel_type,
+            stride: width.get() as usize * pixel_type.size(),
         }
     }
 
@@ -44,8 +53,8 @@ impl<'a> Image<'a> {
         buffer: Vec<u32>,
         pixel_type: PixelType,
     ) -> Result<Self, InvalidBufferSizeError> {
-        let size = (width.get() * height.get()) as usize;
-        if buffer.len() != size {
+        let size = (width.get() * height.get()) as usize * pixel_type.size();
+        if size_of_val(buffer.as_slice()) != size {
             return Err(InvalidBufferSizeError);
         }
         Ok(Self {
@@ -53,6 +62,7 @@ impl<'a> Image<'a> {
             height,
             pixels: PixelsContainer::VecU32(buffer),
             pixel_type,
+            stride: width.get() as usize * pixel_type.size(),
         })
     }
 
@@ -74,6 +84,7 @@ impl<'a> Image<'a> {
             height,
             pixels: PixelsContainer::VecU8(buffer),
             pixel_type,
+            stride: width.get() as usize * pixel_type.size(),
         })
     }
 
@@ -83,8 +94,8 @@ impl<'a> Image<'a> {
         buffer: &'a mut [u32],
         pixel_type: PixelType,
     ) -> Result<Self, InvalidBufferSizeError> {
-        let size = (width.get() * height.get()) as usize;
-        if buffer.len() != size {
+        let size = (width.get() * height.get()) as usize * pixel_type.size();
+        if size_of_val(buffer) != size {
             return Err(InvalidBufferSizeError);
         }
         Ok(Self {
@@ -92,6 +103,7 @@ impl<'a> Image<'a> {
             height,
             pixels: PixelsContainer::MutU32(buffer),
             pixel_type,
+            stride: width.get() as usize * pixel_type.size(),
         })
     }
 
@@ -113,6 +125,29 @@ impl<'a> Image<'a> {
             height,
             pixels: PixelsContainer::MutU8(buffer),
             pixel_type,
+            stride: width.get() as usize * pixel_type.size(),
+        })
+    }
+
+    /// Creates image from buffer with padded rows (e.g. frame of video decoder).
+    ///
+    /// `stride` is a distance in bytes between beginnings of
+    /// adjacent rows. Last row of image may have no padding.
+    pub fn from_slice_u8_with_stride(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        buffer: &'a mut [u8],
+        pixel_type: PixelType,
+        stride: usize,
+    ) -> Result<Self, ImageBufferError> {
+        // Checks size and alignment of the buffer and its rows.
+        ImageView::from_buffer_with_stride(width, height, buffer, pixel_type, stride)?;
+        Ok(Self {
+            width,
+            height,
+            pixels: PixelsContainer::MutU8(buffer),
+            pixel_type,
+            stride,
         })
     }
 
@@ -131,13 +166,20 @@ impl<'a> Image<'a> {
         self.height
     }
 
-    /// Buffer with image pixels.
+    /// Distance in bytes between beginnings of adjacent rows.
+    #[inline(always)]
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Buffer with image pixels (including padding of rows).
     #[inline(always)]
     pub fn buffer(&self) -> &[u8] {
         match &self.pixels {
             PixelsContainer::MutU32(p) => unsafe { p.align_to::<u8>().1 },
-            PixelsContainer::MutU8(p) => *p,
+            PixelsContainer::MutU8(p) => p,
             PixelsContainer::VecU32(v) => unsafe { v.align_to::<u8>().1 },
+            PixelsContainer::VecU16(v) => unsafe { v.align_to::<u8>().1 },
             PixelsContainer::VecU8(v) => v,
         }
     }
@@ -148,6 +190,7 @@ impl<'a> Image<'a> {
             PixelsContainer::MutU32(p) => unsafe { p.align_to::<u8>().1.to_vec() },
             PixelsContainer::MutU8(p) => p.to_vec(),
             PixelsContainer::VecU32(v) => unsafe { v.align_to::<u8>().1.to_vec() },
+            PixelsContainer::VecU16(v) => unsafe { v.align_to::<u8>().1.to_vec() },
             PixelsContainer::VecU8(v) => v,
         }
     }
@@ -158,53 +201,32 @@ impl<'a> Image<'a> {
             PixelsContainer::MutU32(p) => unsafe { p.align_to_mut::<u8>().1 },
             PixelsContainer::MutU8(p) => p,
             PixelsContainer::VecU32(ref mut v) => unsafe { v.align_to_mut::<u8>().1 },
+            PixelsContainer::VecU16(ref mut v) => unsafe { v.align_to_mut::<u8>().1 },
             PixelsContainer::VecU8(ref mut v) => v.as_mut_slice(),
         }
     }
 
     #[inline(always)]
-    pub fn view(&self) -> ImageView {
+    pub fn view(&self) -> ImageView<'_> {
         let buffer = self.buffer();
-        let rows = match self.pixel_type {
-            PixelType::U8x4 => {
-                let pixels = unsafe { buffer.align_to::<u32>().1 };
-                ImageRows::U8x4(pixels.chunks(self.width.get() as usize).collect())
-            }
-            PixelType::I32 => {
-                let pixels = unsafe { buffer.align_to::<i32>().1 };
-                ImageRows::I32(pixels.chunks(self.width.get() as usize).collect())
-            }
-            PixelType::F32 => {
-                let pixels = unsafe { buffer.align_to::<f32>().1 };
-                ImageRows::F32(pixels.chunks(self.width.get() as usize).collect())
-            }
-            PixelType::U8 => ImageRows::U8(buffer.chunks(self.width.get() as usize).collect()),
-        };
-        ImageView::new(self.width, self.height, rows).unwrap()
+        ImageView::from_buffer_with_stride(
+            self.width,
+            self.height,
+            buffer,
+            self.pixel_type,
+            self.stride,
+        )
+        .unwrap()
     }
 
     #[inline(always)]
-    pub fn view_mut(&mut self) -> ImageViewMut {
+    pub fn view_mut(&mut self) -> ImageViewMut<'_> {
         let pixel_type = self.pixel_type;
         let width = self.width;
         let height = self.height;
+        let stride = self.stride;
         let buffer = self.buffer_mut();
-        let rows = match pixel_type {
-            PixelType::U8x4 => {
-                let pixels = unsafe { buffer.align_to_mut::<u32>().1 };
-                ImageRowsMut::U8x4(pixels.chunks_mut(width.get() as usize).collect())
-            }
-            PixelType::I32 => {
-                let pixels = unsafe { buffer.align_to_mut::<i32>().1 };
-                ImageRowsMut::I32(pixels.chunks_mut(width.get() as usize).collect())
-            }
-            PixelType::F32 => {
-                let pixels = unsafe { buffer.align_to_mut::<f32>().1 };
-                ImageRowsMut::F32(pixels.chunks_mut(width.get() as usize).collect())
-            }
-            PixelType::U8 => ImageRowsMut::U8(buffer.chunks_mut(width.get() as usize).collect()),
-        };
-        ImageViewMut::new(width, height, rows).unwrap()
+        ImageViewMut::from_buffer_with_stride(width, height, buffer, pixel_type, stride).unwrap()
     }
 }
 
@@ -242,4 +264,29 @@ where
     pub fn dst_view<'s>(&'s mut self) -> TypedImageViewMut<'s, 'a, P> {
         TypedImageViewMut::new(self.width, self.height, self.rows.as_mut_slice())
     }
+
+    /// Returns view of the first `height` rows of the image.
+    #[inline(always)]
+    pub fn top_src_view<'s>(&'s self, height: NonZeroU32) -> TypedImageView<'s, 'a, P> {
+        self.band_src_view(0, height)
+    }
+
+    /// Returns view of `height` rows of the image starting with `first_row`.
+    #[inline(always)]
+    pub fn band_src_view<'s>(
+        &'s self,
+        first_row: usize,
+        height: NonZeroU32,
+    ) -> TypedImageView<'s, 'a, P> {
+        let rows = &self.rows[first_row..first_row + height.get() as usize];
+        let rows: &[&[P::Type]] = unsafe { std::mem::transmute(rows) };
+        TypedImageView::new(self.width, height, rows)
+    }
+
+    /// Returns mutable view of the first `height` rows of the image.
+    #[inline(always)]
+    pub fn top_dst_view<'s>(&'s mut self, height: NonZeroU32) -> TypedImageViewMut<'s, 'a, P> {
+        let rows = &mut self.rows[..height.get() as usize];
+        TypedImageViewMut::new(self.width, height, rows)
+    }
 }
@@ -2,11 +2,16 @@ use std::num::NonZeroU32;
 use std::slice;
 
 use crate::errors::{CropBoxError, ImageBufferError, ImageRowsError};
-use crate::pixels::{Pixel, PixelType, U8x4, F32, I32, U8};
+use crate::pixels::{
+    F32x3, F32x4, Pixel, PixelType, U16x3, U16x4, U8x2, U8x3, U8x4, F32, I32, U16, U8,
+};
 
+#[cfg(target_arch = "x86_64")]
 pub(crate) type RowMut<'a, 'b, T> = &'a mut &'b mut [T];
 pub(crate) type TwoRows<'a, T> = (&'a [T], &'a [T]);
+#[cfg(target_arch = "x86_64")]
 pub(crate) type FourRows<'a, T> = (&'a [T], &'a [T], &'a [T], &'a [T]);
+#[cfg(target_arch = "x86_64")]
 pub(crate) type FourRowsMut<'a, 'b, T> = (
     &'a mut &'b mut [T],
     &'a mut &'b mut [T],
@@ -14,22 +19,44 @@ pub(crate) type FourRowsMut<'a, 'b, T> = (
     &'a mut &'b mut [T],
 );
 
-/// Parameters of crop box that may be used with [`ImageView`]
-#[derive(Debug, Clone, Copy)]
+/// Parameters of crop box that may be used with [`ImageView`].
+///
+/// Values are given in pixels of source image and may be fractional,
+/// so the crop box may be positioned with sub-pixel accuracy.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct CropBox {
-    pub left: u32,
-    pub top: u32,
-    pub width: NonZeroU32,
-    pub height: NonZeroU32,
+    pub left: f64,
+    pub top: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl CropBox {
+    /// Crop box that covers the whole image.
+    pub(crate) fn from_size(width: NonZeroU32, height: NonZeroU32) -> Self {
+        Self {
+            left: 0.,
+            top: 0.,
+            width: width.get() as f64,
+            height: height.get() as f64,
+        }
+    }
 }
 
 /// An immutable rows of image.
 #[derive(Debug, Clone)]
 pub enum ImageRows<'a> {
+    U8x2(Vec<&'a [[u8; 2]]>),
+    U8x3(Vec<&'a [[u8; 3]]>),
     U8x4(Vec<&'a [u32]>),
     I32(Vec<&'a [i32]>),
     F32(Vec<&'a [f32]>),
     U8(Vec<&'a [u8]>),
+    U16(Vec<&'a [u16]>),
+    U16x3(Vec<&'a [[u16; 3]]>),
+    U16x4(Vec<&'a [[u16; 4]]>),
+    F32x3(Vec<&'a [[f32; 3]]>),
+    F32x4(Vec<&'a [[f32; 4]]>),
 }
 
 impl<'a> ImageRows<'a> {
@@ -39,19 +66,33 @@ impl<'a> ImageRows<'a> {
         height: NonZeroU32,
     ) -> Result<(), ImageRowsError> {
         match self {
+            ImageRows::U8x2(rows) => check_rows_count_and_size(width, height, rows),
+            ImageRows::U8x3(rows) => check_rows_count_and_size(width, height, rows),
             ImageRows::U8x4(rows) => check_rows_count_and_size(width, height, rows),
             ImageRows::I32(rows) => check_rows_count_and_size(width, height, rows),
             ImageRows::F32(rows) => check_rows_count_and_size(width, height, rows),
             ImageRows::U8(rows) => check_rows_count_and_size(width, height, rows),
+            ImageRows::U16(rows) => check_rows_count_and_size(width, height, rows),
+            ImageRows::U16x3(rows) => check_rows_count_and_size(width, height, rows),
+            ImageRows::U16x4(rows) => check_rows_count_and_size(width, height, rows),
+            ImageRows::F32x3(rows) => check_rows_count_and_size(width, height, rows),
+            ImageRows::F32x4(rows) => check_rows_count_and_size(width, height, rows),
         }
     }
 
     pub fn pixel_type(&self) -> PixelType {
         match self {
+            Self::U8x2(_) => PixelType::U8x2,
+            Self::U8x3(_) => PixelType::U8x3,
             Self::U8x4(_) => PixelType::U8x4,
             Self::I32(_) => PixelType::I32,
             Self::F32(_) => PixelType::F32,
             Self::U8(_) => PixelType::U8,
+            Self::U16(_) => PixelType::U16,
+            Self::U16x3(_) => PixelType::U16x3,
+            Self::U16x4(_) => PixelType::U16x4,
+            Self::F32x3(_) => PixelType::F32x3,
+            Self::F32x4(_) => PixelType::F32x4,
         }
     }
 }
@@ -59,10 +100,17 @@ impl<'a> ImageRows<'a> {
 /// A mutable rows of image.
 #[derive(Debug)]
 pub enum ImageRowsMut<'a> {
+    U8x2(Vec<&'a mut [[u8; 2]]>),
+    U8x3(Vec<&'a mut [[u8; 3]]>),
     U8x4(Vec<&'a mut [u32]>),
     I32(Vec<&'a mut [i32]>),
     F32(Vec<&'a mut [f32]>),
     U8(Vec<&'a mut [u8]>),
+    U16(Vec<&'a mut [u16]>),
+    U16x3(Vec<&'a mut [[u16; 3]]>),
+    U16x4(Vec<&'a mut [[u16; 4]]>),
+    F32x3(Vec<&'a mut [[f32; 3]]>),
+    F32x4(Vec<&'a mut [[f32; 4]]>),
 }
 
 impl<'a> ImageRowsMut<'a> {
@@ -72,19 +120,33 @@ impl<'a> ImageRowsMut<'a> {
         height: NonZeroU32,
     ) -> Result<(), ImageRowsError> {
         match self {
+            Self::U8x2(rows) => check_rows_count_and_size(width, height, rows),
+            Self::U8x3(rows) => check_rows_count_and_size(width, height, rows),
             Self::U8x4(rows) => check_rows_count_and_size(width, height, rows),
             Self::I32(rows) => check_rows_count_and_size(width, height, rows),
             Self::F32(rows) => check_rows_count_and_size(width, height, rows),
             Self::U8(rows) => check_rows_count_and_size(width, height, rows),
+            Self::U16(rows) => check_rows_count_and_size(width, height, rows),
+            Self::U16x3(rows) => check_rows_count_and_size(width, height, rows),
+            Self::U16x4(rows) => check_rows_count_and_size(width, height, rows),
+            Self::F32x3(rows) => check_rows_count_and_size(width, height, rows),
+            Self::F32x4(rows) => check_rows_count_and_size(width, height, rows),
         }
     }
 
     pub fn pixel_type(&self) -> PixelType {
         match self {
+            Self::U8x2(_) => PixelType::U8x2,
+            Self::U8x3(_) => PixelType::U8x3,
             Self::U8x4(_) => PixelType::U8x4,
             Self::I32(_) => PixelType::I32,
             Self::F32(_) => PixelType::F32,
             Self::U8(_) => PixelType::U8,
+            Self::U16(_) => PixelType::U16,
+            Self::U16x3(_) => PixelType::U16x3,
+            Self::U16x4(_) => PixelType::U16x4,
+            Self::F32x3(_) => PixelType::F32x3,
+            Self::F32x4(_) => PixelType::F32x4,
         }
     }
 }
@@ -108,12 +170,7 @@ impl<'a> ImageView<'a> {
         Ok(Self {
             width,
             height,
-            crop_box: CropBox {
-                left: 0,
-                top: 0,
-                width,
-                height,
-            },
+            crop_box: CropBox::from_size(width, height),
             rows,
         })
     }
@@ -124,34 +181,40 @@ impl<'a> ImageView<'a> {
         buffer: &'a [u8],
         pixel_type: PixelType,
     ) -> Result<Self, ImageBufferError> {
-        let size = (width.get() * height.get()) as usize * pixel_type.size();
-        if buffer.len() != size {
-            return Err(ImageBufferError::InvalidBufferSize);
-        }
+        let row_size = width.get() as usize * pixel_type.size();
+        Self::from_buffer_with_stride(width, height, buffer, pixel_type, row_size)
+    }
+
+    /// Creates view of image from buffer with padded rows.
+    ///
+    /// `stride` is a distance in bytes between beginnings of
+    /// adjacent rows. Last row of image may have no padding.
+    pub fn from_buffer_with_stride(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        buffer: &'a [u8],
+        pixel_type: PixelType,
+        stride: usize,
+    ) -> Result<Self, ImageBufferError> {
+        let row_size = width.get() as usize * pixel_type.size();
+        check_buffer_size(buffer.len(), height, row_size, stride)?;
         let rows = match pixel_type {
-            PixelType::U8x4 => {
-                let pixels = align_buffer_to(buffer)?;
-                ImageRows::U8x4(pixels.chunks(width.get() as usize).collect())
-            }
-            PixelType::I32 => {
-                let pixels = align_buffer_to(buffer)?;
-                ImageRows::I32(pixels.chunks(width.get() as usize).collect())
-            }
-            PixelType::F32 => {
-                let pixels = align_buffer_to(buffer)?;
-                ImageRows::F32(pixels.chunks(width.get() as usize).collect())
-            }
-            PixelType::U8 => ImageRows::U8(buffer.chunks(width.get() as usize).collect()),
+            PixelType::U8x2 => ImageRows::U8x2(buffer_rows(buffer, stride, row_size)?),
+            PixelType::U8x3 => ImageRows::U8x3(buffer_rows(buffer, stride, row_size)?),
+            PixelType::U8x4 => ImageRows::U8x4(buffer_rows(buffer, stride, row_size)?),
+            PixelType::I32 => ImageRows::I32(buffer_rows(buffer, stride, row_size)?),
+            PixelType::F32 => ImageRows::F32(buffer_rows(buffer, stride, row_size)?),
+            PixelType::U8 => ImageRows::U8(buffer_rows(buffer, stride, row_size)?),
+            PixelType::U16 => ImageRows::U16(buffer_rows(buffer, stride, row_size)?),
+            PixelType::U16x3 => ImageRows::U16x3(buffer_rows(buffer, stride, row_size)?),
+            PixelType::U16x4 => ImageRows::U16x4(buffer_rows(buffer, stride, row_size)?),
+            PixelType::F32x3 => ImageRows::F32x3(buffer_rows(buffer, stride, row_size)?),
+            PixelType::F32x4 => ImageRows::F32x4(buffer_rows(buffer, stride, row_size)?),
         };
         Ok(Self {
             width,
             height,
-            crop_box: CropBox {
-                left: 0,
-                top: 0,
-                width,
-                height,
-            },
+            crop_box: CropBox::from_size(width, height),
             rows,
         })
     }
@@ -177,14 +240,7 @@ impl<'a> ImageView<'a> {
     }
 
     pub fn set_crop_box(&mut self, crop_box: CropBox) -> Result<(), CropBoxError> {
-        if crop_box.left >= self.width.get() || crop_box.top >= self.height.get() {
-            return Err(CropBoxError::PositionIsOutOfImageBoundaries);
-        }
-        let right = crop_box.left + crop_box.width.get();
-        let bottom = crop_box.top + crop_box.height.get();
-        if right > self.width.get() || bottom > self.height.get() {
-            return Err(CropBoxError::SizeIsOutOfImageBoundaries);
-        }
+        check_crop_box(self.width, self.height, crop_box)?;
         self.crop_box = crop_box;
         Ok(())
     }
@@ -216,16 +272,18 @@ impl<'a> ImageView<'a> {
             (0.5, 0.5)
         };
 
+        let centering = (centering.0 as f64, centering.1 as f64);
+
         // calculate aspect ratios
-        let width = self.width.get() as f32;
-        let height = self.height.get() as f32;
+        let width = self.width.get() as f64;
+        let height = self.height.get() as f64;
         let image_ratio = width / height;
-        let required_ration = dst_width.get() as f32 / dst_height.get() as f32;
+        let required_ration = dst_width.get() as f64 / dst_height.get() as f64;
 
         let crop_width;
         let crop_height;
         // figure out if the sides or top/bottom will be cropped off
-        if (image_ratio - required_ration).abs() < f32::EPSILON {
+        if (image_ratio - required_ration).abs() < f64::EPSILON {
             // The image is already the needed ratio
             crop_width = width;
             crop_height = height;
@@ -242,16 +300,45 @@ impl<'a> ImageView<'a> {
         let crop_left = (width - crop_width) * centering.0;
         let crop_top = (height - crop_height) * centering.1;
 
+        // Crop box is not rounded to whole pixels, so the image is not
+        // shifted. Size of the crop box is limited to compensate
+        // errors of floating point calculations.
         self.set_crop_box(CropBox {
-            left: crop_left.round() as u32,
-            top: crop_top.round() as u32,
-            width: NonZeroU32::new(crop_width.round() as u32).unwrap(),
-            height: NonZeroU32::new(crop_height.round() as u32).unwrap(),
+            left: crop_left,
+            top: crop_top,
+            width: crop_width.min(width - crop_left),
+            height: crop_height.min(height - crop_top),
         })
         .unwrap();
     }
 
-    pub(crate) fn u32_image(&self) -> Option<TypedImageView<U8x4>> {
+    pub(crate) fn u8x2_image(&self) -> Option<TypedImageView<'_, '_, U8x2>> {
+        if let ImageRows::U8x2(ref rows) = self.rows {
+            Some(TypedImageView {
+                width: self.width,
+                height: self.height,
+                crop_box: self.crop_box,
+                rows,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn u8x3_image(&self) -> Option<TypedImageView<'_, '_, U8x3>> {
+        if let ImageRows::U8x3(ref rows) = self.rows {
+            Some(TypedImageView {
+                width: self.width,
+                height: self.height,
+                crop_box: self.crop_box,
+                rows,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn u32_image(&self) -> Option<TypedImageView<'_, '_, U8x4>> {
         if let ImageRows::U8x4(ref rows) = self.rows {
             Some(TypedImageView {
                 width: self.width,
@@ -264,7 +351,7 @@ impl<'a> ImageView<'a> {
         }
     }
 
-    pub(crate) fn i32_image(&self) -> Option<TypedImageView<I32>> {
+    pub(crate) fn i32_image(&self) -> Option<TypedImageView<'_, '_, I32>> {
         if let ImageRows::I32(ref rows) = self.rows {
             Some(TypedImageView {
                 width: self.width,
@@ -277,7 +364,7 @@ impl<'a> ImageView<'a> {
         }
     }
 
-    pub(crate) fn f32_image(&self) -> Option<TypedImageView<F32>> {
+    pub(crate) fn f32_image(&self) -> Option<TypedImageView<'_, '_, F32>> {
         if let ImageRows::F32(ref rows) = self.rows {
             Some(TypedImageView {
                 width: self.width,
@@ -290,7 +377,7 @@ impl<'a> ImageView<'a> {
         }
     }
 
-    pub(crate) fn u8_image(&self) -> Option<TypedImageView<U8>> {
+    pub(crate) fn u8_image(&self) -> Option<TypedImageView<'_, '_, U8>> {
         if let ImageRows::U8(ref rows) = self.rows {
             Some(TypedImageView {
                 width: self.width,
@@ -302,6 +389,71 @@ impl<'a> ImageView<'a> {
             None
         }
     }
+
+    pub(crate) fn u16_image(&self) -> Option<TypedImageView<'_, '_, U16>> {
+        if let ImageRows::U16(ref rows) = self.rows {
+            Some(TypedImageView {
+                width: self.width,
+                height: self.height,
+                crop_box: self.crop_box,
+                rows,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn u16x3_image(&self) -> Option<TypedImageView<'_, '_, U16x3>> {
+        if let ImageRows::U16x3(ref rows) = self.rows {
+            Some(TypedImageView {
+                width: self.width,
+                height: self.height,
+                crop_box: self.crop_box,
+                rows,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn u16x4_image(&self) -> Option<TypedImageView<'_, '_, U16x4>> {
+        if let ImageRows::U16x4(ref rows) = self.rows {
+            Some(TypedImageView {
+                width: self.width,
+                height: self.height,
+                crop_box: self.crop_box,
+                rows,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn f32x3_image(&self) -> Option<TypedImageView<'_, '_, F32x3>> {
+        if let ImageRows::F32x3(ref rows) = self.rows {
+            Some(TypedImageView {
+                width: self.width,
+                height: self.height,
+                crop_box: self.crop_box,
+                rows,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn f32x4_image(&self) -> Option<TypedImageView<'_, '_, F32x4>> {
+        if let ImageRows::F32x4(ref rows) = self.rows {
+            Some(TypedImageView {
+                width: self.width,
+                height: self.height,
+                crop_box: self.crop_box,
+                rows,
+            })
+        } else {
+            None
+        }
+    }
 }
 
 /// Generic immutable image view.
@@ -315,6 +467,21 @@ where
     rows: &'a [&'b [P::Type]],
 }
 
+// Implemented manually because derive requires `P: Clone`.
+impl<'a, 'b, P> Clone for TypedImageView<'a, 'b, P>
+where
+    P: Pixel,
+{
+    fn clone(&self) -> Self {
+        Self {
+            width: self.width,
+            height: self.height,
+            crop_box: self.crop_box,
+            rows: self.rows,
+        }
+    }
+}
+
 impl<'a, 'b, P> TypedImageView<'a, 'b, P>
 where
     P: Pixel,
@@ -323,12 +490,7 @@ where
         Self {
             width,
             height,
-            crop_box: CropBox {
-                left: 0,
-                top: 0,
-                width,
-                height,
-            },
+            crop_box: CropBox::from_size(width, height),
             rows,
         }
     }
@@ -348,11 +510,19 @@ where
         self.crop_box
     }
 
+    /// Crop box must be already checked for the size of image.
+    #[inline(always)]
+    pub(crate) fn with_crop_box(mut self, crop_box: CropBox) -> Self {
+        self.crop_box = crop_box;
+        self
+    }
+
     #[inline]
     pub(crate) fn get_pixel(&self, x: u32, y: u32) -> P::Type {
         self.rows[y as usize][x as usize]
     }
 
+    #[cfg(target_arch = "x86_64")]
     #[inline(always)]
     pub(crate) fn iter_4_rows<'s>(
         &'s self,
@@ -361,7 +531,7 @@ where
     ) -> impl Iterator<Item = FourRows<'b, P::Type>> + 's {
         let start_y = start_y as usize;
         let max_y = max_y.min(self.height.get()) as usize;
-        let rows = self.rows.get(start_y..max_y).unwrap_or_else(|| &[]);
+        let rows = self.rows.get(start_y..max_y).unwrap_or(&[]);
         rows.chunks_exact(4).map(|rows| match *rows {
             [r0, r1, r2, r3] => (r0, r1, r2, r3),
             _ => unreachable!(),
@@ -376,7 +546,7 @@ where
     ) -> impl Iterator<Item = TwoRows<'b, P::Type>> + 's {
         let start_y = start_y as usize;
         let max_y = max_y.min(self.height.get()) as usize;
-        let rows = self.rows.get(start_y..max_y).unwrap_or_else(|| &[]);
+        let rows = self.rows.get(start_y..max_y).unwrap_or(&[]);
         rows.chunks_exact(2).map(|rows| match *rows {
             [r0, r1] => (r0, r1),
             _ => unreachable!(),
@@ -391,10 +561,24 @@ where
     ) -> impl Iterator<Item = &'b [P::Type]> + 's {
         let start_y = start_y as usize;
         let max_y = max_y.min(self.height.get()) as usize;
-        let rows = self.rows.get(start_y..max_y).unwrap_or_else(|| &[]);
+        let rows = self.rows.get(start_y..max_y).unwrap_or(&[]);
         rows.iter().copied()
     }
 
+    /// Returns iterator over parts of rows with pixels which are covered
+    /// (even partially) by the crop box.
+    pub(crate) fn iter_crop_box_rows<'s>(&'s self) -> impl Iterator<Item = &'b [P::Type]> + 's {
+        let crop_box = self.crop_box;
+        let width = self.width.get() as f64;
+        let height = self.height.get() as f64;
+        let left = crop_box.left.floor() as usize;
+        let right = (crop_box.left + crop_box.width).ceil().min(width) as usize;
+        let top = crop_box.top.floor() as u32;
+        let bottom = (crop_box.top + crop_box.height).ceil().min(height) as u32;
+        self.iter_rows(top, bottom)
+            .map(move |row| row.get(left..right).unwrap_or(&[]))
+    }
+
     #[inline(always)]
     pub(crate) fn iter_horiz(&self, x: u32, y: u32) -> &'b [P::Type] {
         if let Some(&row) = self.rows.get(y as usize) {
@@ -411,6 +595,11 @@ where
         self.rows.get(y as usize).copied()
     }
 
+    #[inline(always)]
+    pub(crate) fn rows(&self) -> &'a [&'b [P::Type]] {
+        self.rows
+    }
+
     #[inline(always)]
     pub(crate) fn iter_rows_with_step<'s>(
         &'s self,
@@ -457,24 +646,36 @@ impl<'a> ImageViewMut<'a> {
         buffer: &'a mut [u8],
         pixel_type: PixelType,
     ) -> Result<Self, ImageBufferError> {
-        let size = (width.get() * height.get()) as usize * pixel_type.size();
-        if buffer.len() != size {
-            return Err(ImageBufferError::InvalidBufferSize);
-        }
+        let row_size = width.get() as usize * pixel_type.size();
+        Self::from_buffer_with_stride(width, height, buffer, pixel_type, row_size)
+    }
+
+    /// Creates mutable view of image from buffer with padded rows.
+    ///
+    /// `stride` is a distance in bytes between beginnings of
+    /// adjacent rows. Last row of image may have no padding.
+    /// Padding bytes are never changed by resizer.
+    pub fn from_buffer_with_stride(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        buffer: &'a mut [u8],
+        pixel_type: PixelType,
+        stride: usize,
+    ) -> Result<Self, ImageBufferError> {
+        let row_size = width.get() as usize * pixel_type.size();
+        check_buffer_size(buffer.len(), height, row_size, stride)?;
         let rows = match pixel_type {
-            PixelType::U8x4 => {
-                let pixels = align_buffer_to_mut(buffer)?;
-                ImageRowsMut::U8x4(pixels.chunks_mut(width.get() as usize).collect())
-            }
-            PixelType::I32 => {
-                let pixels = align_buffer_to_mut(buffer)?;
-                ImageRowsMut::I32(pixels.chunks_mut(width.get() as usize).collect())
-            }
-            PixelType::F32 => {
-                let pixels = align_buffer_to_mut(buffer)?;
-                ImageRowsMut::F32(pixels.chunks_mut(width.get() as usize).collect())
-            }
-            PixelType::U8 => ImageRowsMut::U8(buffer.chunks_mut(width.get() as usize).collect()),
+            PixelType::U8x2 => ImageRowsMut::U8x2(buffer_rows_mut(buffer, stride, row_size)?),
+            PixelType::U8x3 => ImageRowsMut::U8x3(buffer_rows_mut(buffer, stride, row_size)?),
+            PixelType::U8x4 => ImageRowsMut::U8x4(buffer_rows_mut(buffer, stride, row_size)?),
+            PixelType::I32 => ImageRowsMut::I32(buffer_rows_mut(buffer, stride, row_size)?),
+            PixelType::F32 => ImageRowsMut::F32(buffer_rows_mut(buffer, stride, row_size)?),
+            PixelType::U8 => ImageRowsMut::U8(buffer_rows_mut(buffer, stride, row_size)?),
+            PixelType::U16 => ImageRowsMut::U16(buffer_rows_mut(buffer, stride, row_size)?),
+            PixelType::U16x3 => ImageRowsMut::U16x3(buffer_rows_mut(buffer, stride, row_size)?),
+            PixelType::U16x4 => ImageRowsMut::U16x4(buffer_rows_mut(buffer, stride, row_size)?),
+            PixelType::F32x3 => ImageRowsMut::F32x3(buffer_rows_mut(buffer, stride, row_size)?),
+            PixelType::F32x4 => ImageRowsMut::F32x4(buffer_rows_mut(buffer, stride, row_size)?),
         };
         Ok(Self {
             width,
@@ -498,6 +699,98 @@ impl<'a> ImageViewMut<'a> {
         self.height
     }
 
+    /// Returns mutable view of the given region of the image.
+    ///
+    /// It may be used to resize an image directly into a part
+    /// of bigger image (e.g. contact sheet or sprite atlas).
+    /// Pixels outside the region are never changed by
+    /// [Resizer](crate::Resizer) and [MulDiv](crate::MulDiv).
+    pub fn sub_view_mut(
+        &mut self,
+        left: u32,
+        top: u32,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<ImageViewMut<'_>, CropBoxError> {
+        if left >= self.width.get() || top >= self.height.get() {
+            return Err(CropBoxError::PositionIsOutOfImageBoundaries);
+        }
+        if width.get() > self.width.get() - left || height.get() > self.height.get() - top {
+            return Err(CropBoxError::SizeIsOutOfImageBoundaries);
+        }
+        let region = Region {
+            left: left as usize,
+            top: top as usize,
+            width: width.get() as usize,
+            height: height.get() as usize,
+        };
+        let rows = match &mut self.rows {
+            ImageRowsMut::U8x2(rows) => ImageRowsMut::U8x2(sub_rows_mut(rows, region)),
+            ImageRowsMut::U8x3(rows) => ImageRowsMut::U8x3(sub_rows_mut(rows, region)),
+            ImageRowsMut::U8x4(rows) => ImageRowsMut::U8x4(sub_rows_mut(rows, region)),
+            ImageRowsMut::I32(rows) => ImageRowsMut::I32(sub_rows_mut(rows, region)),
+            ImageRowsMut::F32(rows) => ImageRowsMut::F32(sub_rows_mut(rows, region)),
+            ImageRowsMut::U8(rows) => ImageRowsMut::U8(sub_rows_mut(rows, region)),
+            ImageRowsMut::U16(rows) => ImageRowsMut::U16(sub_rows_mut(rows, region)),
+            ImageRowsMut::U16x3(rows) => ImageRowsMut::U16x3(sub_rows_mut(rows, region)),
+            ImageRowsMut::U16x4(rows) => ImageRowsMut::U16x4(sub_rows_mut(rows, region)),
+            ImageRowsMut::F32x3(rows) => ImageRowsMut::F32x3(sub_rows_mut(rows, region)),
+            ImageRowsMut::F32x4(rows) => ImageRowsMut::F32x4(sub_rows_mut(rows, region)),
+        };
+        Ok(ImageViewMut {
+            width,
+            height,
+            rows,
+        })
+    }
+
+    /// Returns immutable view of the image.
+    pub(crate) fn view(&self) -> ImageView<'_> {
+        let rows = match &self.rows {
+            ImageRowsMut::U8x2(rows) => ImageRows::U8x2(immutable_rows(rows)),
+            ImageRowsMut::U8x3(rows) => ImageRows::U8x3(immutable_rows(rows)),
+            ImageRowsMut::U8x4(rows) => ImageRows::U8x4(immutable_rows(rows)),
+            ImageRowsMut::I32(rows) => ImageRows::I32(immutable_rows(rows)),
+            ImageRowsMut::F32(rows) => ImageRows::F32(immutable_rows(rows)),
+            ImageRowsMut::U8(rows) => ImageRows::U8(immutable_rows(rows)),
+            ImageRowsMut::U16(rows) => ImageRows::U16(immutable_rows(rows)),
+            ImageRowsMut::U16x3(rows) => ImageRows::U16x3(immutable_rows(rows)),
+            ImageRowsMut::U16x4(rows) => ImageRows::U16x4(immutable_rows(rows)),
+            ImageRowsMut::F32x3(rows) => ImageRows::F32x3(immutable_rows(rows)),
+            ImageRowsMut::F32x4(rows) => ImageRows::F32x4(immutable_rows(rows)),
+        };
+        ImageView {
+            width: self.width,
+            height: self.height,
+            crop_box: CropBox::from_size(self.width, self.height),
+            rows,
+        }
+    }
+
+    pub(crate) fn u8x2_image<'s>(&'s mut self) -> Option<TypedImageViewMut<'s, 'a, U8x2>> {
+        if let ImageRowsMut::U8x2(rows) = &mut self.rows {
+            Some(TypedImageViewMut {
+                width: self.width,
+                height: self.height,
+                rows,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn u8x3_image<'s>(&'s mut self) -> Option<TypedImageViewMut<'s, 'a, U8x3>> {
+        if let ImageRowsMut::U8x3(rows) = &mut self.rows {
+            Some(TypedImageViewMut {
+                width: self.width,
+                height: self.height,
+                rows,
+            })
+        } else {
+            None
+        }
+    }
+
     pub(crate) fn u32_image<'s>(&'s mut self) -> Option<TypedImageViewMut<'s, 'a, U8x4>> {
         if let ImageRowsMut::U8x4(rows) = &mut self.rows {
             Some(TypedImageViewMut {
@@ -545,6 +838,66 @@ impl<'a> ImageViewMut<'a> {
             None
         }
     }
+
+    pub(crate) fn u16_image<'s>(&'s mut self) -> Option<TypedImageViewMut<'s, 'a, U16>> {
+        if let ImageRowsMut::U16(rows) = &mut self.rows {
+            Some(TypedImageViewMut {
+                width: self.width,
+                height: self.height,
+                rows,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn u16x3_image<'s>(&'s mut self) -> Option<TypedImageViewMut<'s, 'a, U16x3>> {
+        if let ImageRowsMut::U16x3(rows) = &mut self.rows {
+            Some(TypedImageViewMut {
+                width: self.width,
+                height: self.height,
+                rows,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn u16x4_image<'s>(&'s mut self) -> Option<TypedImageViewMut<'s, 'a, U16x4>> {
+        if let ImageRowsMut::U16x4(rows) = &mut self.rows {
+            Some(TypedImageViewMut {
+                width: self.width,
+                height: self.height,
+                rows,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn f32x3_image<'s>(&'s mut self) -> Option<TypedImageViewMut<'s, 'a, F32x3>> {
+        if let ImageRowsMut::F32x3(rows) = &mut self.rows {
+            Some(TypedImageViewMut {
+                width: self.width,
+                height: self.height,
+                rows,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn f32x4_image<'s>(&'s mut self) -> Option<TypedImageViewMut<'s, 'a, F32x4>> {
+        if let ImageRowsMut::F32x4(rows) = &mut self.rows {
+            Some(TypedImageViewMut {
+                width: self.width,
+                height: self.height,
+                rows,
+            })
+        } else {
+            None
+        }
+    }
 }
 
 /// Generic mutable image view.
@@ -580,10 +933,16 @@ where
     }
 
     #[inline(always)]
-    pub fn iter_rows_mut(&mut self) -> slice::IterMut<&'b mut [P::Type]> {
+    pub fn iter_rows_mut(&mut self) -> slice::IterMut<'_, &'b mut [P::Type]> {
         self.rows.iter_mut()
     }
 
+    #[inline(always)]
+    pub(crate) fn rows_mut(&mut self) -> &mut [&'b mut [P::Type]] {
+        self.rows
+    }
+
+    #[cfg(target_arch = "x86_64")]
     #[inline(always)]
     pub fn iter_4_rows_mut<'s>(&'s mut self) -> impl Iterator<Item = FourRowsMut<'s, 'b, P::Type>> {
         self.rows.chunks_exact_mut(4).map(|rows| match rows {
@@ -592,6 +951,7 @@ where
         })
     }
 
+    #[cfg(target_arch = "x86_64")]
     #[inline(always)]
     pub fn get_row_mut<'s>(&'s mut self, y: u32) -> Option<RowMut<'s, 'b, P::Type>> {
         self.rows.get_mut(y as usize)
@@ -613,6 +973,102 @@ fn check_rows_count_and_size<T>(
     Ok(())
 }
 
+fn check_crop_box(
+    width: NonZeroU32,
+    height: NonZeroU32,
+    crop_box: CropBox,
+) -> Result<(), CropBoxError> {
+    // Negated comparisons also reject NaN values.
+    if !(crop_box.width > 0. && crop_box.height > 0.) {
+        return Err(CropBoxError::WidthOrHeightLessOrEqualToZero);
+    }
+    let width = width.get() as f64;
+    let height = height.get() as f64;
+    if !(crop_box.left >= 0.
+        && crop_box.left < width
+        && crop_box.top >= 0.
+        && crop_box.top < height)
+    {
+        return Err(CropBoxError::PositionIsOutOfImageBoundaries);
+    }
+    let right = crop_box.left + crop_box.width;
+    let bottom = crop_box.top + crop_box.height;
+    if right > width || bottom > height {
+        return Err(CropBoxError::SizeIsOutOfImageBoundaries);
+    }
+    Ok(())
+}
+
+fn immutable_rows<'s, T>(rows: &'s [&mut [T]]) -> Vec<&'s [T]> {
+    rows.iter().map(|row| &**row).collect()
+}
+
+/// Region of image in whole pixels.
+struct Region {
+    left: usize,
+    top: usize,
+    width: usize,
+    height: usize,
+}
+
+/// Returns parts of rows inside the region. Region must be checked
+/// for the size of image before.
+fn sub_rows_mut<'s, T>(rows: &'s mut [&mut [T]], region: Region) -> Vec<&'s mut [T]> {
+    let right = region.left + region.width;
+    rows[region.top..region.top + region.height]
+        .iter_mut()
+        .map(|row| &mut row[region.left..right])
+        .collect()
+}
+
+/// Checks that buffer contains `height` rows with given size
+/// placed at distance `stride` from each other.
+fn check_buffer_size(
+    buffer_len: usize,
+    height: NonZeroU32,
+    row_size: usize,
+    stride: usize,
+) -> Result<(), ImageBufferError> {
+    if stride < row_size {
+        return Err(ImageBufferError::InvalidStride);
+    }
+    let height = height.get() as usize;
+    // Sizes which don't fit into `usize` can't be sizes of any buffer.
+    let min_size = stride
+        .checked_mul(height - 1)
+        .and_then(|size| size.checked_add(row_size))
+        .ok_or(ImageBufferError::InvalidBufferSize)?;
+    let max_size = stride.saturating_mul(height);
+    if buffer_len < min_size || buffer_len > max_size {
+        return Err(ImageBufferError::InvalidBufferSize);
+    }
+    Ok(())
+}
+
+/// Splits buffer into rows of pixels. Buffer must be checked
+/// by `check_buffer_size()` before.
+fn buffer_rows<T>(
+    buffer: &[u8],
+    stride: usize,
+    row_size: usize,
+) -> Result<Vec<&[T]>, ImageBufferError> {
+    buffer
+        .chunks(stride)
+        .map(|row| align_buffer_to(&row[..row_size]))
+        .collect()
+}
+
+fn buffer_rows_mut<T>(
+    buffer: &mut [u8],
+    stride: usize,
+    row_size: usize,
+) -> Result<Vec<&mut [T]>, ImageBufferError> {
+    buffer
+        .chunks_mut(stride)
+        .map(|row| align_buffer_to_mut(&mut row[..row_size]))
+        .collect()
+}
+
 fn align_buffer_to<T>(buffer: &[u8]) -> Result<&[T], ImageBufferError> {
     let (head, pixels, _) = unsafe { buffer.align_to::<T>() };
     if !head.is_empty() {
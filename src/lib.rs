@@ -1,20 +1,28 @@
 #![doc = include_str!("../README.md")]
+// Example from README is a copy of test from "tests/examples.rs".
+#![allow(clippy::test_attr_in_doctest)]
 
 pub use alpha::{MulDiv, MulDivImageError, MulDivImagesError};
-pub use convolution::FilterType;
+pub use convolution::{BicubicParams, Filter, FilterType, GaussianParams};
 pub use errors::*;
 pub use image_view::{CropBox, ImageRows, ImageRowsMut, ImageView, ImageViewMut};
+pub use mipmaps::MipmapBuilder;
 pub use pixels::PixelType;
-pub use resizer::{CpuExtensions, ResizeAlg, Resizer};
+pub use resizer::{CpuExtensions, FloatOutputMode, GammaCorrection, ResizeAlg, Resizer};
+pub use streaming::StreamingResizer;
 
 pub use crate::image::Image;
 
 mod alpha;
+pub mod color;
 mod convolution;
 mod errors;
 mod image;
 mod image_view;
+mod mipmaps;
 mod pixels;
 mod resizer;
 #[cfg(target_arch = "x86_64")]
 mod simd_utils;
+mod streaming;
+mod threading;
@@ -0,0 +1,61 @@
+use std::arch::x86_64::*;
+
+use super::native;
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::U8x4;
+use crate::simd_utils;
+
+pub(crate) fn reduce(src_image: TypedImageView<U8x4>, mut dst_image: TypedImageViewMut<U8x4>) {
+    let src_rows = src_image.iter_2_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+    for ((src_row0, src_row1), dst_row) in src_rows.zip(dst_rows) {
+        unsafe {
+            reduce_row(src_row0, src_row1, dst_row);
+        }
+    }
+}
+
+/// Length of source rows must be equal to double length
+/// of destination row.
+#[target_feature(enable = "avx2")]
+unsafe fn reduce_row(src_row0: &[u32], src_row1: &[u32], dst_row: &mut [u32]) {
+    // Places the same components of adjacent pixels side by side.
+    #[rustfmt::skip]
+    let shuffle = _mm256_set_epi8(
+        15, 11, 14, 10, 13, 9, 12, 8,
+        7, 3, 6, 2, 5, 1, 4, 0,
+        15, 11, 14, 10, 13, 9, 12, 8,
+        7, 3, 6, 2, 5, 1, 4, 0,
+    );
+    let ones = _mm256_set1_epi8(1);
+    let two = _mm256_set1_epi16(2);
+
+    let dst_width = dst_row.len();
+    let mut dst_x: usize = 0;
+    while dst_x + 8 <= dst_width {
+        let src_x = dst_x * 2;
+        let mut sums = [_mm256_setzero_si256(); 2];
+        for (i, sum) in sums.iter_mut().enumerate() {
+            let pixels0 = simd_utils::loadu_si256(src_row0, src_x + i * 8);
+            let pixels1 = simd_utils::loadu_si256(src_row1, src_x + i * 8);
+            // Sums of components of horizontal pairs of pixels
+            let pairs0 = _mm256_maddubs_epi16(_mm256_shuffle_epi8(pixels0, shuffle), ones);
+            let pairs1 = _mm256_maddubs_epi16(_mm256_shuffle_epi8(pixels1, shuffle), ones);
+            *sum = _mm256_srli_epi16::<2>(_mm256_add_epi16(_mm256_add_epi16(pairs0, pairs1), two));
+        }
+        // Packing works inside of 128-bit lanes, so pairs of pixels
+        // are stored in order 0, 2, 1, 3.
+        let dst_pixels = _mm256_packus_epi16(sums[0], sums[1]);
+        let dst_pixels = _mm256_permute4x64_epi64::<0b11_01_10_00>(dst_pixels);
+        let dst_ptr = dst_row.get_unchecked_mut(dst_x..).as_mut_ptr() as *mut __m256i;
+        _mm256_storeu_si256(dst_ptr, dst_pixels);
+        dst_x += 8;
+    }
+
+    let src_x = dst_x * 2;
+    native::reduce_row::<U8x4>(
+        &src_row0[src_x..],
+        &src_row1[src_x..],
+        &mut dst_row[dst_x..],
+    );
+}
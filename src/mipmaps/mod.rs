@@ -0,0 +1,375 @@
+use std::num::NonZeroU32;
+
+use crate::alpha::AlphaMulDiv;
+use crate::image_view::{CropBox, TypedImageView, TypedImageViewMut};
+use crate::pixels::{
+    F32x3, F32x4, Pixel, PixelType, U16x3, U16x4, U8x2, U8x3, U8x4, F32, I32, U16, U8,
+};
+use crate::resizer::get_temp_image_from_buffer;
+use crate::{
+    CpuExtensions, FilterType, Image, ImageView, ImageViewMut, MipmapsError, ResizeAlg, Resizer,
+};
+
+#[cfg(target_arch = "x86_64")]
+mod avx2;
+mod native;
+#[cfg(target_arch = "x86_64")]
+mod sse4;
+
+/// Averaging of components of four pixels of 2x2 block.
+pub(crate) trait Average: Copy {
+    fn average(a: Self, b: Self, c: Self, d: Self) -> Self;
+}
+
+impl Average for u8 {
+    #[inline(always)]
+    fn average(a: Self, b: Self, c: Self, d: Self) -> Self {
+        ((a as u16 + b as u16 + c as u16 + d as u16 + 2) >> 2) as u8
+    }
+}
+
+impl Average for u16 {
+    #[inline(always)]
+    fn average(a: Self, b: Self, c: Self, d: Self) -> Self {
+        ((a as u32 + b as u32 + c as u32 + d as u32 + 2) >> 2) as u16
+    }
+}
+
+impl Average for i32 {
+    #[inline(always)]
+    fn average(a: Self, b: Self, c: Self, d: Self) -> Self {
+        ((a as i64 + b as i64 + c as i64 + d as i64 + 2) >> 2) as i32
+    }
+}
+
+impl Average for f32 {
+    #[inline(always)]
+    fn average(a: Self, b: Self, c: Self, d: Self) -> Self {
+        (a + b + c + d) * 0.25
+    }
+}
+
+/// Reduction of image by two times with box filter.
+pub(crate) trait BoxReduce: Pixel + Sized {
+    type Component: Average;
+    /// Count of components in pixel.
+    const COUNT: usize;
+
+    /// Size of destination image must be equal to half of size
+    /// of source image.
+    fn reduce(
+        src_image: TypedImageView<Self>,
+        dst_image: TypedImageViewMut<Self>,
+        _cpu_extensions: CpuExtensions,
+    ) {
+        native::reduce(src_image, dst_image);
+    }
+}
+
+macro_rules! box_reduce {
+    ($name:ident, $component:ty, $count:expr) => {
+        impl BoxReduce for $name {
+            type Component = $component;
+            const COUNT: usize = $count;
+        }
+    };
+}
+
+box_reduce!(U8, u8, 1);
+box_reduce!(U8x2, u8, 2);
+box_reduce!(U8x3, u8, 3);
+box_reduce!(U16, u16, 1);
+box_reduce!(U16x3, u16, 3);
+box_reduce!(U16x4, u16, 4);
+box_reduce!(I32, i32, 1);
+box_reduce!(F32, f32, 1);
+box_reduce!(F32x3, f32, 3);
+box_reduce!(F32x4, f32, 4);
+
+impl BoxReduce for U8x4 {
+    type Component = u8;
+    const COUNT: usize = 4;
+
+    fn reduce(
+        src_image: TypedImageView<Self>,
+        dst_image: TypedImageViewMut<Self>,
+        cpu_extensions: CpuExtensions,
+    ) {
+        match cpu_extensions {
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Avx2 => avx2::reduce(src_image, dst_image),
+            #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+            CpuExtensions::Avx512 => avx2::reduce(src_image, dst_image),
+            #[cfg(target_arch = "x86_64")]
+            CpuExtensions::Sse4_1 => sse4::reduce(src_image, dst_image),
+            _ => native::reduce(src_image, dst_image),
+        }
+    }
+}
+
+/// Builder of mipmaps (image pyramid). Every next level of mipmaps
+/// is created from the previous one and has half of its size
+/// (rounded down, but not less than one pixel), down to the level
+/// with size 1x1.
+///
+/// Levels are resized by [Resizer] with convolution with chosen filter,
+/// so levels of images with odd dimensions include all pixels of
+/// the previous level. If `FilterType::Box` is used, levels with exactly
+/// half size of the previous level are calculated by fast path that
+/// averages blocks of 2x2 pixels (with SIMD for `U8x4` pixels).
+///
+/// Color-channels of `U8x2`, `U8x4`, `U16x4` and `F32x4` images are
+/// multiplied by alpha-channel before the reduction and divided by it
+/// after the reduction (see [MipmapBuilder::premultiply_alpha]), so colors
+/// of transparent pixels don't bleed into the visible ones.
+///
+/// Crop box of source image is ignored.
+///
+/// By default, instance of `MipmapBuilder` created with best CPU-extensions
+/// provided by your CPU. You can change this by use method
+/// [MipmapBuilder::set_cpu_extensions].
+///
+/// # Examples
+///
+/// ```
+/// use std::num::NonZeroU32;
+/// use fast_image_resize::{FilterType, Image, MipmapBuilder, PixelType};
+///
+/// let width = NonZeroU32::new(10).unwrap();
+/// let height = NonZeroU32::new(7).unwrap();
+/// let src_image = Image::new(width, height, PixelType::U8x4);
+///
+/// let mut builder = MipmapBuilder::new(FilterType::Box);
+/// let levels = builder.build(&src_image.view());
+/// let sizes: Vec<(u32, u32)> = levels
+///     .iter()
+///     .map(|level| (level.width().get(), level.height().get()))
+///     .collect();
+/// assert_eq!(sizes, [(5, 3), (2, 1), (1, 1)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MipmapBuilder {
+    filter_type: FilterType,
+    resizer: Resizer,
+    alpha_buffer: Vec<u8>,
+    /// Multiplication of color-channels of `U8x2`, `U8x4`, `U16x4` and
+    /// `F32x4` images by alpha-channel while calculating levels.
+    /// Default is `true`. It must be disabled if color-channels of source
+    /// image are already multiplied by alpha.
+    pub premultiply_alpha: bool,
+}
+
+impl MipmapBuilder {
+    pub fn new(filter_type: FilterType) -> Self {
+        Self {
+            filter_type,
+            resizer: Resizer::new(ResizeAlg::Convolution(filter_type)),
+            alpha_buffer: Vec::new(),
+            premultiply_alpha: true,
+        }
+    }
+
+    #[inline(always)]
+    pub fn filter_type(&self) -> FilterType {
+        self.filter_type
+    }
+
+    #[inline(always)]
+    pub fn cpu_extensions(&self) -> CpuExtensions {
+        self.resizer.cpu_extensions()
+    }
+
+    /// # Safety
+    /// This is unsafe because this method allows you to set a CPU-extensions
+    /// that is not actually supported by your CPU.
+    pub unsafe fn set_cpu_extensions(&mut self, extensions: CpuExtensions) {
+        self.resizer.set_cpu_extensions(extensions);
+    }
+
+    /// Returns sizes of levels of mipmaps for image with given size.
+    /// Level with size of source image is not included.
+    pub fn level_sizes(width: NonZeroU32, height: NonZeroU32) -> Vec<(NonZeroU32, NonZeroU32)> {
+        let mut sizes = Vec::new();
+        let (mut width, mut height) = (width.get(), height.get());
+        while width > 1 || height > 1 {
+            width = (width / 2).max(1);
+            height = (height / 2).max(1);
+            sizes.push((
+                NonZeroU32::new(width).unwrap(),
+                NonZeroU32::new(height).unwrap(),
+            ));
+        }
+        sizes
+    }
+
+    /// Creates all levels of mipmaps of source image.
+    /// Level with size of source image is not included.
+    pub fn build(&mut self, src_image: &ImageView) -> Vec<Image<'static>> {
+        let src_image = full_image_view(src_image);
+        let pixel_type = src_image.pixel_type();
+        let sizes = Self::level_sizes(src_image.width(), src_image.height());
+        let mut levels: Vec<Image<'static>> = Vec::with_capacity(sizes.len());
+        for (width, height) in sizes {
+            let mut level = Image::new(width, height, pixel_type);
+            match levels.last() {
+                Some(prev_level) => self.reduce(&prev_level.view(), &mut level.view_mut()),
+                None => self.reduce(&src_image, &mut level.view_mut()),
+            }
+            levels.push(level);
+        }
+        levels
+    }
+
+    /// Creates all levels of mipmaps of source image and stores them into
+    /// destination images. Sizes of destination images must be equal to
+    /// sizes returned by [MipmapBuilder::level_sizes].
+    pub fn build_into(
+        &mut self,
+        src_image: &ImageView,
+        dst_images: &mut [ImageViewMut],
+    ) -> Result<(), MipmapsError> {
+        let src_image = full_image_view(src_image);
+        let pixel_type = src_image.pixel_type();
+        if dst_images.iter().any(|dst| dst.pixel_type() != pixel_type) {
+            return Err(MipmapsError::DifferentTypesOfPixels);
+        }
+        let sizes = Self::level_sizes(src_image.width(), src_image.height());
+        if dst_images.len() != sizes.len() {
+            return Err(MipmapsError::InvalidCountOfLevels);
+        }
+        let is_size_valid = dst_images
+            .iter()
+            .zip(sizes)
+            .all(|(dst, size)| (dst.width(), dst.height()) == size);
+        if !is_size_valid {
+            return Err(MipmapsError::InvalidLevelSize);
+        }
+
+        for i in 0..dst_images.len() {
+            let (prev_levels, levels) = dst_images.split_at_mut(i);
+            let level = &mut levels[0];
+            match prev_levels.last() {
+                Some(prev_level) => self.reduce(&prev_level.view(), level),
+                None => self.reduce(&src_image, level),
+            }
+        }
+        Ok(())
+    }
+
+    fn reduce(&mut self, src_image: &ImageView, dst_image: &mut ImageViewMut) {
+        let is_half = src_image.width().get() == dst_image.width().get() * 2
+            && src_image.height().get() == dst_image.height().get() * 2;
+        if !(is_half && self.filter_type == FilterType::Box) {
+            self.resizer.premultiply_alpha = self.premultiply_alpha;
+            // Types of pixels of images are checked by caller.
+            self.resizer.resize(src_image, dst_image).unwrap();
+            return;
+        }
+        let cpu_extensions = self.cpu_extensions();
+        let premultiply_alpha = self.premultiply_alpha;
+        let alpha_buffer = &mut self.alpha_buffer;
+        match src_image.pixel_type() {
+            PixelType::U8x2 if premultiply_alpha => reduce_with_alpha(
+                src_image.u8x2_image(),
+                dst_image.u8x2_image(),
+                cpu_extensions,
+                alpha_buffer,
+            ),
+            PixelType::U8x4 if premultiply_alpha => reduce_with_alpha(
+                src_image.u32_image(),
+                dst_image.u32_image(),
+                cpu_extensions,
+                alpha_buffer,
+            ),
+            PixelType::U16x4 if premultiply_alpha => reduce_with_alpha(
+                src_image.u16x4_image(),
+                dst_image.u16x4_image(),
+                cpu_extensions,
+                alpha_buffer,
+            ),
+            PixelType::F32x4 if premultiply_alpha => reduce_with_alpha(
+                src_image.f32x4_image(),
+                dst_image.f32x4_image(),
+                cpu_extensions,
+                alpha_buffer,
+            ),
+            PixelType::U8x2 => reduce(
+                src_image.u8x2_image(),
+                dst_image.u8x2_image(),
+                cpu_extensions,
+            ),
+            PixelType::U8x3 => reduce(
+                src_image.u8x3_image(),
+                dst_image.u8x3_image(),
+                cpu_extensions,
+            ),
+            PixelType::U8x4 => reduce(src_image.u32_image(), dst_image.u32_image(), cpu_extensions),
+            PixelType::I32 => reduce(src_image.i32_image(), dst_image.i32_image(), cpu_extensions),
+            PixelType::F32 => reduce(src_image.f32_image(), dst_image.f32_image(), cpu_extensions),
+            PixelType::U8 => reduce(src_image.u8_image(), dst_image.u8_image(), cpu_extensions),
+            PixelType::U16 => reduce(src_image.u16_image(), dst_image.u16_image(), cpu_extensions),
+            PixelType::U16x3 => reduce(
+                src_image.u16x3_image(),
+                dst_image.u16x3_image(),
+                cpu_extensions,
+            ),
+            PixelType::U16x4 => reduce(
+                src_image.u16x4_image(),
+                dst_image.u16x4_image(),
+                cpu_extensions,
+            ),
+            PixelType::F32x3 => reduce(
+                src_image.f32x3_image(),
+                dst_image.f32x3_image(),
+                cpu_extensions,
+            ),
+            PixelType::F32x4 => reduce(
+                src_image.f32x4_image(),
+                dst_image.f32x4_image(),
+                cpu_extensions,
+            ),
+        }
+    }
+}
+
+/// Returns view of the whole source image.
+fn full_image_view<'a>(src_image: &ImageView<'a>) -> ImageView<'a> {
+    let mut src_image = src_image.clone();
+    src_image
+        .set_crop_box(CropBox::from_size(src_image.width(), src_image.height()))
+        .unwrap();
+    src_image
+}
+
+fn reduce<P: BoxReduce>(
+    src_image: Option<TypedImageView<P>>,
+    dst_image: Option<TypedImageViewMut<P>>,
+    cpu_extensions: CpuExtensions,
+) {
+    if let (Some(src_image), Some(dst_image)) = (src_image, dst_image) {
+        P::reduce(src_image, dst_image, cpu_extensions);
+    }
+}
+
+/// Same as `reduce`, but color-channels of source image are multiplied
+/// by alpha before the reduction and color-channels of destination image
+/// are divided by alpha after it.
+fn reduce_with_alpha<P: BoxReduce + AlphaMulDiv>(
+    src_image: Option<TypedImageView<P>>,
+    dst_image: Option<TypedImageViewMut<P>>,
+    cpu_extensions: CpuExtensions,
+    alpha_buffer: &mut Vec<u8>,
+) {
+    if let (Some(src_image), Some(mut dst_image)) = (src_image, dst_image) {
+        let mut premultiplied =
+            get_temp_image_from_buffer(alpha_buffer, src_image.width(), src_image.height());
+        P::multiply_alpha(src_image, premultiplied.dst_view(), cpu_extensions);
+        let (width, height) = (dst_image.width(), dst_image.height());
+        P::reduce(
+            premultiplied.src_view(),
+            TypedImageViewMut::new(width, height, dst_image.rows_mut()),
+            cpu_extensions,
+        );
+        P::divide_alpha_inplace(dst_image, cpu_extensions);
+    }
+}
@@ -0,0 +1,37 @@
+use super::{Average, BoxReduce};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+
+pub(crate) fn reduce<P: BoxReduce>(
+    src_image: TypedImageView<P>,
+    mut dst_image: TypedImageViewMut<P>,
+) {
+    let src_rows = src_image.iter_2_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+    for ((src_row0, src_row1), dst_row) in src_rows.zip(dst_rows) {
+        reduce_row::<P>(src_row0, src_row1, dst_row);
+    }
+}
+
+/// Length of source rows must be equal to double length
+/// of destination row.
+#[inline(always)]
+pub(crate) fn reduce_row<P: BoxReduce>(
+    src_row0: &[P::Type],
+    src_row1: &[P::Type],
+    dst_row: &mut [P::Type],
+) {
+    let count = P::COUNT;
+    // Safety: all types of pixels are arrays of `P::COUNT` components.
+    let src_row0 = unsafe { src_row0.align_to::<P::Component>().1 };
+    let src_row1 = unsafe { src_row1.align_to::<P::Component>().1 };
+    let dst_row = unsafe { dst_row.align_to_mut::<P::Component>().1 };
+
+    let src_pixels = src_row0
+        .chunks_exact(count * 2)
+        .zip(src_row1.chunks_exact(count * 2));
+    for ((s0, s1), dst_pixel) in src_pixels.zip(dst_row.chunks_exact_mut(count)) {
+        for (c, dst_component) in dst_pixel.iter_mut().enumerate() {
+            *dst_component = Average::average(s0[c], s0[c + count], s1[c], s1[c + count]);
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::arch::x86_64::*;
+
+use super::native;
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::U8x4;
+use crate::simd_utils;
+
+pub(crate) fn reduce(src_image: TypedImageView<U8x4>, mut dst_image: TypedImageViewMut<U8x4>) {
+    let src_rows = src_image.iter_2_rows(0, src_image.height().get());
+    let dst_rows = dst_image.iter_rows_mut();
+    for ((src_row0, src_row1), dst_row) in src_rows.zip(dst_rows) {
+        unsafe {
+            reduce_row(src_row0, src_row1, dst_row);
+        }
+    }
+}
+
+/// Length of source rows must be equal to double length
+/// of destination row.
+#[target_feature(enable = "sse4.1")]
+unsafe fn reduce_row(src_row0: &[u32], src_row1: &[u32], dst_row: &mut [u32]) {
+    // Places the same components of adjacent pixels side by side.
+    #[rustfmt::skip]
+    let shuffle = _mm_set_epi8(
+        15, 11, 14, 10, 13, 9, 12, 8,
+        7, 3, 6, 2, 5, 1, 4, 0,
+    );
+    let ones = _mm_set1_epi8(1);
+    let two = _mm_set1_epi16(2);
+
+    let dst_width = dst_row.len();
+    let mut dst_x: usize = 0;
+    while dst_x + 4 <= dst_width {
+        let src_x = dst_x * 2;
+        let mut sums = [_mm_setzero_si128(); 2];
+        for (i, sum) in sums.iter_mut().enumerate() {
+            let pixels0 = simd_utils::loadu_si128(src_row0, src_x + i * 4);
+            let pixels1 = simd_utils::loadu_si128(src_row1, src_x + i * 4);
+            // Sums of components of horizontal pairs of pixels
+            let pairs0 = _mm_maddubs_epi16(_mm_shuffle_epi8(pixels0, shuffle), ones);
+            let pairs1 = _mm_maddubs_epi16(_mm_shuffle_epi8(pixels1, shuffle), ones);
+            *sum = _mm_srli_epi16::<2>(_mm_add_epi16(_mm_add_epi16(pairs0, pairs1), two));
+        }
+        let dst_pixels = _mm_packus_epi16(sums[0], sums[1]);
+        let dst_ptr = dst_row.get_unchecked_mut(dst_x..).as_mut_ptr() as *mut __m128i;
+        _mm_storeu_si128(dst_ptr, dst_pixels);
+        dst_x += 4;
+    }
+
+    let src_x = dst_x * 2;
+    native::reduce_row::<U8x4>(
+        &src_row0[src_x..],
+        &src_row1[src_x..],
+        &mut dst_row[dst_x..],
+    );
+}
@@ -2,32 +2,53 @@ use std::mem::size_of;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PixelType {
+    U8x2,
+    U8x3,
     U8x4,
     I32,
     F32,
     U8,
+    U16,
+    U16x3,
+    U16x4,
+    F32x3,
+    F32x4,
 }
 
 impl PixelType {
     pub(crate) fn size(&self) -> usize {
         match self {
             Self::U8 => 1,
+            Self::U8x2 => 2,
+            Self::U8x3 => 3,
+            Self::U16 => 2,
+            Self::U16x3 => 6,
+            Self::U16x4 => 8,
+            Self::F32x3 => 12,
+            Self::F32x4 => 16,
             _ => 4,
         }
     }
 
     pub(crate) fn is_aligned(&self, buffer: &[u8]) -> bool {
         match self {
+            Self::U8x2 => true,
+            Self::U8x3 => true,
             Self::U8x4 => unsafe { buffer.align_to::<u32>().0.is_empty() },
             Self::I32 => unsafe { buffer.align_to::<i32>().0.is_empty() },
-            Self::F32 => unsafe { buffer.align_to::<f32>().0.is_empty() },
+            Self::F32 | Self::F32x3 | Self::F32x4 => unsafe {
+                buffer.align_to::<f32>().0.is_empty()
+            },
             Self::U8 => true,
+            Self::U16 | Self::U16x3 | Self::U16x4 => unsafe {
+                buffer.align_to::<u16>().0.is_empty()
+            },
         }
     }
 }
 
 pub(crate) trait Pixel {
-    type Type: Copy;
+    type Type: Copy + Send + Sync;
 
     fn size() -> usize {
         size_of::<Self::Type>()
@@ -50,7 +71,14 @@ macro_rules! pixel_struct {
     };
 }
 
+pixel_struct!(U8x2, [u8; 2], PixelType::U8x2);
+pixel_struct!(U8x3, [u8; 3], PixelType::U8x3);
 pixel_struct!(U8x4, u32, PixelType::U8x4);
 pixel_struct!(I32, i32, PixelType::I32);
 pixel_struct!(F32, f32, PixelType::F32);
 pixel_struct!(U8, u8, PixelType::U8);
+pixel_struct!(U16, u16, PixelType::U16);
+pixel_struct!(U16x3, [u16; 3], PixelType::U16x3);
+pixel_struct!(U16x4, [u16; 4], PixelType::U16x4);
+pixel_struct!(F32x3, [f32; 3], PixelType::F32x3);
+pixel_struct!(F32x4, [f32; 4], PixelType::F32x4);
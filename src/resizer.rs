@@ -1,10 +1,17 @@
 use std::num::NonZeroU32;
+use std::ops::Range;
+use std::sync::Arc;
 
-use crate::convolution::{self, Convolution, FilterType};
+use crate::alpha::AlphaMulDiv;
+use crate::color::SrgbLuts;
+use crate::convolution::{
+    Coefficients, CoefficientsCache, CoefficientsView, Convolution, FilterType,
+};
 use crate::errors::DifferentTypesOfPixelsError;
 use crate::image::InnerImage;
-use crate::image_view::{ImageView, ImageViewMut, TypedImageView, TypedImageViewMut};
-use crate::pixels::{Pixel, PixelType};
+use crate::image_view::{CropBox, ImageView, ImageViewMut, TypedImageView, TypedImageViewMut};
+use crate::pixels::{Pixel, PixelType, U16x4, U8x4, U16, U8};
+use crate::threading;
 
 #[derive(Debug, Clone, Copy)]
 pub enum CpuExtensions {
@@ -13,14 +20,33 @@ pub enum CpuExtensions {
     Sse2,
     #[cfg(target_arch = "x86_64")]
     Sse4_1,
+    /// Kernels for floating point pixels also use FMA instructions,
+    /// so this variant requires CPU supporting both AVX2 and FMA.
     #[cfg(target_arch = "x86_64")]
     Avx2,
+    /// Requires CPU supporting AVX-512F and AVX-512BW (and also AVX2 and FMA
+    /// for types of pixels that haven't got AVX-512 version of kernels).
+    #[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+    Avx512,
+    #[cfg(target_arch = "aarch64")]
+    Neon,
+    /// Available only if crate is compiled with `target-feature=+simd128`.
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    Simd128,
 }
 
 impl Default for CpuExtensions {
     #[cfg(target_arch = "x86_64")]
     fn default() -> Self {
-        if is_x86_feature_detected!("avx2") {
+        #[cfg(feature = "avx512")]
+        if is_x86_feature_detected!("avx512f")
+            && is_x86_feature_detected!("avx512bw")
+            && is_x86_feature_detected!("avx2")
+            && is_x86_feature_detected!("fma")
+        {
+            return Self::Avx512;
+        }
+        if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
             Self::Avx2
         } else if is_x86_feature_detected!("sse4.1") {
             Self::Sse4_1
@@ -31,7 +57,25 @@ impl Default for CpuExtensions {
         }
     }
 
-    #[cfg(not(target_arch = "x86_64"))]
+    #[cfg(target_arch = "aarch64")]
+    fn default() -> Self {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            Self::Neon
+        } else {
+            Self::None
+        }
+    }
+
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    fn default() -> Self {
+        Self::Simd128
+    }
+
+    #[cfg(not(any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        all(target_arch = "wasm32", target_feature = "simd128")
+    )))]
     fn default() -> Self {
         Self::None
     }
@@ -51,13 +95,117 @@ impl Default for ResizeAlg {
     }
 }
 
+/// Post-processing of components of pixels with floating point
+/// values (`F32`, `F32x3` and `F32x4`) applied after resizing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum FloatOutputMode {
+    /// Results of resizing are stored with full precision.
+    Exact,
+    /// Results of resizing are rounded to the nearest integer.
+    Round,
+    /// Results of resizing are clamped into the range `[min, max]`.
+    Clamp { min: f32, max: f32 },
+    /// Results of resizing are rounded to the nearest integer
+    /// and clamped into the range `[min, max]`.
+    RoundAndClamp { min: f32, max: f32 },
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for FloatOutputMode {
+    fn default() -> Self {
+        Self::Exact
+    }
+}
+
+impl FloatOutputMode {
+    #[inline(always)]
+    fn process(self, value: f32) -> f32 {
+        match self {
+            Self::Exact => value,
+            Self::Round => value.round(),
+            Self::Clamp { min, max } => value.max(min).min(max),
+            Self::RoundAndClamp { min, max } => value.round().max(min).min(max),
+        }
+    }
+
+    fn apply<P: Pixel>(
+        self,
+        mut image: TypedImageViewMut<P>,
+        components: fn(&mut P::Type) -> &mut [f32],
+    ) {
+        if self == Self::Exact {
+            return;
+        }
+        for row in image.iter_rows_mut() {
+            for pixel in row.iter_mut() {
+                for value in components(pixel) {
+                    *value = self.process(*value);
+                }
+            }
+        }
+    }
+}
+
+/// Transfer function of color-channels of source and destination images.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GammaCorrection {
+    /// Values of pixels are resized as is.
+    None,
+    /// Color-channels of `U8` and `U8x4` images are encoded with sRGB
+    /// transfer function. Such images are converted into 16-bit
+    /// linear light before resizing and converted back after it.
+    /// Images with other types of pixels are resized as is.
+    ///
+    /// Resizing in linear light is much slower (about 20 times for
+    /// downscaling of `U8x4` image with `Lanczos3` filter on CPU with AVX2),
+    /// because convolution of 16-bit pixels has only native implementation
+    /// yet. Only pixels of source image that are used by resizing of its
+    /// crop box are converted into linear light.
+    ///
+    /// Color-channels of `U8x4` images must be premultiplied by alpha
+    /// with help of [MulDiv](crate::MulDiv), as usual, or
+    /// [Resizer::premultiply_alpha] must be enabled. Resizer takes it into
+    /// account and premultiplies color-channels in linear light.
+    Srgb,
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for GammaCorrection {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
 /// Methods of this structure used to resize images.
 #[derive(Default, Debug, Clone)]
 pub struct Resizer {
     pub algorithm: ResizeAlg,
+    /// Post-processing of results of resizing images
+    /// with floating point pixels.
+    pub float_output_mode: FloatOutputMode,
+    /// Resizing of images in linear light. Downscaling of sRGB images
+    /// without it darkens fine high-contrast details.
+    pub gamma_correction: GammaCorrection,
+    /// Multiplication of color-channels by alpha-channel inside of
+    /// resizing. If it is `true`, color-channels of `U8x2`, `U8x4`,
+    /// `U16x4` and `F32x4` source images must not be premultiplied by
+    /// alpha. Resizer multiplies rows of source image by alpha before
+    /// the horizontal pass of convolution and divides rows of destination
+    /// image by alpha right after the vertical pass, so separate calls of
+    /// [MulDiv](crate::MulDiv) are not needed.
+    pub premultiply_alpha: bool,
     cpu_extensions: CpuExtensions,
     convolution_buffer: Vec<u8>,
     super_sampling_buffer: Vec<u8>,
+    coefficients_cache: CoefficientsCache,
+    linear_src_buffer: Vec<u8>,
+    linear_dst_buffer: Vec<u8>,
+    alpha_buffer: Vec<u8>,
+    srgb_luts: Option<Arc<SrgbLuts>>,
+    #[cfg(feature = "rayon")]
+    thread_pool: Option<Arc<rayon::ThreadPool>>,
 }
 
 impl Resizer {
@@ -76,8 +224,13 @@ impl Resizer {
     /// the result to the latter's pixel buffer.
     ///
     /// This method doesn't multiply source image and doesn't divide
-    /// destination image by alpha channel.
-    /// You must use [MulDiv](crate::MulDiv) for these actions.
+    /// destination image by alpha channel if [Resizer::premultiply_alpha]
+    /// is `false`. You must use [MulDiv](crate::MulDiv) for these actions
+    /// in this case.
+    ///
+    /// Coefficients of convolution are cached between calls, so it is
+    /// better to reuse the instance of resizer to resize many images
+    /// with the same sizes, crop box and filter.
     pub fn resize(
         &mut self,
         src_image: &ImageView,
@@ -87,10 +240,49 @@ impl Resizer {
             return Err(DifferentTypesOfPixelsError);
         }
         match src_image.pixel_type() {
+            PixelType::U8x2 => {
+                if let Some(src_rows) = src_image.u8x2_image() {
+                    if let Some(dst_rows) = dst_image.u8x2_image() {
+                        if self.premultiply_alpha {
+                            self.resize_inner_with_alpha(src_rows, dst_rows);
+                        } else {
+                            self.resize_inner(src_rows, dst_rows);
+                        }
+                    }
+                }
+            }
+            PixelType::U8x3 => {
+                if let Some(src_rows) = src_image.u8x3_image() {
+                    if let Some(dst_rows) = dst_image.u8x3_image() {
+                        self.resize_inner(src_rows, dst_rows);
+                    }
+                }
+            }
             PixelType::U8x4 => {
                 if let Some(src_rows) = src_image.u32_image() {
                     if let Some(dst_rows) = dst_image.u32_image() {
-                        self.resize_inner(src_rows, dst_rows);
+                        let is_srgb = self.gamma_correction == GammaCorrection::Srgb;
+                        if is_srgb && self.premultiply_alpha {
+                            self.resize_in_linear_light::<U8x4, U16x4>(
+                                src_rows,
+                                dst_rows,
+                                SrgbLuts::u8x4_straight_to_linear,
+                                SrgbLuts::linear_to_u8x4_straight,
+                                Self::resize_inner_with_alpha,
+                            );
+                        } else if is_srgb {
+                            self.resize_in_linear_light::<U8x4, U16x4>(
+                                src_rows,
+                                dst_rows,
+                                SrgbLuts::u8x4_to_linear,
+                                SrgbLuts::linear_to_u8x4,
+                                Self::resize_inner,
+                            );
+                        } else if self.premultiply_alpha {
+                            self.resize_inner_with_alpha(src_rows, dst_rows);
+                        } else {
+                            self.resize_inner(src_rows, dst_rows);
+                        }
                     }
                 }
             }
@@ -111,15 +303,437 @@ impl Resizer {
             PixelType::U8 => {
                 if let Some(src_rows) = src_image.u8_image() {
                     if let Some(dst_rows) = dst_image.u8_image() {
+                        if self.gamma_correction == GammaCorrection::Srgb {
+                            self.resize_in_linear_light::<U8, U16>(
+                                src_rows,
+                                dst_rows,
+                                SrgbLuts::u8_to_linear,
+                                SrgbLuts::linear_to_u8,
+                                Self::resize_inner,
+                            );
+                        } else {
+                            self.resize_inner(src_rows, dst_rows);
+                        }
+                    }
+                }
+            }
+            PixelType::U16 => {
+                if let Some(src_rows) = src_image.u16_image() {
+                    if let Some(dst_rows) = dst_image.u16_image() {
                         self.resize_inner(src_rows, dst_rows);
                     }
                 }
             }
+            PixelType::U16x3 => {
+                if let Some(src_rows) = src_image.u16x3_image() {
+                    if let Some(dst_rows) = dst_image.u16x3_image() {
+                        self.resize_inner(src_rows, dst_rows);
+                    }
+                }
+            }
+            PixelType::U16x4 => {
+                if let Some(src_rows) = src_image.u16x4_image() {
+                    if let Some(dst_rows) = dst_image.u16x4_image() {
+                        if self.premultiply_alpha {
+                            self.resize_inner_with_alpha(src_rows, dst_rows);
+                        } else {
+                            self.resize_inner(src_rows, dst_rows);
+                        }
+                    }
+                }
+            }
+            PixelType::F32x3 => {
+                if let Some(src_rows) = src_image.f32x3_image() {
+                    if let Some(dst_rows) = dst_image.f32x3_image() {
+                        self.resize_inner(src_rows, dst_rows);
+                    }
+                }
+            }
+            PixelType::F32x4 => {
+                if let Some(src_rows) = src_image.f32x4_image() {
+                    if let Some(dst_rows) = dst_image.f32x4_image() {
+                        if self.premultiply_alpha {
+                            self.resize_inner_with_alpha(src_rows, dst_rows);
+                        } else {
+                            self.resize_inner(src_rows, dst_rows);
+                        }
+                    }
+                }
+            }
         }
+        self.apply_float_output_mode(dst_image);
         Ok(())
     }
 
+    /// Resize source image to the sizes of several destination images
+    /// (e.g. thumbnails of different sizes).
+    ///
+    /// The work is shared between destination images where it is possible:
+    ///
+    /// - With [ResizeAlg::Convolution] and [ResizeAlg::SuperSampling]
+    ///   destination image which is at least two times smaller (in both
+    ///   dimensions) than another destination image is resized from the
+    ///   smallest of such images instead of the source image. Its result is
+    ///   equal to result of calling [Resizer::resize] for that bigger image,
+    ///   so it is close, but not equal to result of resizing of the source
+    ///   image.
+    /// - With [ResizeAlg::Convolution] the source image is resized
+    ///   horizontally only once for all other destination images with the
+    ///   same width (e.g. thumbnails cropped to different heights).
+    ///
+    /// Results of other destination images are equal to results of calling
+    /// [Resizer::resize] for them.
+    pub fn resize_many(
+        &mut self,
+        src_image: &ImageView,
+        dst_images: &mut [ImageViewMut],
+    ) -> Result<(), DifferentTypesOfPixelsError> {
+        let pixel_type = src_image.pixel_type();
+        if dst_images.iter().any(|dst| dst.pixel_type() != pixel_type) {
+            return Err(DifferentTypesOfPixelsError);
+        }
+        let sources = match self.algorithm {
+            ResizeAlg::Convolution(_) | ResizeAlg::SuperSampling(_, _) => {
+                let sizes: Vec<_> = dst_images
+                    .iter()
+                    .map(|dst| (dst.width().get(), dst.height().get()))
+                    .collect();
+                cascade_sources(&sizes)
+            }
+            _ => vec![None; dst_images.len()],
+        };
+        self.resize_many_from_source(src_image, dst_images, &sources)?;
+
+        // Destination images resized from other destination images are
+        // processed from the biggest to the smallest one, so their sources
+        // are always ready.
+        let mut cascaded: Vec<usize> = (0..dst_images.len())
+            .filter(|&i| sources[i].is_some())
+            .collect();
+        cascaded.sort_by_key(|&i| {
+            let dst = &dst_images[i];
+            std::cmp::Reverse(dst.width().get() as u64 * dst.height().get() as u64)
+        });
+        for i in cascaded {
+            let source = sources[i].unwrap();
+            let (src_image, dst_image) = if source < i {
+                let (head, tail) = dst_images.split_at_mut(i);
+                (&head[source], &mut tail[0])
+            } else {
+                let (head, tail) = dst_images.split_at_mut(source);
+                (&tail[0], &mut head[i])
+            };
+            self.resize(&src_image.view(), dst_image)?;
+        }
+        Ok(())
+    }
+
+    /// Resizes source image into destination images which `sources` are `None`.
+    fn resize_many_from_source(
+        &mut self,
+        src_image: &ImageView,
+        dst_images: &mut [ImageViewMut],
+        sources: &[Option<usize>],
+    ) -> Result<(), DifferentTypesOfPixelsError> {
+        let pixel_type = src_image.pixel_type();
+        let is_linear_light = self.gamma_correction == GammaCorrection::Srgb
+            && matches!(pixel_type, PixelType::U8 | PixelType::U8x4);
+        let is_premultiplied_inside = self.premultiply_alpha
+            && matches!(
+                pixel_type,
+                PixelType::U8x2 | PixelType::U8x4 | PixelType::U16x4 | PixelType::F32x4
+            );
+        if !matches!(self.algorithm, ResizeAlg::Convolution(_))
+            || is_linear_light
+            || is_premultiplied_inside
+        {
+            for (dst_image, source) in dst_images.iter_mut().zip(sources) {
+                if source.is_none() {
+                    self.resize(src_image, dst_image)?;
+                }
+            }
+            return Ok(());
+        }
+
+        let mut widths: Vec<NonZeroU32> = dst_images
+            .iter()
+            .zip(sources)
+            .filter(|(_, source)| source.is_none())
+            .map(|(dst, _)| dst.width())
+            .collect();
+        widths.sort_unstable();
+        widths.dedup();
+        for width in widths {
+            let group: Vec<&mut ImageViewMut> = dst_images
+                .iter_mut()
+                .zip(sources)
+                .filter(|(dst, source)| source.is_none() && dst.width() == width)
+                .map(|(dst, _)| dst)
+                .collect();
+            match pixel_type {
+                PixelType::U8x2 => self.resize_group(
+                    src_image.u8x2_image(),
+                    group
+                        .into_iter()
+                        .filter_map(|dst| dst.u8x2_image())
+                        .collect(),
+                ),
+                PixelType::U8x3 => self.resize_group(
+                    src_image.u8x3_image(),
+                    group
+                        .into_iter()
+                        .filter_map(|dst| dst.u8x3_image())
+                        .collect(),
+                ),
+                PixelType::U8x4 => self.resize_group(
+                    src_image.u32_image(),
+                    group
+                        .into_iter()
+                        .filter_map(|dst| dst.u32_image())
+                        .collect(),
+                ),
+                PixelType::I32 => self.resize_group(
+                    src_image.i32_image(),
+                    group
+                        .into_iter()
+                        .filter_map(|dst| dst.i32_image())
+                        .collect(),
+                ),
+                PixelType::F32 => self.resize_group(
+                    src_image.f32_image(),
+                    group
+                        .into_iter()
+                        .filter_map(|dst| dst.f32_image())
+                        .collect(),
+                ),
+                PixelType::U8 => self.resize_group(
+                    src_image.u8_image(),
+                    group.into_iter().filter_map(|dst| dst.u8_image()).collect(),
+                ),
+                PixelType::U16 => self.resize_group(
+                    src_image.u16_image(),
+                    group
+                        .into_iter()
+                        .filter_map(|dst| dst.u16_image())
+                        .collect(),
+                ),
+                PixelType::U16x3 => self.resize_group(
+                    src_image.u16x3_image(),
+                    group
+                        .into_iter()
+                        .filter_map(|dst| dst.u16x3_image())
+                        .collect(),
+                ),
+                PixelType::U16x4 => self.resize_group(
+                    src_image.u16x4_image(),
+                    group
+                        .into_iter()
+                        .filter_map(|dst| dst.u16x4_image())
+                        .collect(),
+                ),
+                PixelType::F32x3 => self.resize_group(
+                    src_image.f32x3_image(),
+                    group
+                        .into_iter()
+                        .filter_map(|dst| dst.f32x3_image())
+                        .collect(),
+                ),
+                PixelType::F32x4 => self.resize_group(
+                    src_image.f32x4_image(),
+                    group
+                        .into_iter()
+                        .filter_map(|dst| dst.f32x4_image())
+                        .collect(),
+                ),
+            }
+        }
+        for (dst_image, source) in dst_images.iter_mut().zip(sources) {
+            if source.is_none() {
+                self.apply_float_output_mode(dst_image);
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_float_output_mode(&self, dst_image: &mut ImageViewMut) {
+        match dst_image.pixel_type() {
+            PixelType::F32 => {
+                if let Some(dst_rows) = dst_image.f32_image() {
+                    self.float_output_mode.apply(dst_rows, std::slice::from_mut);
+                }
+            }
+            PixelType::F32x3 => {
+                if let Some(dst_rows) = dst_image.f32x3_image() {
+                    self.float_output_mode.apply(dst_rows, AsMut::as_mut);
+                }
+            }
+            PixelType::F32x4 => {
+                if let Some(dst_rows) = dst_image.f32x4_image() {
+                    self.float_output_mode.apply(dst_rows, AsMut::as_mut);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Resizes source image into destination images with the same width.
+    fn resize_group<P>(
+        &mut self,
+        src_image: Option<TypedImageView<P>>,
+        dst_images: Vec<TypedImageViewMut<P>>,
+    ) where
+        P: Convolution,
+    {
+        let filter_type = match self.algorithm {
+            ResizeAlg::Convolution(filter_type) => filter_type,
+            _ => return,
+        };
+        let src_image = match src_image {
+            Some(src_image) => src_image,
+            None => return,
+        };
+        let cpu_extensions = self.cpu_extensions;
+        let convolution_buffer = &mut self.convolution_buffer;
+        let coefficients_cache = &mut self.coefficients_cache;
+        let resample = || {
+            resample_convolution_group(
+                src_image,
+                dst_images,
+                filter_type,
+                cpu_extensions,
+                convolution_buffer,
+                coefficients_cache,
+            )
+        };
+        #[cfg(feature = "rayon")]
+        if let Some(thread_pool) = self.thread_pool.clone() {
+            thread_pool.install(resample);
+            return;
+        }
+        resample();
+    }
+
     fn resize_inner<P>(&mut self, src_image: TypedImageView<P>, dst_image: TypedImageViewMut<P>)
+    where
+        P: Convolution,
+    {
+        #[cfg(feature = "rayon")]
+        if let Some(thread_pool) = self.thread_pool.clone() {
+            thread_pool.install(|| self.resample(src_image, dst_image));
+            return;
+        }
+        self.resample(src_image, dst_image);
+    }
+
+    fn resize_inner_with_alpha<P>(
+        &mut self,
+        src_image: TypedImageView<P>,
+        dst_image: TypedImageViewMut<P>,
+    ) where
+        P: Convolution + AlphaMulDiv,
+    {
+        #[cfg(feature = "rayon")]
+        if let Some(thread_pool) = self.thread_pool.clone() {
+            thread_pool.install(|| self.resample_with_alpha(src_image, dst_image));
+            return;
+        }
+        self.resample_with_alpha(src_image, dst_image);
+    }
+
+    /// Converts source image into linear light, resizes it into temporary
+    /// image and converts the result back into destination image.
+    ///
+    /// Only pixels used by resizing of the crop box of source image
+    /// are converted into linear light.
+    fn resize_in_linear_light<P, L>(
+        &mut self,
+        src_image: TypedImageView<P>,
+        dst_image: TypedImageViewMut<P>,
+        to_linear: fn(&SrgbLuts, TypedImageView<P>, TypedImageViewMut<L>),
+        from_linear: fn(&SrgbLuts, TypedImageView<L>, TypedImageViewMut<P>),
+        resize: fn(&mut Self, TypedImageView<L>, TypedImageViewMut<L>),
+    ) where
+        P: Pixel,
+        L: Convolution,
+    {
+        let luts = self
+            .srgb_luts
+            .get_or_insert_with(|| Arc::new(SrgbLuts::new()))
+            .clone();
+        let mut linear_src_buffer = std::mem::take(&mut self.linear_src_buffer);
+        let mut linear_dst_buffer = std::mem::take(&mut self.linear_dst_buffer);
+
+        let crop_box = src_image.crop_box();
+        let region = self.src_region(&src_image, dst_image.width(), dst_image.height());
+        let mut linear_src = get_temp_image_from_buffer(
+            &mut linear_src_buffer,
+            // Region contains the crop box, so its size is not zero.
+            NonZeroU32::new(region.width as u32).unwrap(),
+            NonZeroU32::new(region.height as u32).unwrap(),
+        );
+        to_linear(
+            &luts,
+            src_image.with_crop_box(region),
+            linear_src.dst_view(),
+        );
+        let crop_box = CropBox {
+            left: crop_box.left - region.left,
+            top: crop_box.top - region.top,
+            ..crop_box
+        };
+        let mut linear_dst = get_temp_image_from_buffer(
+            &mut linear_dst_buffer,
+            dst_image.width(),
+            dst_image.height(),
+        );
+        resize(
+            self,
+            linear_src.src_view().with_crop_box(crop_box),
+            linear_dst.dst_view(),
+        );
+        from_linear(&luts, linear_dst.src_view(), dst_image);
+
+        self.linear_src_buffer = linear_src_buffer;
+        self.linear_dst_buffer = linear_dst_buffer;
+    }
+
+    /// Returns the smallest region of source image with integer bounds
+    /// that contains all pixels used to resize the crop box of source image
+    /// into image with given size.
+    fn src_region<P: Pixel>(
+        &self,
+        src_image: &TypedImageView<P>,
+        dst_width: NonZeroU32,
+        dst_height: NonZeroU32,
+    ) -> CropBox {
+        let crop_box = src_image.crop_box();
+        let support = match self.algorithm {
+            ResizeAlg::Convolution(filter_type) => filter_type.support(),
+            // Super-sampling uses crop box only for nearest resizing.
+            ResizeAlg::Nearest | ResizeAlg::SuperSampling(_, _) => 0.,
+        };
+        let bounds = |start: f64, size: f64, dst_size: NonZeroU32, src_size: NonZeroU32| {
+            let radius = support * (size / dst_size.get() as f64).max(1.);
+            let start_px = (start - radius).floor().max(0.);
+            let end_px = (start + size + radius).ceil().min(src_size.get() as f64);
+            (start_px, end_px - start_px)
+        };
+        let (left, width) = bounds(crop_box.left, crop_box.width, dst_width, src_image.width());
+        let (top, height) = bounds(
+            crop_box.top,
+            crop_box.height,
+            dst_height,
+            src_image.height(),
+        );
+        CropBox {
+            left,
+            top,
+            width,
+            height,
+        }
+    }
+
+    fn resample<P>(&mut self, src_image: TypedImageView<P>, dst_image: TypedImageViewMut<P>)
     where
         P: Convolution,
     {
@@ -133,40 +747,110 @@ impl Resizer {
                     filter_type,
                     self.cpu_extensions,
                     convolution_buffer,
+                    &mut self.coefficients_cache,
                 )
             }
             ResizeAlg::SuperSampling(filter_type, multiplicity) => {
+                let cpu_extensions = self.cpu_extensions;
                 let convolution_buffer = &mut self.convolution_buffer;
-                let super_sampling_buffer = &mut self.super_sampling_buffer;
+                let coefficients_cache = &mut self.coefficients_cache;
                 resample_super_sampling(
                     src_image,
                     dst_image,
-                    filter_type,
                     multiplicity,
-                    self.cpu_extensions,
-                    super_sampling_buffer,
-                    convolution_buffer,
+                    &mut self.super_sampling_buffer,
+                    |src_image, dst_image| {
+                        resample_convolution(
+                            src_image,
+                            dst_image,
+                            filter_type,
+                            cpu_extensions,
+                            convolution_buffer,
+                            coefficients_cache,
+                        )
+                    },
+                )
+            }
+        }
+    }
+
+    /// Nearest neighbor algorithm doesn't mix pixels, so it doesn't need
+    /// multiplication by alpha.
+    fn resample_with_alpha<P>(
+        &mut self,
+        src_image: TypedImageView<P>,
+        dst_image: TypedImageViewMut<P>,
+    ) where
+        P: Convolution + AlphaMulDiv,
+    {
+        match self.algorithm {
+            ResizeAlg::Nearest => resample_nearest(src_image, dst_image),
+            ResizeAlg::Convolution(filter_type) => resample_convolution_with_alpha(
+                src_image,
+                dst_image,
+                filter_type,
+                self.cpu_extensions,
+                &mut self.convolution_buffer,
+                &mut self.alpha_buffer,
+                &mut self.coefficients_cache,
+            ),
+            ResizeAlg::SuperSampling(filter_type, multiplicity) => {
+                let cpu_extensions = self.cpu_extensions;
+                let convolution_buffer = &mut self.convolution_buffer;
+                let alpha_buffer = &mut self.alpha_buffer;
+                let coefficients_cache = &mut self.coefficients_cache;
+                resample_super_sampling(
+                    src_image,
+                    dst_image,
+                    multiplicity,
+                    &mut self.super_sampling_buffer,
+                    |src_image, dst_image| {
+                        resample_convolution_with_alpha(
+                            src_image,
+                            dst_image,
+                            filter_type,
+                            cpu_extensions,
+                            convolution_buffer,
+                            alpha_buffer,
+                            coefficients_cache,
+                        )
+                    },
                 )
             }
         }
     }
 
     /// Returns the size of internal buffers used to store the results of
-    /// intermediate resizing steps.
+    /// intermediate resizing steps and cached coefficients of convolution.
     pub fn size_of_internal_buffers(&self) -> usize {
-        (self.convolution_buffer.capacity() + self.super_sampling_buffer.capacity())
+        (self.convolution_buffer.capacity()
+            + self.super_sampling_buffer.capacity()
+            + self.linear_src_buffer.capacity()
+            + self.linear_dst_buffer.capacity()
+            + self.alpha_buffer.capacity())
             * std::mem::size_of::<u8>()
+            + self.coefficients_cache.size()
     }
 
     /// Deallocates the internal buffers used to store the results of
-    /// intermediate resizing steps.
+    /// intermediate resizing steps and cached coefficients of convolution.
     pub fn reset_internal_buffers(&mut self) {
+        self.coefficients_cache = CoefficientsCache::default();
         if self.convolution_buffer.capacity() > 0 {
             self.convolution_buffer = Vec::new();
         }
         if self.super_sampling_buffer.capacity() > 0 {
             self.super_sampling_buffer = Vec::new();
         }
+        if self.linear_src_buffer.capacity() > 0 {
+            self.linear_src_buffer = Vec::new();
+        }
+        if self.linear_dst_buffer.capacity() > 0 {
+            self.linear_dst_buffer = Vec::new();
+        }
+        if self.alpha_buffer.capacity() > 0 {
+            self.alpha_buffer = Vec::new();
+        }
     }
 
     #[inline(always)]
@@ -180,13 +864,23 @@ impl Resizer {
     pub unsafe fn set_cpu_extensions(&mut self, extensions: CpuExtensions) {
         self.cpu_extensions = extensions;
     }
+
+    /// Sets thread pool used to resize images. Global thread pool
+    /// of rayon is used by default (if `thread_pool` is `None`).
+    ///
+    /// Use a thread pool with one thread to resize images
+    /// in the current thread only.
+    #[cfg(feature = "rayon")]
+    pub fn set_thread_pool(&mut self, thread_pool: Option<Arc<rayon::ThreadPool>>) {
+        self.thread_pool = thread_pool;
+    }
 }
 
-fn get_temp_image_from_buffer<P: Pixel>(
+pub(crate) fn get_temp_image_from_buffer<P: Pixel>(
     buffer: &mut Vec<u8>,
     width: NonZeroU32,
     height: NonZeroU32,
-) -> InnerImage<P> {
+) -> InnerImage<'_, P> {
     let pixels_count = (width.get() * height.get()) as usize;
     // Add pixel size as gap for alignment of resulted buffer.
     let buf_size = pixels_count * P::size() + P::size();
@@ -203,26 +897,38 @@ where
 {
     let crop_box = src_image.crop_box();
     let dst_width = dst_image.width().get();
-    let x_scale = crop_box.width.get() as f64 / dst_width as f64;
-    let y_scale = crop_box.height.get() as f64 / dst_image.height().get() as f64;
+    let x_scale = crop_box.width / dst_width as f64;
+    let y_scale = crop_box.height / dst_image.height().get() as f64;
 
     // Pretabulate horizontal pixel positions
-    let x_in_start = crop_box.left as f64 + x_scale * 0.5;
-    let max_src_x = src_image.width().get() as usize;
+    let x_in_start = crop_box.left + x_scale * 0.5;
+    let max_src_x = src_image.width().get() as usize - 1;
     let x_in_tab: Vec<usize> = (0..dst_width)
         .map(|x| ((x_in_start + x_scale * x as f64) as usize).min(max_src_x))
         .collect();
 
-    let y_in_start = crop_box.top as f64 + y_scale * 0.5;
+    let y_in_start = crop_box.top + y_scale * 0.5;
 
-    let src_rows =
-        src_image.iter_rows_with_step(y_in_start, y_scale, dst_image.height().get() as usize);
-    let dst_rows = dst_image.iter_rows_mut();
-    for (out_row, in_row) in dst_rows.zip(src_rows) {
+    let src_rows: Vec<&[P::Type]> = src_image
+        .iter_rows_with_step(y_in_start, y_scale, dst_image.height().get() as usize)
+        .collect();
+    let resample_row = |(out_row, in_row): (&mut &mut [P::Type], &&[P::Type])| {
         for (&x_in, out_pixel) in x_in_tab.iter().zip(out_row.iter_mut()) {
             // Safety of value of x_in guaranteed by algorithm of creating of x_in_tab
             *out_pixel = unsafe { *in_row.get_unchecked(x_in) };
         }
+    };
+
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        let dst_rows = dst_image.rows_mut().par_iter_mut();
+        dst_rows.zip(src_rows.par_iter()).for_each(resample_row);
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        let dst_rows = dst_image.iter_rows_mut();
+        dst_rows.zip(src_rows.iter()).for_each(resample_row);
     }
 }
 
@@ -232,34 +938,34 @@ fn resample_convolution<P>(
     filter_type: FilterType,
     cpu_extensions: CpuExtensions,
     temp_buffer: &mut Vec<u8>,
+    coefficients_cache: &mut CoefficientsCache,
 ) where
     P: Convolution,
 {
     let crop_box = src_image.crop_box();
     let dst_width = dst_image.width();
     let dst_height = dst_image.height();
-    let (filter_fn, filter_support) = convolution::get_filter_func(filter_type);
 
-    let need_horizontal = dst_width != src_image.width() || crop_box.width != src_image.width();
-    let need_vertical = dst_height != src_image.height() || crop_box.height != src_image.height();
+    let need_horizontal =
+        dst_width != src_image.width() || crop_box.width != src_image.width().get() as f64;
+    let need_vertical =
+        dst_height != src_image.height() || crop_box.height != src_image.height().get() as f64;
 
-    let mut vert_coeffs = convolution::precompute_coefficients(
+    let vert_coeffs = coefficients_cache.coefficients::<P>(
         src_image.height(),
-        crop_box.top as f64,
-        crop_box.top as f64 + crop_box.height.get() as f64,
+        crop_box.top,
+        crop_box.top + crop_box.height,
         dst_height,
-        filter_fn,
-        filter_support,
+        filter_type,
     );
 
     if need_horizontal {
-        let horiz_coeffs = convolution::precompute_coefficients(
+        let horiz_coeffs = coefficients_cache.coefficients::<P>(
             src_image.width(),
-            crop_box.left as f64,
-            crop_box.left as f64 + crop_box.width.get() as f64,
+            crop_box.left,
+            crop_box.left + crop_box.width,
             dst_width,
-            filter_fn,
-            filter_support,
+            filter_type,
         );
 
         // First used row in the source image
@@ -272,49 +978,378 @@ fn resample_convolution<P>(
 
             let temp_height = NonZeroU32::new(y_last - y_first).unwrap();
             let mut temp_image = get_temp_image_from_buffer(temp_buffer, dst_width, temp_height);
-            P::horiz_convolution(
+            threading::horiz_convolution(
                 src_image,
                 temp_image.dst_view(),
                 y_first,
-                horiz_coeffs,
+                horiz_coeffs.view(),
                 cpu_extensions,
             );
 
-            // Shift bounds for vertical pass
-            vert_coeffs
-                .bounds
-                .iter_mut()
-                .for_each(|b| b.start -= y_first);
-            P::vert_convolution(
+            // Temporary image starts with `y_first` row of the source image
+            threading::vert_convolution(
                 temp_image.src_view(),
                 dst_image,
-                vert_coeffs,
+                vert_coeffs.view().with_offset(y_first),
                 cpu_extensions,
             );
         } else {
-            P::horiz_convolution(src_image, dst_image, y_first, horiz_coeffs, cpu_extensions);
+            threading::horiz_convolution(
+                src_image,
+                dst_image,
+                y_first,
+                horiz_coeffs.view(),
+                cpu_extensions,
+            );
         }
     } else if need_vertical {
-        P::vert_convolution(src_image, dst_image, vert_coeffs, cpu_extensions);
+        threading::vert_convolution(src_image, dst_image, vert_coeffs.view(), cpu_extensions);
     }
 }
 
-fn resample_super_sampling<P>(
+/// Returns index of the destination image used as source image for every
+/// destination image given by its size, or `None` if destination image
+/// is resized from the source image. The smallest destination image which
+/// is at least two times bigger in both dimensions is used as source image.
+fn cascade_sources(sizes: &[(u32, u32)]) -> Vec<Option<usize>> {
+    sizes
+        .iter()
+        .map(|&(width, height)| {
+            sizes
+                .iter()
+                .enumerate()
+                .filter(|(_, &(w, h))| w / 2 >= width && h / 2 >= height)
+                .min_by_key(|(_, &(w, h))| w as u64 * h as u64)
+                .map(|(i, _)| i)
+        })
+        .collect()
+}
+
+/// Count of rows processed at once by convolution with multiplication
+/// by alpha. Premultiplied rows of source image and rows of destination
+/// image stay in CPU cache until they are used.
+const ALPHA_BAND_HEIGHT: usize = 32;
+
+/// Same as `resample_convolution`, but color-channels of source image are
+/// multiplied by alpha right before the horizontal pass and color-channels
+/// of destination image are divided by alpha right after the vertical pass.
+/// Both passes are performed by bands of rows, so neither premultiplied
+/// copy of the whole source image nor horizontally resized copy of all its
+/// rows is created.
+fn resample_convolution_with_alpha<P>(
     src_image: TypedImageView<P>,
+    mut dst_image: TypedImageViewMut<P>,
+    filter_type: FilterType,
+    cpu_extensions: CpuExtensions,
+    temp_buffer: &mut Vec<u8>,
+    alpha_buffer: &mut Vec<u8>,
+    coefficients_cache: &mut CoefficientsCache,
+) where
+    P: Convolution + AlphaMulDiv,
+{
+    let crop_box = src_image.crop_box();
+    let dst_width = dst_image.width();
+    let dst_height = dst_image.height();
+
+    let need_horizontal =
+        dst_width != src_image.width() || crop_box.width != src_image.width().get() as f64;
+    let need_vertical =
+        dst_height != src_image.height() || crop_box.height != src_image.height().get() as f64;
+    if !need_horizontal && !need_vertical {
+        return;
+    }
+
+    let horiz_coeffs = if need_horizontal {
+        let coeffs = coefficients_cache.coefficients::<P>(
+            src_image.width(),
+            crop_box.left,
+            crop_box.left + crop_box.width,
+            dst_width,
+            filter_type,
+        );
+        // Only used columns of the source image are multiplied by alpha.
+        let x_first = coeffs.bounds[0].start;
+        let last_x_bound = coeffs.bounds.last().unwrap();
+        let x_last = last_x_bound.start + last_x_bound.size;
+        Some((coeffs, x_first as usize..x_last as usize))
+    } else {
+        None
+    };
+    let src_rows = src_image.rows();
+    // Image with premultiplied columns used by the horizontal pass
+    // for one band of source rows.
+    let mut premultiplied = horiz_coeffs.as_ref().map(|(_, columns)| {
+        let width = NonZeroU32::new(columns.len() as u32).unwrap();
+        let height = ALPHA_BAND_HEIGHT.min(src_rows.len()) as u32;
+        get_temp_image_from_buffer::<P>(alpha_buffer, width, NonZeroU32::new(height).unwrap())
+    });
+
+    if need_vertical {
+        let vert_coeffs = coefficients_cache.coefficients::<P>(
+            src_image.height(),
+            crop_box.top,
+            crop_box.top + crop_box.height,
+            dst_height,
+            filter_type,
+        );
+        // Rows of the source image used by the band of destination rows.
+        let used_rows = |rows: Range<usize>| {
+            let first_bound = vert_coeffs.bounds[rows.start];
+            let last_bound = vert_coeffs.bounds[rows.end - 1];
+            first_bound.start..last_bound.start + last_bound.size
+        };
+        let dst_rows_count = dst_height.get() as usize;
+        let temp_height = (0..dst_rows_count)
+            .step_by(ALPHA_BAND_HEIGHT)
+            .map(|y| used_rows(y..(y + ALPHA_BAND_HEIGHT).min(dst_rows_count)).len())
+            .max()
+            .unwrap();
+        // Temporary image contains only rows used by the current band
+        // of destination rows. Bounds of coefficients don't decrease, so
+        // rows used by the previous band are either used by the current
+        // one too or never used again.
+        let temp_height = NonZeroU32::new(temp_height as u32).unwrap();
+        let mut temp_image = get_temp_image_from_buffer::<P>(temp_buffer, dst_width, temp_height);
+        let vert_coeffs = vert_coeffs.view();
+        let mut temp_rows = 0..0;
+
+        let dst_bands = dst_image.rows_mut().chunks_mut(ALPHA_BAND_HEIGHT);
+        for (i, dst_band) in dst_bands.enumerate() {
+            let rows = i * ALPHA_BAND_HEIGHT..i * ALPHA_BAND_HEIGHT + dst_band.len();
+            let band_rows = used_rows(rows.clone());
+
+            let mut temp_view = temp_image.dst_view();
+            let temp_image_rows = temp_view.rows_mut();
+            // Move rows calculated for the previous band to the top.
+            let kept_rows =
+                band_rows.start.max(temp_rows.start)..temp_rows.end.max(band_rows.start);
+            let shift = (kept_rows.start - temp_rows.start) as usize;
+            if shift > 0 {
+                for y in 0..kept_rows.len() {
+                    let (top, bottom) = temp_image_rows.split_at_mut(y + shift);
+                    top[y].copy_from_slice(bottom[0]);
+                }
+            }
+
+            let new_rows = kept_rows.end as usize..band_rows.end as usize;
+            let src_bands = src_rows[new_rows].chunks(ALPHA_BAND_HEIGHT);
+            let temp_bands =
+                temp_image_rows[kept_rows.len()..band_rows.len()].chunks_mut(ALPHA_BAND_HEIGHT);
+            for (src_band, temp_band) in src_bands.zip(temp_bands) {
+                let height = NonZeroU32::new(temp_band.len() as u32).unwrap();
+                premultiplied_horiz_convolution::<P>(
+                    src_band,
+                    TypedImageViewMut::new(dst_width, height, temp_band),
+                    horiz_coeffs
+                        .as_ref()
+                        .map(|(c, columns)| (c.view(), columns.clone())),
+                    premultiplied.as_mut(),
+                    cpu_extensions,
+                );
+            }
+            temp_rows = band_rows;
+
+            let height = NonZeroU32::new(dst_band.len() as u32).unwrap();
+            // Temporary image starts with `temp_rows.start` row of the source image
+            threading::vert_convolution(
+                temp_image.src_view(),
+                TypedImageViewMut::new(dst_width, height, dst_band),
+                vert_coeffs.slice(rows).with_offset(temp_rows.start),
+                cpu_extensions,
+            );
+            P::divide_alpha_inplace(
+                TypedImageViewMut::new(dst_width, height, dst_band),
+                cpu_extensions,
+            );
+        }
+    } else {
+        // Height of image is not changed, so all rows of the source
+        // image are used.
+        let src_bands = src_rows.chunks(ALPHA_BAND_HEIGHT);
+        let dst_bands = dst_image.rows_mut().chunks_mut(ALPHA_BAND_HEIGHT);
+        for (src_band, dst_band) in src_bands.zip(dst_bands) {
+            let height = NonZeroU32::new(dst_band.len() as u32).unwrap();
+            premultiplied_horiz_convolution::<P>(
+                src_band,
+                TypedImageViewMut::new(dst_width, height, dst_band),
+                horiz_coeffs
+                    .as_ref()
+                    .map(|(c, columns)| (c.view(), columns.clone())),
+                premultiplied.as_mut(),
+                cpu_extensions,
+            );
+            P::divide_alpha_inplace(
+                TypedImageViewMut::new(dst_width, height, dst_band),
+                cpu_extensions,
+            );
+        }
+    }
+}
+
+/// Multiplies the band of rows of source image by alpha and resizes it
+/// horizontally into the band of destination image. Without coefficients
+/// of the horizontal pass the rows are only multiplied by alpha.
+///
+/// Used columns of the source rows are multiplied by alpha inside of
+/// `premultiplied` image which has enough rows for the whole band.
+fn premultiplied_horiz_convolution<P>(
+    src_rows: &[&[P::Type]],
     dst_image: TypedImageViewMut<P>,
+    horiz_coeffs: Option<(CoefficientsView<P::Coefficient>, Range<usize>)>,
+    premultiplied: Option<&mut InnerImage<P>>,
+    cpu_extensions: CpuExtensions,
+) where
+    P: Convolution + AlphaMulDiv,
+{
+    let height = dst_image.height();
+    match (horiz_coeffs, premultiplied) {
+        (Some((coeffs, columns)), Some(premultiplied)) => {
+            let mut premultiplied_view = premultiplied.top_dst_view(height);
+            for (src_row, row) in src_rows.iter().zip(premultiplied_view.iter_rows_mut()) {
+                row.copy_from_slice(&src_row[columns.clone()]);
+            }
+            P::multiply_alpha_inplace(premultiplied_view, cpu_extensions);
+            threading::horiz_convolution(
+                premultiplied.top_src_view(height),
+                dst_image,
+                0,
+                // Premultiplied image starts with `columns.start` column
+                // of the source image
+                coeffs.with_offset(columns.start as u32),
+                cpu_extensions,
+            );
+        }
+        _ => {
+            let src_image = TypedImageView::new(dst_image.width(), height, src_rows);
+            P::multiply_alpha(src_image, dst_image, cpu_extensions);
+        }
+    }
+}
+
+/// Resizes source image into destination images with the same width.
+/// Horizontal pass is performed once for all rows of source image
+/// used by destination images.
+fn resample_convolution_group<P>(
+    src_image: TypedImageView<P>,
+    dst_images: Vec<TypedImageViewMut<P>>,
     filter_type: FilterType,
-    multiplicity: u8,
     cpu_extensions: CpuExtensions,
     temp_buffer: &mut Vec<u8>,
-    convolution_temp_buffer: &mut Vec<u8>,
+    coefficients_cache: &mut CoefficientsCache,
 ) where
     P: Convolution,
+{
+    let crop_box = src_image.crop_box();
+    let dst_width = match dst_images.first() {
+        Some(dst_image) => dst_image.width(),
+        None => return,
+    };
+    let need_horizontal =
+        dst_width != src_image.width() || crop_box.width != src_image.width().get() as f64;
+    if !need_horizontal || dst_images.len() == 1 {
+        for dst_image in dst_images {
+            resample_convolution(
+                src_image.clone(),
+                dst_image,
+                filter_type,
+                cpu_extensions,
+                temp_buffer,
+                coefficients_cache,
+            );
+        }
+        return;
+    }
+
+    let vert_coeffs: Vec<Option<Arc<Coefficients<P::Coefficient>>>> = dst_images
+        .iter()
+        .map(|dst_image| {
+            let need_vertical = dst_image.height() != src_image.height()
+                || crop_box.height != src_image.height().get() as f64;
+            if need_vertical {
+                Some(coefficients_cache.coefficients::<P>(
+                    src_image.height(),
+                    crop_box.top,
+                    crop_box.top + crop_box.height,
+                    dst_image.height(),
+                    filter_type,
+                ))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    // Range of rows of the source image used by all destination images.
+    let (y_first, y_last) = vert_coeffs
+        .iter()
+        .map(|coeffs| match coeffs {
+            Some(coeffs) => {
+                let last_bound = coeffs.bounds.last().unwrap();
+                (coeffs.bounds[0].start, last_bound.start + last_bound.size)
+            }
+            None => (0, src_image.height().get()),
+        })
+        .fold((u32::MAX, 0), |(first, last), (start, end)| {
+            (first.min(start), last.max(end))
+        });
+
+    let horiz_coeffs = coefficients_cache.coefficients::<P>(
+        src_image.width(),
+        crop_box.left,
+        crop_box.left + crop_box.width,
+        dst_width,
+        filter_type,
+    );
+    let temp_height = NonZeroU32::new(y_last - y_first).unwrap();
+    let mut temp_image = get_temp_image_from_buffer(temp_buffer, dst_width, temp_height);
+    threading::horiz_convolution(
+        src_image,
+        temp_image.dst_view(),
+        y_first,
+        horiz_coeffs.view(),
+        cpu_extensions,
+    );
+    let temp_view = temp_image.src_view();
+
+    for (mut dst_image, coeffs) in dst_images.into_iter().zip(vert_coeffs) {
+        match coeffs {
+            Some(coeffs) => {
+                // Temporary image starts with `y_first` row of the source image
+                threading::vert_convolution(
+                    temp_view.clone(),
+                    dst_image,
+                    coeffs.view().with_offset(y_first),
+                    cpu_extensions,
+                );
+            }
+            None => {
+                // Height of image is not changed, so `y_first` is zero.
+                for (y, dst_row) in dst_image.iter_rows_mut().enumerate() {
+                    dst_row.copy_from_slice(temp_view.get_row(y as u32).unwrap());
+                }
+            }
+        }
+    }
+}
+
+/// Resizes source image by nearest neighbor algorithm into temporary
+/// image about `multiplicity` times larger than destination image and
+/// resizes temporary image into destination image by `resample_convolution`.
+fn resample_super_sampling<P, F>(
+    src_image: TypedImageView<P>,
+    dst_image: TypedImageViewMut<P>,
+    multiplicity: u8,
+    temp_buffer: &mut Vec<u8>,
+    resample_convolution: F,
+) where
+    P: Pixel,
+    F: FnOnce(TypedImageView<P>, TypedImageViewMut<P>),
 {
     let crop_box = src_image.crop_box();
     let dst_width = dst_image.width().get();
     let dst_height = dst_image.height().get();
-    let width_scale = crop_box.width.get() as f32 / dst_width as f32;
-    let height_scale = crop_box.height.get() as f32 / dst_height as f32;
+    let width_scale = crop_box.width as f32 / dst_width as f32;
+    let height_scale = crop_box.height as f32 / dst_height as f32;
     // It makes sense to resize the image in two steps only if the image
     // size is greater than the required size by multiplicity times.
     let factor = width_scale.min(height_scale) / multiplicity as f32;
@@ -322,30 +1357,16 @@ fn resample_super_sampling<P>(
         // First step is resizing the source image by fastest algorithm.
         // The temporary image will be about ``multiplicity`` times larger
         // than required.
-        let tmp_width =
-            NonZeroU32::new((crop_box.width.get() as f32 / factor).round() as u32).unwrap();
-        let tmp_height =
-            NonZeroU32::new((crop_box.height.get() as f32 / factor).round() as u32).unwrap();
+        let tmp_width = NonZeroU32::new((crop_box.width as f32 / factor).round() as u32).unwrap();
+        let tmp_height = NonZeroU32::new((crop_box.height as f32 / factor).round() as u32).unwrap();
 
         let mut tmp_img = get_temp_image_from_buffer(temp_buffer, tmp_width, tmp_height);
         resample_nearest(src_image, tmp_img.dst_view());
         // Second step is resizing the temporary image with a convolution.
-        resample_convolution(
-            tmp_img.src_view(),
-            dst_image,
-            filter_type,
-            cpu_extensions,
-            convolution_temp_buffer,
-        );
+        resample_convolution(tmp_img.src_view(), dst_image);
     } else {
         // There is no point in doing the resizing in two steps.
         // We immediately resize the original image with a convolution.
-        resample_convolution(
-            src_image,
-            dst_image,
-            filter_type,
-            cpu_extensions,
-            convolution_temp_buffer,
-        );
+        resample_convolution(src_image, dst_image);
     }
 }
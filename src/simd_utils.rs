@@ -1,5 +1,4 @@
 use std::arch::x86_64::*;
-use std::intrinsics::transmute;
 
 #[inline(always)]
 pub unsafe fn loadu_si128<T>(buf: &[T], index: usize) -> __m128i {
@@ -18,22 +17,81 @@ pub unsafe fn loadl_epi64<T>(buf: &[T], index: usize) -> __m128i {
 
 #[inline(always)]
 pub unsafe fn mm_cvtepu8_epi32(buf: &[u32], index: usize) -> __m128i {
-    let v: i32 = transmute(*buf.get_unchecked(index));
+    let v = *buf.get_unchecked(index) as i32;
     _mm_cvtepu8_epi32(_mm_cvtsi32_si128(v))
 }
 
 #[inline(always)]
 pub unsafe fn mm_cvtsi32_si128(buf: &[u32], index: usize) -> __m128i {
-    let v: i32 = transmute(*buf.get_unchecked(index));
+    let v = *buf.get_unchecked(index) as i32;
     _mm_cvtsi32_si128(v)
 }
 
 #[inline(always)]
 pub unsafe fn ptr_i16_to_set1_epi32(buf: &[i16], index: usize) -> __m128i {
-    _mm_set1_epi32(*(buf.get_unchecked(index..).as_ptr() as *const i32))
+    _mm_set1_epi32((buf.get_unchecked(index..).as_ptr() as *const i32).read_unaligned())
 }
 
 #[inline(always)]
 pub unsafe fn ptr_i16_to_256set1_epi32(buf: &[i16], index: usize) -> __m256i {
-    _mm256_set1_epi32(*(buf.get_unchecked(index..).as_ptr() as *const i32))
+    _mm256_set1_epi32((buf.get_unchecked(index..).as_ptr() as *const i32).read_unaligned())
+}
+
+#[cfg(feature = "avx512")]
+#[inline]
+#[target_feature(enable = "avx512f")]
+pub unsafe fn ptr_i16_to_512set1_epi32(buf: &[i16], index: usize) -> __m512i {
+    _mm512_set1_epi32((buf.get_unchecked(index..).as_ptr() as *const i32).read_unaligned())
+}
+
+/// Loads two three-byte pixels into the lower 6 bytes of the result.
+/// Unlike `loadl_epi64` it never reads beyond the second pixel.
+#[inline(always)]
+pub unsafe fn loadl_two_u8x3(buf: &[[u8; 3]], index: usize) -> __m128i {
+    let p0 = *buf.get_unchecked(index);
+    let p1 = *buf.get_unchecked(index + 1);
+    let v = i64::from_le_bytes([p0[0], p0[1], p0[2], p1[0], p1[1], p1[2], 0, 0]);
+    _mm_cvtsi64_si128(v)
+}
+
+#[inline(always)]
+pub unsafe fn mm_cvtepu8_epi32_u8x3(buf: &[[u8; 3]], index: usize) -> __m128i {
+    let p = *buf.get_unchecked(index);
+    let v = i32::from_le_bytes([p[0], p[1], p[2], 0]);
+    _mm_cvtepu8_epi32(_mm_cvtsi32_si128(v))
+}
+
+#[inline(always)]
+pub unsafe fn load_two_u8x2(buf: &[[u8; 2]], index: usize) -> __m128i {
+    let p0 = *buf.get_unchecked(index);
+    let p1 = *buf.get_unchecked(index + 1);
+    let v = i32::from_le_bytes([p0[0], p0[1], p1[0], p1[1]]);
+    _mm_cvtsi32_si128(v)
+}
+
+#[inline(always)]
+pub unsafe fn mm_cvtepu8_epi32_u8x2(buf: &[[u8; 2]], index: usize) -> __m128i {
+    let p = *buf.get_unchecked(index);
+    let v = i32::from_le_bytes([p[0], p[1], 0, 0]);
+    _mm_cvtepu8_epi32(_mm_cvtsi32_si128(v))
+}
+
+/// Loads four bytes into the lower 32 bits of the result.
+#[inline(always)]
+pub unsafe fn load_four_u8(buf: &[u8], index: usize) -> __m128i {
+    _mm_cvtsi32_si128((buf.get_unchecked(index..).as_ptr() as *const i32).read_unaligned())
+}
+
+/// Returns sum of both `f64` values of the register.
+#[inline(always)]
+pub unsafe fn hsum_pd(v: __m128d) -> f64 {
+    _mm_cvtsd_f64(_mm_add_sd(v, _mm_unpackhi_pd(v, v)))
+}
+
+/// Returns sum of all four `f64` values of the register.
+#[inline]
+#[target_feature(enable = "avx")]
+pub unsafe fn hsum_256_pd(v: __m256d) -> f64 {
+    let sum = _mm_add_pd(_mm256_castpd256_pd128(v), _mm256_extractf128_pd::<1>(v));
+    hsum_pd(sum)
 }
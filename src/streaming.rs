@@ -0,0 +1,321 @@
+use std::num::NonZeroU32;
+use std::sync::Arc;
+
+use crate::convolution::{
+    self, AnyCoefficients, CoefficientValue, Coefficients, Convolution, FilterType,
+};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::pixels::{F32x3, F32x4, U16x3, U16x4, U8x2, U8x3, U8x4, F32, I32, U16, U8};
+use crate::resizer::get_temp_image_from_buffer;
+use crate::{CpuExtensions, ImageView, PixelType, StreamingResizeError};
+
+/// Resizer of images which rows are received incrementally
+/// (e.g. from decoder of a big image).
+///
+/// Source rows are resized horizontally as soon as they are pushed
+/// and are kept in a ring buffer only until all destination rows that
+/// depend on them are calculated. So memory used by the resizer depends
+/// on the width of destination image and the size of vertical window
+/// of the filter, but not on the height of images.
+///
+/// Destination rows are passed into the callback in order from top to bottom.
+/// Results of resizing are equal to results of [Resizer](crate::Resizer)
+/// with the same filter.
+///
+/// # Examples
+///
+/// ```
+/// use std::num::NonZeroU32;
+/// use fast_image_resize::{FilterType, ImageView, PixelType, StreamingResizer};
+///
+/// let width = NonZeroU32::new(40).unwrap();
+/// let height = NonZeroU32::new(30).unwrap();
+/// let mut resizer = StreamingResizer::new(
+///     width,
+///     height,
+///     NonZeroU32::new(20).unwrap(),
+///     NonZeroU32::new(15).unwrap(),
+///     PixelType::U8,
+///     FilterType::Lanczos3,
+/// );
+///
+/// let src_row = vec![128u8; 40];
+/// let mut dst_rows = Vec::new();
+/// for _ in 0..height.get() {
+///     let row = ImageView::from_buffer(width, NonZeroU32::new(1).unwrap(), &src_row, PixelType::U8)
+///         .unwrap();
+///     resizer
+///         .push_rows(&row, |_y, dst_row| dst_rows.push(dst_row.to_vec()))
+///         .unwrap();
+/// }
+/// assert!(resizer.is_finished());
+/// assert_eq!(dst_rows.len(), 15);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StreamingResizer {
+    src_width: NonZeroU32,
+    src_height: NonZeroU32,
+    dst_width: NonZeroU32,
+    dst_height: NonZeroU32,
+    pixel_type: PixelType,
+    cpu_extensions: CpuExtensions,
+    /// `None` if width of image is not changed.
+    horiz_coeffs: Option<AnyCoefficients>,
+    /// `None` if height of image is not changed.
+    vert_coeffs: Option<AnyCoefficients>,
+    /// Horizontally resized source row `y` is stored in the rows
+    /// `y % ring_height` and `y % ring_height + ring_height` of ring buffer,
+    /// so rows of any vertical window are adjacent in the buffer.
+    ring_height: NonZeroU32,
+    ring_buffer: Vec<u8>,
+    dst_row_buffer: Vec<u8>,
+    next_src_row: u32,
+    next_dst_row: u32,
+}
+
+impl StreamingResizer {
+    /// Creates resizer of image with size `src_width` x `src_height`
+    /// into image with size `dst_width` x `dst_height`.
+    ///
+    /// By default, instance of `StreamingResizer` created with best CPU-extensions
+    /// provided by your CPU. You can change this by use method
+    /// [StreamingResizer::set_cpu_extensions].
+    pub fn new(
+        src_width: NonZeroU32,
+        src_height: NonZeroU32,
+        dst_width: NonZeroU32,
+        dst_height: NonZeroU32,
+        pixel_type: PixelType,
+        filter_type: FilterType,
+    ) -> Self {
+        let (filter_fn, filter_support) = convolution::get_filter_func(filter_type);
+        let precompute = |in_size: NonZeroU32, out_size: NonZeroU32| {
+            convolution::precompute_coefficients(
+                in_size,
+                0.,
+                in_size.get() as f64,
+                out_size,
+                filter_fn,
+                filter_support,
+            )
+        };
+        let horiz_coeffs = if src_width != dst_width {
+            Some(precompute(src_width, dst_width))
+        } else {
+            None
+        };
+        let vert_coeffs = if src_height != dst_height {
+            Some(precompute(src_height, dst_height))
+        } else {
+            None
+        };
+        let ring_height = vert_coeffs
+            .as_ref()
+            .and_then(|coeffs| coeffs.bounds.iter().map(|bound| bound.size).max())
+            .and_then(NonZeroU32::new)
+            .unwrap_or_else(|| NonZeroU32::new(1).unwrap());
+
+        // Coefficients are converted into values used by convolution
+        // of given type of pixels only once.
+        let convert = match pixel_type {
+            PixelType::U8x2 => convert_coefficients::<U8x2>,
+            PixelType::U8x3 => convert_coefficients::<U8x3>,
+            PixelType::U8x4 => convert_coefficients::<U8x4>,
+            PixelType::I32 => convert_coefficients::<I32>,
+            PixelType::F32 => convert_coefficients::<F32>,
+            PixelType::U8 => convert_coefficients::<U8>,
+            PixelType::U16 => convert_coefficients::<U16>,
+            PixelType::U16x3 => convert_coefficients::<U16x3>,
+            PixelType::U16x4 => convert_coefficients::<U16x4>,
+            PixelType::F32x3 => convert_coefficients::<F32x3>,
+            PixelType::F32x4 => convert_coefficients::<F32x4>,
+        };
+        let horiz_coeffs = horiz_coeffs.map(convert);
+        let vert_coeffs = vert_coeffs.map(convert);
+
+        Self {
+            src_width,
+            src_height,
+            dst_width,
+            dst_height,
+            pixel_type,
+            cpu_extensions: Default::default(),
+            horiz_coeffs,
+            vert_coeffs,
+            ring_height,
+            ring_buffer: Vec::new(),
+            dst_row_buffer: Vec::new(),
+            next_src_row: 0,
+            next_dst_row: 0,
+        }
+    }
+
+    #[inline(always)]
+    pub fn cpu_extensions(&self) -> CpuExtensions {
+        self.cpu_extensions
+    }
+
+    /// # Safety
+    /// This is unsafe because this method allows you to set a CPU-extensions
+    /// that is not actually supported by your CPU.
+    pub unsafe fn set_cpu_extensions(&mut self, extensions: CpuExtensions) {
+        self.cpu_extensions = extensions;
+    }
+
+    /// Returns count of source rows pushed into resizer.
+    #[inline(always)]
+    pub fn pushed_rows(&self) -> u32 {
+        self.next_src_row
+    }
+
+    /// Returns count of destination rows passed into callbacks.
+    #[inline(always)]
+    pub fn finished_rows(&self) -> u32 {
+        self.next_dst_row
+    }
+
+    /// Returns `true` if all rows of destination image are calculated.
+    #[inline(always)]
+    pub fn is_finished(&self) -> bool {
+        self.next_dst_row >= self.dst_height.get()
+    }
+
+    /// Pushes next rows of source image into resizer. Crop box
+    /// of `src_rows` is ignored.
+    ///
+    /// `callback` is called for every destination row that can be calculated
+    /// with help of rows pushed so far. It receives index of destination row
+    /// and its pixels as bytes.
+    pub fn push_rows<F>(
+        &mut self,
+        src_rows: &ImageView,
+        mut callback: F,
+    ) -> Result<(), StreamingResizeError>
+    where
+        F: FnMut(u32, &[u8]),
+    {
+        if src_rows.pixel_type() != self.pixel_type {
+            return Err(StreamingResizeError::DifferentTypesOfPixels);
+        }
+        if src_rows.width() != self.src_width {
+            return Err(StreamingResizeError::InvalidRowsWidth);
+        }
+        if src_rows.height().get() > self.src_height.get() - self.next_src_row {
+            return Err(StreamingResizeError::TooManyRows);
+        }
+        let callback = &mut callback;
+        match self.pixel_type {
+            PixelType::U8x2 => self.push_typed(src_rows.u8x2_image(), callback),
+            PixelType::U8x3 => self.push_typed(src_rows.u8x3_image(), callback),
+            PixelType::U8x4 => self.push_typed(src_rows.u32_image(), callback),
+            PixelType::I32 => self.push_typed(src_rows.i32_image(), callback),
+            PixelType::F32 => self.push_typed(src_rows.f32_image(), callback),
+            PixelType::U8 => self.push_typed(src_rows.u8_image(), callback),
+            PixelType::U16 => self.push_typed(src_rows.u16_image(), callback),
+            PixelType::U16x3 => self.push_typed(src_rows.u16x3_image(), callback),
+            PixelType::U16x4 => self.push_typed(src_rows.u16x4_image(), callback),
+            PixelType::F32x3 => self.push_typed(src_rows.f32x3_image(), callback),
+            PixelType::F32x4 => self.push_typed(src_rows.f32x4_image(), callback),
+        }
+        Ok(())
+    }
+
+    fn push_typed<P, F>(&mut self, src_rows: Option<TypedImageView<P>>, callback: &mut F)
+    where
+        P: Convolution,
+        F: FnMut(u32, &[u8]),
+    {
+        let src_rows = match src_rows {
+            Some(src_rows) => src_rows,
+            None => return,
+        };
+        let dst_width = self.dst_width;
+        let ring_height = self.ring_height.get();
+        let one_row = NonZeroU32::new(1).unwrap();
+        // Buffers are allocated once and reused by all rows.
+        let mut ring = get_temp_image_from_buffer::<P>(
+            &mut self.ring_buffer,
+            dst_width,
+            NonZeroU32::new(ring_height * 2).unwrap(),
+        );
+        let mut dst_row =
+            get_temp_image_from_buffer::<P>(&mut self.dst_row_buffer, dst_width, one_row);
+        // Type of pixels is checked by `push_rows()`.
+        let horiz_coeffs = self.horiz_coeffs.as_ref().map(typed_coefficients::<P>);
+        let vert_coeffs = self.vert_coeffs.as_ref().map(typed_coefficients::<P>);
+
+        for y in 0..src_rows.height().get() {
+            let src_y = self.next_src_row;
+            self.next_src_row += 1;
+
+            if let Some(vert_coeffs) = vert_coeffs {
+                match vert_coeffs.bounds.get(self.next_dst_row as usize) {
+                    // The row is not used by remaining destination rows.
+                    Some(bound) if src_y >= bound.start => (),
+                    _ => continue,
+                }
+            }
+
+            let slot = (src_y % ring_height) as usize;
+            let mut ring_view = ring.dst_view();
+            let ring_rows = ring_view.rows_mut();
+            match horiz_coeffs {
+                Some(horiz_coeffs) => P::horiz_convolution(
+                    src_rows.clone(),
+                    TypedImageViewMut::new(dst_width, one_row, &mut ring_rows[slot..slot + 1]),
+                    y,
+                    horiz_coeffs.view(),
+                    self.cpu_extensions,
+                ),
+                None => ring_rows[slot].copy_from_slice(src_rows.get_row(y).unwrap()),
+            }
+
+            let vert_coeffs = match vert_coeffs {
+                Some(vert_coeffs) => vert_coeffs,
+                None => {
+                    callback(src_y, as_bytes(ring_rows[slot]));
+                    self.next_dst_row += 1;
+                    continue;
+                }
+            };
+            let (ring_top, ring_bottom) = ring_rows.split_at_mut(ring_height as usize);
+            ring_bottom[slot].copy_from_slice(ring_top[slot]);
+
+            // Calculate destination rows which vertical windows are complete.
+            while let Some(&bound) = vert_coeffs.bounds.get(self.next_dst_row as usize) {
+                if bound.start + bound.size > self.next_src_row {
+                    break;
+                }
+                let window = ring.band_src_view(
+                    (bound.start % ring_height) as usize,
+                    NonZeroU32::new(bound.size).unwrap(),
+                );
+                // Window starts with `bound.start` row of the source image
+                let dst_y = self.next_dst_row as usize;
+                let coeffs = vert_coeffs
+                    .view()
+                    .slice(dst_y..dst_y + 1)
+                    .with_offset(bound.start);
+                P::vert_convolution(window, dst_row.dst_view(), coeffs, self.cpu_extensions);
+                callback(
+                    self.next_dst_row,
+                    as_bytes(dst_row.src_view().get_row(0).unwrap()),
+                );
+                self.next_dst_row += 1;
+            }
+        }
+    }
+}
+
+fn convert_coefficients<P: Convolution>(coeffs: Coefficients) -> AnyCoefficients {
+    P::Coefficient::into_any(Arc::new(P::Coefficient::from_f64(coeffs)))
+}
+
+fn typed_coefficients<P: Convolution>(coeffs: &AnyCoefficients) -> &Coefficients<P::Coefficient> {
+    P::Coefficient::from_any(coeffs).expect("coefficients of another type of pixels")
+}
+
+fn as_bytes<T>(pixels: &[T]) -> &[u8] {
+    // Safety: all types of pixels are plain numbers or arrays of numbers.
+    unsafe { pixels.align_to::<u8>().1 }
+}
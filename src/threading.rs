@@ -0,0 +1,107 @@
+//! Splitting of convolution passes into bands processed in parallel
+//! by threads of [rayon] thread pool (if feature `rayon` is enabled).
+use crate::convolution::{CoefficientsView, Convolution};
+use crate::image_view::{TypedImageView, TypedImageViewMut};
+use crate::CpuExtensions;
+
+#[cfg(not(feature = "rayon"))]
+#[inline(always)]
+pub(crate) fn horiz_convolution<P: Convolution>(
+    src_image: TypedImageView<P>,
+    dst_image: TypedImageViewMut<P>,
+    offset: u32,
+    coeffs: CoefficientsView<P::Coefficient>,
+    cpu_extensions: CpuExtensions,
+) {
+    P::horiz_convolution(src_image, dst_image, offset, coeffs, cpu_extensions);
+}
+
+#[cfg(not(feature = "rayon"))]
+#[inline(always)]
+pub(crate) fn vert_convolution<P: Convolution>(
+    src_image: TypedImageView<P>,
+    dst_image: TypedImageViewMut<P>,
+    coeffs: CoefficientsView<P::Coefficient>,
+    cpu_extensions: CpuExtensions,
+) {
+    P::vert_convolution(src_image, dst_image, coeffs, cpu_extensions);
+}
+
+/// Destination image is split into bands of rows. Every band uses
+/// the same coefficients as the whole image, so result is equal to
+/// result of single-threaded convolution.
+#[cfg(feature = "rayon")]
+pub(crate) fn horiz_convolution<P: Convolution>(
+    src_image: TypedImageView<P>,
+    mut dst_image: TypedImageViewMut<P>,
+    offset: u32,
+    coeffs: CoefficientsView<P::Coefficient>,
+    cpu_extensions: CpuExtensions,
+) {
+    use rayon::prelude::*;
+    use std::num::NonZeroU32;
+
+    let dst_width = dst_image.width();
+    // SIMD-versions of convolution process rows in groups of four.
+    let band_height = band_size(dst_image.height().get() as usize, 4);
+    if band_height >= dst_image.height().get() as usize {
+        P::horiz_convolution(src_image, dst_image, offset, coeffs, cpu_extensions);
+        return;
+    }
+
+    dst_image
+        .rows_mut()
+        .par_chunks_mut(band_height)
+        .enumerate()
+        .for_each(|(i, rows)| {
+            let band_offset = offset + (i * band_height) as u32;
+            let height = NonZeroU32::new(rows.len() as u32).unwrap();
+            let band = TypedImageViewMut::new(dst_width, height, rows);
+            P::horiz_convolution(src_image.clone(), band, band_offset, coeffs, cpu_extensions);
+        });
+}
+
+/// Destination image is split into bands of rows. Every band uses
+/// only coefficients of its own rows, so result is equal to result
+/// of single-threaded convolution.
+#[cfg(feature = "rayon")]
+pub(crate) fn vert_convolution<P: Convolution>(
+    src_image: TypedImageView<P>,
+    mut dst_image: TypedImageViewMut<P>,
+    coeffs: CoefficientsView<P::Coefficient>,
+    cpu_extensions: CpuExtensions,
+) {
+    use rayon::prelude::*;
+    use std::num::NonZeroU32;
+
+    let dst_width = dst_image.width();
+    let dst_height = dst_image.height().get() as usize;
+    let band_height = band_size(dst_height, 1);
+    if band_height >= dst_height {
+        P::vert_convolution(src_image, dst_image, coeffs, cpu_extensions);
+        return;
+    }
+
+    dst_image
+        .rows_mut()
+        .par_chunks_mut(band_height)
+        .enumerate()
+        .for_each(|(i, rows)| {
+            let y_start = i * band_height;
+            let band_coeffs = coeffs.slice(y_start..y_start + rows.len());
+            let height = NonZeroU32::new(rows.len() as u32).unwrap();
+            let band = TypedImageViewMut::new(dst_width, height, rows);
+            P::vert_convolution(src_image.clone(), band, band_coeffs, cpu_extensions);
+        });
+}
+
+/// Returns size of bands that is enough to split `size` between all
+/// threads of current thread pool. Returned value is multiple of `align`.
+/// `size` must be greater than zero.
+#[cfg(feature = "rayon")]
+fn band_size(size: usize, align: usize) -> usize {
+    let threads = rayon::current_num_threads().max(1);
+    let aligned_blocks = (size - 1) / align + 1;
+    let blocks_per_band = (aligned_blocks - 1) / threads + 1;
+    blocks_per_band * align
+}
@@ -1,7 +1,8 @@
 use std::num::NonZeroU32;
 
 use fast_image_resize::{
-    CpuExtensions, Image, ImageRows, ImageRowsMut, ImageView, ImageViewMut, MulDiv, PixelType,
+    CpuExtensions, FilterType, GammaCorrection, Image, ImageRows, ImageRowsMut, ImageView,
+    ImageViewMut, MulDiv, PixelType, ResizeAlg, Resizer,
 };
 
 const fn p(r: u8, g: u8, b: u8, a: u8) -> u32 {
@@ -74,16 +75,44 @@ fn multiply_alpha_test(cpu_extensions: CpuExtensions) {
     }
 }
 
+#[cfg(target_arch = "x86_64")]
 #[test]
 fn multiply_alpha_avx2_test() {
     multiply_alpha_test(CpuExtensions::Avx2);
 }
 
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn multiply_alpha_sse4_test() {
+    multiply_alpha_test(CpuExtensions::Sse4_1);
+}
+
+#[cfg(target_arch = "x86_64")]
 #[test]
 fn multiply_alpha_sse2_test() {
     multiply_alpha_test(CpuExtensions::Sse2);
 }
 
+#[cfg(target_arch = "aarch64")]
+#[test]
+fn multiply_alpha_neon_test() {
+    multiply_alpha_test(CpuExtensions::Neon);
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+#[test]
+fn multiply_alpha_avx512_test() {
+    if is_avx512_supported() {
+        multiply_alpha_test(CpuExtensions::Avx512);
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+#[test]
+fn multiply_alpha_simd128_test() {
+    multiply_alpha_test(CpuExtensions::Simd128);
+}
+
 #[test]
 fn multiply_alpha_native_test() {
     multiply_alpha_test(CpuExtensions::None);
@@ -153,17 +182,670 @@ fn divide_alpha_test(cpu_extensions: CpuExtensions) {
     }
 }
 
+#[cfg(target_arch = "x86_64")]
 #[test]
 fn divide_alpha_avx2_test() {
     divide_alpha_test(CpuExtensions::Avx2);
 }
 
+#[cfg(target_arch = "x86_64")]
 #[test]
 fn divide_alpha_sse2_test() {
     divide_alpha_test(CpuExtensions::Sse2);
 }
 
+#[cfg(target_arch = "aarch64")]
+#[test]
+fn divide_alpha_neon_test() {
+    divide_alpha_test(CpuExtensions::Neon);
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+#[test]
+fn divide_alpha_avx512_test() {
+    if is_avx512_supported() {
+        divide_alpha_test(CpuExtensions::Avx512);
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+#[test]
+fn divide_alpha_simd128_test() {
+    divide_alpha_test(CpuExtensions::Simd128);
+}
+
 #[test]
 fn divide_alpha_native_test() {
     divide_alpha_test(CpuExtensions::None);
 }
+
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+fn is_avx512_supported() -> bool {
+    is_x86_feature_detected!("avx512f") && is_x86_feature_detected!("avx512bw")
+}
+
+#[cfg(any(
+    target_arch = "x86_64",
+    all(target_arch = "wasm32", target_feature = "simd128")
+))]
+/// Image with all combinations of values of color components and alpha.
+fn all_colors_and_alphas_image() -> Image<'static> {
+    let buffer: Vec<u8> = (0..=255u8)
+        .flat_map(|alpha| (0..=255u8).flat_map(move |c| [c, 255 - c, c / 2, alpha]))
+        .collect();
+    Image::from_vec_u8(
+        NonZeroU32::new(256).unwrap(),
+        NonZeroU32::new(256).unwrap(),
+        buffer,
+        PixelType::U8x4,
+    )
+    .unwrap()
+}
+
+#[cfg(any(
+    target_arch = "x86_64",
+    all(target_arch = "wasm32", target_feature = "simd128")
+))]
+fn multiply_alpha_is_equal_to_native(cpu_extensions: CpuExtensions) {
+    let mut native = all_colors_and_alphas_image();
+    let mut result = all_colors_and_alphas_image();
+
+    let mut mul_div = MulDiv::default();
+    for (cpu_extensions, image) in [
+        (CpuExtensions::None, &mut native),
+        (cpu_extensions, &mut result),
+    ] {
+        unsafe {
+            mul_div.set_cpu_extensions(cpu_extensions);
+        }
+        mul_div
+            .multiply_alpha_inplace(&mut image.view_mut())
+            .unwrap();
+    }
+    assert_eq!(result.buffer(), native.buffer());
+}
+
+#[cfg(any(
+    all(target_arch = "x86_64", feature = "avx512"),
+    all(target_arch = "wasm32", target_feature = "simd128")
+))]
+fn divide_alpha_is_equal_to_native(cpu_extensions: CpuExtensions) {
+    let mut native = all_colors_and_alphas_image();
+    let mut result = all_colors_and_alphas_image();
+
+    let mut mul_div = MulDiv::default();
+    for (cpu_extensions, image) in [
+        (CpuExtensions::None, &mut native),
+        (cpu_extensions, &mut result),
+    ] {
+        unsafe {
+            mul_div.set_cpu_extensions(cpu_extensions);
+        }
+        mul_div.divide_alpha_inplace(&mut image.view_mut()).unwrap();
+    }
+    assert_eq!(result.buffer(), native.buffer());
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn multiply_alpha_sse4_is_equal_to_native() {
+    multiply_alpha_is_equal_to_native(CpuExtensions::Sse4_1);
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+#[test]
+fn mul_div_alpha_avx512_is_equal_to_native() {
+    if is_avx512_supported() {
+        multiply_alpha_is_equal_to_native(CpuExtensions::Avx512);
+        divide_alpha_is_equal_to_native(CpuExtensions::Avx512);
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+#[test]
+fn mul_div_alpha_simd128_is_equal_to_native() {
+    multiply_alpha_is_equal_to_native(CpuExtensions::Simd128);
+    divide_alpha_is_equal_to_native(CpuExtensions::Simd128);
+}
+
+// Luma with alpha
+
+fn mul_div_alpha_u8x2_test(
+    cpu_extensions: CpuExtensions,
+    src_pixels: [[u8; 2]; 3],
+    res_pixels: [[u8; 2]; 3],
+    multiply: bool,
+) {
+    let width: u32 = 16 + 8 + 4 + 3;
+    let height: u32 = 3;
+
+    let mut src_buffer: Vec<u8> = src_pixels
+        .iter()
+        .flat_map(|pixel| pixel.repeat(width as usize))
+        .collect();
+    let src_image = Image::from_vec_u8(
+        NonZeroU32::new(width).unwrap(),
+        NonZeroU32::new(height).unwrap(),
+        src_buffer.clone(),
+        PixelType::U8x2,
+    )
+    .unwrap();
+    let mut dst_image = Image::new(
+        NonZeroU32::new(width).unwrap(),
+        NonZeroU32::new(height).unwrap(),
+        PixelType::U8x2,
+    );
+
+    let mut alpha_mul_div: MulDiv = Default::default();
+    unsafe {
+        alpha_mul_div.set_cpu_extensions(cpu_extensions);
+    }
+
+    let mut dst_view = dst_image.view_mut();
+    if multiply {
+        alpha_mul_div.multiply_alpha(&src_image.view(), &mut dst_view)
+    } else {
+        alpha_mul_div.divide_alpha(&src_image.view(), &mut dst_view)
+    }
+    .unwrap();
+
+    let valid_buffer: Vec<u8> = res_pixels
+        .iter()
+        .flat_map(|pixel| pixel.repeat(width as usize))
+        .collect();
+    assert_eq!(dst_image.buffer(), valid_buffer.as_slice());
+
+    // Inplace
+    let mut image_view = ImageViewMut::from_buffer(
+        NonZeroU32::new(width).unwrap(),
+        NonZeroU32::new(height).unwrap(),
+        &mut src_buffer,
+        PixelType::U8x2,
+    )
+    .unwrap();
+    if multiply {
+        alpha_mul_div.multiply_alpha_inplace(&mut image_view)
+    } else {
+        alpha_mul_div.divide_alpha_inplace(&mut image_view)
+    }
+    .unwrap();
+    assert_eq!(src_buffer, valid_buffer);
+}
+
+fn multiply_alpha_u8x2_test(cpu_extensions: CpuExtensions) {
+    let src_pixels = [[255, 128], [128, 255], [255, 0]];
+    let res_pixels = [[128, 128], [128, 255], [0, 0]];
+    mul_div_alpha_u8x2_test(cpu_extensions, src_pixels, res_pixels, true);
+}
+
+fn divide_alpha_u8x2_test(cpu_extensions: CpuExtensions) {
+    let src_pixels = [[64, 128], [128, 255], [255, 0]];
+    let res_pixels = [[127, 128], [128, 255], [0, 0]];
+    mul_div_alpha_u8x2_test(cpu_extensions, src_pixels, res_pixels, false);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn multiply_alpha_u8x2_avx2_test() {
+    multiply_alpha_u8x2_test(CpuExtensions::Avx2);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn multiply_alpha_u8x2_sse4_test() {
+    multiply_alpha_u8x2_test(CpuExtensions::Sse4_1);
+}
+
+#[test]
+fn multiply_alpha_u8x2_native_test() {
+    multiply_alpha_u8x2_test(CpuExtensions::None);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn divide_alpha_u8x2_avx2_test() {
+    divide_alpha_u8x2_test(CpuExtensions::Avx2);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn divide_alpha_u8x2_sse4_test() {
+    divide_alpha_u8x2_test(CpuExtensions::Sse4_1);
+}
+
+#[test]
+fn divide_alpha_u8x2_native_test() {
+    divide_alpha_u8x2_test(CpuExtensions::None);
+}
+
+// 16-bit RGBA
+
+fn u16x4_image(pixels: [[u16; 4]; 3], width: u32) -> Image<'static> {
+    let buffer = pixels
+        .iter()
+        .flat_map(|pixel| pixel.repeat(width as usize))
+        .flat_map(|c| c.to_ne_bytes())
+        .collect();
+    Image::from_vec_u8(
+        NonZeroU32::new(width).unwrap(),
+        NonZeroU32::new(3).unwrap(),
+        buffer,
+        PixelType::U16x4,
+    )
+    .unwrap()
+}
+
+#[test]
+fn multiply_alpha_u16x4_test() {
+    let width: u32 = 9;
+    let src_pixels = [
+        [0xffff, 0x8000, 0, 0x8000],
+        [0xffff, 0x8000, 0, 0xffff],
+        [0xffff, 0x8000, 0, 0],
+    ];
+    let res_pixels = [
+        [0x8000, 0x4000, 0, 0x8000],
+        [0xffff, 0x8000, 0, 0xffff],
+        [0, 0, 0, 0],
+    ];
+    let src_image = u16x4_image(src_pixels, width);
+    let valid_image = u16x4_image(res_pixels, width);
+    let mut dst_image = Image::new(src_image.width(), src_image.height(), PixelType::U16x4);
+
+    let alpha_mul_div: MulDiv = Default::default();
+    alpha_mul_div
+        .multiply_alpha(&src_image.view(), &mut dst_image.view_mut())
+        .unwrap();
+    assert_eq!(dst_image.buffer(), valid_image.buffer());
+
+    let mut image = u16x4_image(src_pixels, width);
+    alpha_mul_div
+        .multiply_alpha_inplace(&mut image.view_mut())
+        .unwrap();
+    assert_eq!(image.buffer(), valid_image.buffer());
+}
+
+#[test]
+fn divide_alpha_u16x4_test() {
+    let width: u32 = 9;
+    let src_pixels = [
+        [0x4000, 0x2000, 0, 0x8000],
+        [0xffff, 0x8000, 0, 0xffff],
+        [0xffff, 0x8000, 0, 0],
+    ];
+    let res_pixels = [
+        [0x8000, 0x4000, 0, 0x8000],
+        [0xffff, 0x8000, 0, 0xffff],
+        [0, 0, 0, 0],
+    ];
+    let src_image = u16x4_image(src_pixels, width);
+    let valid_image = u16x4_image(res_pixels, width);
+    let mut dst_image = Image::new(src_image.width(), src_image.height(), PixelType::U16x4);
+
+    let alpha_mul_div: MulDiv = Default::default();
+    alpha_mul_div
+        .divide_alpha(&src_image.view(), &mut dst_image.view_mut())
+        .unwrap();
+    assert_eq!(dst_image.buffer(), valid_image.buffer());
+
+    let mut image = u16x4_image(src_pixels, width);
+    alpha_mul_div
+        .divide_alpha_inplace(&mut image.view_mut())
+        .unwrap();
+    assert_eq!(image.buffer(), valid_image.buffer());
+}
+
+fn f32x4_image(pixels: [[f32; 4]; 3], width: u32) -> Image<'static> {
+    let buffer = pixels
+        .iter()
+        .flat_map(|pixel| pixel.repeat(width as usize))
+        .flat_map(|c| c.to_ne_bytes())
+        .collect();
+    Image::from_vec_u8(
+        NonZeroU32::new(width).unwrap(),
+        NonZeroU32::new(3).unwrap(),
+        buffer,
+        PixelType::F32x4,
+    )
+    .unwrap()
+}
+
+fn mul_div_alpha_f32x4_test(
+    cpu_extensions: CpuExtensions,
+    src_pixels: [[f32; 4]; 3],
+    res_pixels: [[f32; 4]; 3],
+    multiply: bool,
+) {
+    // Odd width checks processing of the last pixels in rows.
+    let width: u32 = 9;
+    let src_image = f32x4_image(src_pixels, width);
+    let valid_image = f32x4_image(res_pixels, width);
+    let mut dst_image = Image::new(src_image.width(), src_image.height(), PixelType::F32x4);
+
+    let mut alpha_mul_div: MulDiv = Default::default();
+    unsafe {
+        alpha_mul_div.set_cpu_extensions(cpu_extensions);
+    }
+    let mut image = f32x4_image(src_pixels, width);
+    if multiply {
+        alpha_mul_div
+            .multiply_alpha(&src_image.view(), &mut dst_image.view_mut())
+            .unwrap();
+        alpha_mul_div
+            .multiply_alpha_inplace(&mut image.view_mut())
+            .unwrap();
+    } else {
+        alpha_mul_div
+            .divide_alpha(&src_image.view(), &mut dst_image.view_mut())
+            .unwrap();
+        alpha_mul_div
+            .divide_alpha_inplace(&mut image.view_mut())
+            .unwrap();
+    }
+    assert_eq!(dst_image.buffer(), valid_image.buffer());
+    assert_eq!(image.buffer(), valid_image.buffer());
+}
+
+fn multiply_alpha_f32x4_test(cpu_extensions: CpuExtensions) {
+    // Color-channels with values greater than 1.0 are HDR values.
+    let src_pixels = [[1., 0.5, 2., 0.5], [1., 0.5, 2., 1.], [1., 0.5, 2., 0.]];
+    let res_pixels = [[0.5, 0.25, 1., 0.5], [1., 0.5, 2., 1.], [0., 0., 0., 0.]];
+    mul_div_alpha_f32x4_test(cpu_extensions, src_pixels, res_pixels, true);
+}
+
+fn divide_alpha_f32x4_test(cpu_extensions: CpuExtensions) {
+    let src_pixels = [
+        [0.25, 0.125, 1.5, 0.5],
+        [1., 0.5, 2., 1.],
+        [1., 0.5, 2., 0.],
+    ];
+    let res_pixels = [[0.5, 0.25, 3., 0.5], [1., 0.5, 2., 1.], [0., 0., 0., 0.]];
+    mul_div_alpha_f32x4_test(cpu_extensions, src_pixels, res_pixels, false);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn multiply_alpha_f32x4_avx2_test() {
+    multiply_alpha_f32x4_test(CpuExtensions::Avx2);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn multiply_alpha_f32x4_sse4_test() {
+    multiply_alpha_f32x4_test(CpuExtensions::Sse4_1);
+}
+
+#[test]
+fn multiply_alpha_f32x4_native_test() {
+    multiply_alpha_f32x4_test(CpuExtensions::None);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn divide_alpha_f32x4_avx2_test() {
+    divide_alpha_f32x4_test(CpuExtensions::Avx2);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn divide_alpha_f32x4_sse4_test() {
+    divide_alpha_f32x4_test(CpuExtensions::Sse4_1);
+}
+
+#[test]
+fn divide_alpha_f32x4_native_test() {
+    divide_alpha_f32x4_test(CpuExtensions::None);
+}
+
+#[test]
+fn multiply_alpha_in_sub_view() {
+    let mut image = Image::from_vec_u8(
+        NonZeroU32::new(4).unwrap(),
+        NonZeroU32::new(3).unwrap(),
+        [255, 255, 255, 128].repeat(12),
+        PixelType::U8x4,
+    )
+    .unwrap();
+    let size = NonZeroU32::new(2).unwrap();
+    let mut view = image.view_mut();
+    let mut sub_view = view.sub_view_mut(1, 1, size, size).unwrap();
+    MulDiv::default()
+        .multiply_alpha_inplace(&mut sub_view)
+        .unwrap();
+
+    for (i, pixel) in image.buffer().chunks(4).enumerate() {
+        let (x, y) = (i % 4, i / 4);
+        let inside = (1..3).contains(&x) && (1..3).contains(&y);
+        let expected = if inside {
+            [128, 128, 128, 128]
+        } else {
+            [255, 255, 255, 128]
+        };
+        assert_eq!(pixel, expected, "x = {}, y = {}", x, y);
+    }
+}
+
+// Multiplies by alpha inside of Resizer
+
+fn cpu_extensions_for_tests() -> Vec<CpuExtensions> {
+    #[allow(unused_mut)]
+    let mut extensions = vec![CpuExtensions::None];
+    #[cfg(target_arch = "x86_64")]
+    extensions.extend([CpuExtensions::Sse4_1, CpuExtensions::Avx2]);
+    extensions
+}
+
+/// Creates image with not premultiplied color-channels and
+/// with pixels of different transparency.
+fn straight_alpha_image(pixel_type: PixelType, width: u32, height: u32) -> Image<'static> {
+    let components = if pixel_type == PixelType::U8x2 { 2 } else { 4 };
+    let values: Vec<u8> = (0..(width * height) as usize * components)
+        .map(|i| {
+            let pixel = i / components;
+            if i % components == components - 1 {
+                [0, 255, 1, 128, 37, 200][pixel % 6]
+            } else {
+                ((i * 73 + pixel / 5 * 29) % 256) as u8
+            }
+        })
+        .collect();
+    let buffer = match pixel_type {
+        PixelType::U16x4 => values
+            .iter()
+            .flat_map(|&v| (v as u16 * 257).to_le_bytes())
+            .collect(),
+        PixelType::F32x4 => values
+            .iter()
+            .flat_map(|&v| (v as f32 / 255.).to_le_bytes())
+            .collect(),
+        _ => values,
+    };
+    Image::from_vec_u8(
+        NonZeroU32::new(width).unwrap(),
+        NonZeroU32::new(height).unwrap(),
+        buffer,
+        pixel_type,
+    )
+    .unwrap()
+}
+
+/// Returns components of pixels normalized into range `[0, 1]`.
+fn normalized_components(image: &Image) -> Vec<f64> {
+    let buffer = image.buffer();
+    match image.pixel_type() {
+        PixelType::U16x4 => buffer
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]) as f64 / 65535.)
+            .collect(),
+        PixelType::F32x4 => buffer
+            .chunks_exact(4)
+            .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]) as f64)
+            .collect(),
+        _ => buffer.iter().map(|&c| c as f64 / 255.).collect(),
+    }
+}
+
+/// Compares result of resizing with `Resizer::premultiply_alpha`
+/// with result of resizing of premultiplied image. The former is
+/// multiplied by alpha before comparison, because dividing by small
+/// alpha increases difference of rounding.
+fn resize_with_premultiply_alpha_test(pixel_type: PixelType, tolerance: f64) {
+    let src_image = straight_alpha_image(pixel_type, 67, 45);
+    let algorithms = [
+        ResizeAlg::Convolution(FilterType::Lanczos3),
+        ResizeAlg::SuperSampling(FilterType::Bilinear, 2),
+    ];
+    let sizes = [(31, 97), (31, 45), (67, 97), (131, 20), (13, 7)];
+
+    for cpu_extensions in cpu_extensions_for_tests() {
+        let mut mul_div = MulDiv::default();
+        unsafe {
+            mul_div.set_cpu_extensions(cpu_extensions);
+        }
+        let mut premultiplied = Image::new(src_image.width(), src_image.height(), pixel_type);
+        mul_div
+            .multiply_alpha(&src_image.view(), &mut premultiplied.view_mut())
+            .unwrap();
+
+        for &algorithm in algorithms.iter() {
+            for &(width, height) in sizes.iter() {
+                let width = NonZeroU32::new(width).unwrap();
+                let height = NonZeroU32::new(height).unwrap();
+                let mut resizer = Resizer::new(algorithm);
+                unsafe {
+                    resizer.set_cpu_extensions(cpu_extensions);
+                }
+
+                let mut expected = Image::new(width, height, pixel_type);
+                resizer
+                    .resize(&premultiplied.view(), &mut expected.view_mut())
+                    .unwrap();
+
+                resizer.premultiply_alpha = true;
+                let mut result = Image::new(width, height, pixel_type);
+                resizer
+                    .resize(&src_image.view(), &mut result.view_mut())
+                    .unwrap();
+                mul_div
+                    .multiply_alpha_inplace(&mut result.view_mut())
+                    .unwrap();
+
+                let mut expected = normalized_components(&expected);
+                let result = normalized_components(&result);
+                if pixel_type != PixelType::F32x4 {
+                    // Integer color-channels greater than alpha are clipped
+                    // by dividing by alpha.
+                    let components = if pixel_type == PixelType::U8x2 { 2 } else { 4 };
+                    for pixel in expected.chunks_exact_mut(components) {
+                        let (alpha, colors) = pixel.split_last_mut().unwrap();
+                        colors.iter_mut().for_each(|c| *c = c.min(*alpha));
+                    }
+                }
+                for (i, (&e, &r)) in expected.iter().zip(result.iter()).enumerate() {
+                    assert!(
+                        (e - r).abs() <= tolerance + 1e-9,
+                        "{:?}, {:?}, {:?}, {}x{}: component {}: {} != {}",
+                        pixel_type,
+                        cpu_extensions,
+                        algorithm,
+                        width,
+                        height,
+                        i,
+                        r,
+                        e
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn resize_with_premultiply_alpha_u8x2() {
+    resize_with_premultiply_alpha_test(PixelType::U8x2, 2. / 255.);
+}
+
+#[test]
+fn resize_with_premultiply_alpha_u8x4() {
+    resize_with_premultiply_alpha_test(PixelType::U8x4, 2. / 255.);
+}
+
+#[test]
+fn resize_with_premultiply_alpha_u16x4() {
+    resize_with_premultiply_alpha_test(PixelType::U16x4, 2. / 65535.);
+}
+
+#[test]
+fn resize_with_premultiply_alpha_f32x4() {
+    resize_with_premultiply_alpha_test(PixelType::F32x4, 1e-5);
+}
+
+#[test]
+fn premultiply_alpha_prevents_dark_halos() {
+    // Left half of source image is opaque white,
+    // right half is transparent black.
+    let width = NonZeroU32::new(64).unwrap();
+    let buffer: Vec<u8> = (0..64 * 64)
+        .flat_map(|i| {
+            if i % 64 < 32 {
+                [255, 255, 255, 255]
+            } else {
+                [0, 0, 0, 0]
+            }
+        })
+        .collect();
+    let src_image = Image::from_vec_u8(width, width, buffer, PixelType::U8x4).unwrap();
+    let dst_size = NonZeroU32::new(21).unwrap();
+
+    for &gamma_correction in [GammaCorrection::None, GammaCorrection::Srgb].iter() {
+        let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Lanczos3));
+        resizer.gamma_correction = gamma_correction;
+        resizer.premultiply_alpha = true;
+        let mut dst_image = Image::new(dst_size, dst_size, PixelType::U8x4);
+        resizer
+            .resize(&src_image.view(), &mut dst_image.view_mut())
+            .unwrap();
+
+        let mut has_translucent_pixels = false;
+        for pixel in dst_image.buffer().chunks_exact(4) {
+            match pixel[3] {
+                0 => assert_eq!(pixel, [0, 0, 0, 0], "{:?}", gamma_correction),
+                a => {
+                    has_translucent_pixels |= a < 255;
+                    // SIMD-versions of dividing by alpha may have error equal to 1.
+                    assert!(
+                        pixel[..3].iter().all(|&c| c >= 254),
+                        "{:?}",
+                        gamma_correction
+                    );
+                }
+            }
+        }
+        assert!(has_translucent_pixels);
+    }
+}
+
+#[test]
+fn premultiply_alpha_uses_buffers_for_bands_of_rows() {
+    let src_width = NonZeroU32::new(64).unwrap();
+    let src_height = NonZeroU32::new(4096).unwrap();
+    let src_image = Image::new(src_width, src_height, PixelType::U8x4);
+    let dst_width = NonZeroU32::new(32).unwrap();
+    let dst_height = NonZeroU32::new(1024).unwrap();
+    let mut dst_image = Image::new(dst_width, dst_height, PixelType::U8x4);
+
+    let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Lanczos3));
+    resizer.premultiply_alpha = true;
+    resizer
+        .resize(&src_image.view(), &mut dst_image.view_mut())
+        .unwrap();
+
+    // Horizontally resized copy of all rows of source image is not created.
+    let temp_image_size = (dst_width.get() * src_height.get()) as usize * 4;
+    let buffers_size = resizer.size_of_internal_buffers();
+    assert!(
+        buffers_size < temp_image_size / 4,
+        "size of buffers is {}",
+        buffers_size
+    );
+}
@@ -0,0 +1,239 @@
+use std::num::NonZeroU32;
+
+use fast_image_resize::color::{GammaConvertError, GammaConverter, TransferFunction};
+use fast_image_resize::{CpuExtensions, Image, PixelType};
+
+/// Returns image with one row that contains all values of `u8` in every channel.
+fn all_u8_values_image(pixel_type: PixelType, channels: usize) -> Image<'static> {
+    let buffer: Vec<u8> = (0..=255u8).flat_map(|v| vec![v; channels]).collect();
+    Image::from_vec_u8(
+        NonZeroU32::new(256).unwrap(),
+        NonZeroU32::new(1).unwrap(),
+        buffer,
+        pixel_type,
+    )
+    .unwrap()
+}
+
+fn convert(
+    src_image: &Image,
+    pixel_type: PixelType,
+    src_transfer: TransferFunction,
+    dst_transfer: TransferFunction,
+) -> Image<'static> {
+    let mut dst_image = Image::new(src_image.width(), src_image.height(), pixel_type);
+    let converter = GammaConverter::new(src_transfer, dst_transfer);
+    converter
+        .convert(&src_image.view(), &mut dst_image.view_mut())
+        .unwrap();
+    dst_image
+}
+
+fn components_f32(image: &Image) -> Vec<f32> {
+    image
+        .buffer()
+        .chunks_exact(4)
+        .map(|c| f32::from_ne_bytes([c[0], c[1], c[2], c[3]]))
+        .collect()
+}
+
+#[test]
+fn srgb_to_linear_f32() {
+    let src_image = all_u8_values_image(PixelType::U8x4, 4);
+    let dst_image = convert(
+        &src_image,
+        PixelType::F32x4,
+        TransferFunction::Srgb,
+        TransferFunction::Linear,
+    );
+    let components = components_f32(&dst_image);
+    // Reference values from the sRGB specification.
+    for (v, expected) in [
+        (0, 0.),
+        (10, 0.003035),
+        (128, 0.215861),
+        (188, 0.502886),
+        (255, 1.),
+    ] {
+        let pixel = &components[v * 4..v * 4 + 4];
+        for &c in &pixel[..3] {
+            assert!((c - expected).abs() < 1e-6, "{}: {} != {}", v, c, expected);
+        }
+        // Alpha-channel is only stretched.
+        assert_eq!(pixel[3], v as f32 / 255.);
+    }
+}
+
+#[test]
+fn u8_round_trip() {
+    let transfers = [
+        TransferFunction::Srgb,
+        TransferFunction::Gamma(2.2),
+        TransferFunction::Gamma(1.8),
+    ];
+    let cases = [
+        (PixelType::U8, 1, [PixelType::U16, PixelType::F32]),
+        (PixelType::U8x3, 3, [PixelType::U16x3, PixelType::F32x3]),
+        (PixelType::U8x4, 4, [PixelType::U16x4, PixelType::F32x4]),
+    ];
+    for transfer in transfers {
+        for (pixel_type, channels, linear_types) in cases {
+            let src_image = all_u8_values_image(pixel_type, channels);
+            for linear_type in linear_types {
+                let is_u16 = matches!(
+                    linear_type,
+                    PixelType::U16 | PixelType::U16x3 | PixelType::U16x4
+                );
+                if transfer != TransferFunction::Srgb && is_u16 {
+                    // 16 bits are not enough to store the darkest values
+                    // of pure gamma curves in linear light.
+                    continue;
+                }
+                let linear_image =
+                    convert(&src_image, linear_type, transfer, TransferFunction::Linear);
+                let res_image = convert(
+                    &linear_image,
+                    pixel_type,
+                    TransferFunction::Linear,
+                    transfer,
+                );
+                assert_eq!(
+                    src_image.buffer(),
+                    res_image.buffer(),
+                    "{:?}: {:?} -> {:?}",
+                    transfer,
+                    pixel_type,
+                    linear_type
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn srgb_to_gamma() {
+    let src_image = all_u8_values_image(PixelType::U8x2, 2);
+    let dst_image = convert(
+        &src_image,
+        PixelType::U8x2,
+        TransferFunction::Srgb,
+        TransferFunction::Gamma(2.2),
+    );
+    for (v, pixel) in dst_image.buffer().chunks_exact(2).enumerate() {
+        let linear = TransferFunction::Srgb.to_linear(v as f32 / 255.);
+        let expected = (TransferFunction::Gamma(2.2).from_linear(linear) * 255.).round();
+        assert_eq!(pixel[0] as f32, expected);
+        assert_eq!(pixel[1] as usize, v);
+    }
+}
+
+#[test]
+fn convert_errors() {
+    let width = NonZeroU32::new(4).unwrap();
+    let height = NonZeroU32::new(3).unwrap();
+    let converter = GammaConverter::new(TransferFunction::Srgb, TransferFunction::Linear);
+    let src_image = Image::new(width, height, PixelType::U8x4);
+
+    let mut dst_image = Image::new(width, height, PixelType::F32x3);
+    let res = converter.convert(&src_image.view(), &mut dst_image.view_mut());
+    assert!(matches!(
+        res,
+        Err(GammaConvertError::CountOfChannelsIsDifferent)
+    ));
+
+    let mut dst_image = Image::new(height, width, PixelType::U16x4);
+    let res = converter.convert(&src_image.view(), &mut dst_image.view_mut());
+    assert!(matches!(res, Err(GammaConvertError::SizeIsDifferent)));
+
+    let mut dst_image = Image::new(width, height, PixelType::I32);
+    let res = converter.convert(&src_image.view(), &mut dst_image.view_mut());
+    assert!(matches!(res, Err(GammaConvertError::UnsupportedPixelType)));
+}
+
+/// Returns image with pseudo-random components (floating point components
+/// are in the range `[-0.25, 1.25]`).
+fn random_image(width: u32, height: u32, pixel_type: PixelType) -> Image<'static> {
+    let mut state = 0x1234_5678u32;
+    let mut next = move || {
+        state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        state >> 8
+    };
+    let size = Image::new(
+        NonZeroU32::new(width).unwrap(),
+        NonZeroU32::new(height).unwrap(),
+        pixel_type,
+    )
+    .buffer()
+    .len();
+    let buffer: Vec<u8> = match pixel_type {
+        PixelType::F32 | PixelType::F32x3 | PixelType::F32x4 => (0..size / 4)
+            .flat_map(|_| {
+                let v = next() as f32 / (1 << 24) as f32 * 1.5 - 0.25;
+                v.to_ne_bytes()
+            })
+            .collect(),
+        _ => (0..size).map(|_| next() as u8).collect(),
+    };
+    Image::from_vec_u8(
+        NonZeroU32::new(width).unwrap(),
+        NonZeroU32::new(height).unwrap(),
+        buffer,
+        pixel_type,
+    )
+    .unwrap()
+}
+
+fn cpu_extensions_test(cpu_extensions: CpuExtensions) {
+    let cases = [
+        (PixelType::U8x4, PixelType::U16x4),
+        (PixelType::U16x4, PixelType::U8x4),
+        (PixelType::F32x4, PixelType::U8x4),
+        (PixelType::U8x3, PixelType::F32x3),
+        (PixelType::U16, PixelType::U16),
+        (PixelType::U8x2, PixelType::U8x2),
+    ];
+    let transfers = [
+        (TransferFunction::Srgb, TransferFunction::Linear),
+        (TransferFunction::Linear, TransferFunction::Srgb),
+        (TransferFunction::Gamma(2.2), TransferFunction::Srgb),
+    ];
+    for (src_type, dst_type) in cases {
+        // Width is not multiple of count of components processed at once.
+        let src_image = random_image(37, 5, src_type);
+        for (src_transfer, dst_transfer) in transfers {
+            let mut converter = GammaConverter::new(src_transfer, dst_transfer);
+            let mut expected_image = Image::new(src_image.width(), src_image.height(), dst_type);
+            unsafe { converter.set_cpu_extensions(CpuExtensions::None) };
+            converter
+                .convert(&src_image.view(), &mut expected_image.view_mut())
+                .unwrap();
+
+            let mut dst_image = Image::new(src_image.width(), src_image.height(), dst_type);
+            unsafe { converter.set_cpu_extensions(cpu_extensions) };
+            converter
+                .convert(&src_image.view(), &mut dst_image.view_mut())
+                .unwrap();
+            assert!(
+                dst_image.buffer() == expected_image.buffer(),
+                "{:?}: {:?} -> {:?}, {:?} -> {:?}",
+                cpu_extensions,
+                src_type,
+                dst_type,
+                src_transfer,
+                dst_transfer
+            );
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn convert_avx2_test() {
+    cpu_extensions_test(CpuExtensions::Avx2);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn convert_sse4_test() {
+    cpu_extensions_test(CpuExtensions::Sse4_1);
+}
@@ -0,0 +1,172 @@
+use fast_image_resize::FilterType;
+
+/// Checks values of filter function in given points.
+fn check_weights(filter_type: FilterType, expected: &[(f64, f64)]) {
+    for &(x, weight) in expected {
+        for x in [x, -x] {
+            let res = filter_type.weight(x);
+            assert!(
+                (res - weight).abs() < 1e-6,
+                "{:?}: weight({}) = {}, expected {}",
+                filter_type,
+                x,
+                res,
+                weight
+            );
+        }
+    }
+    let support = filter_type.support();
+    assert_eq!(filter_type.weight(support + 1e-9), 0., "{:?}", filter_type);
+}
+
+#[test]
+fn gaussian_weights() {
+    // Values of normal distribution with sigma = 0.5
+    let gaussian = FilterType::gaussian(0.5).unwrap();
+    check_weights(gaussian, &[(0., 0.797885), (0.5, 0.483941), (1., 0.107982)]);
+    assert_eq!(gaussian.support(), 2.);
+}
+
+#[test]
+fn gaussian_with_invalid_sigma() {
+    assert!(FilterType::gaussian(0.).is_err());
+    assert!(FilterType::gaussian(-1.).is_err());
+    assert!(FilterType::gaussian(f64::NAN).is_err());
+    assert!(FilterType::gaussian(f64::INFINITY).is_err());
+}
+
+#[test]
+fn lanczos_weights() {
+    check_weights(
+        FilterType::Lanczos2,
+        &[(0., 1.), (0.5, 0.573159), (1., 0.), (1.5, -0.063684)],
+    );
+    check_weights(
+        FilterType::Lanczos4,
+        &[
+            (0., 1.),
+            (0.5, 0.620383),
+            (1.5, -0.166415),
+            (2.5, 0.059909),
+            (3.5, -0.012661),
+        ],
+    );
+}
+
+#[test]
+fn cubic_weights() {
+    check_weights(
+        FilterType::BSpline,
+        &[
+            (0., 2. / 3.),
+            (0.5, 23. / 48.),
+            (1., 1. / 6.),
+            (1.5, 1. / 48.),
+        ],
+    );
+    check_weights(FilterType::Hermite, &[(0., 1.), (0.5, 0.5), (1., 0.)]);
+}
+
+#[test]
+fn spline_weights() {
+    // Taps of filters for the shift by half of pixel
+    check_weights(
+        FilterType::Spline16,
+        &[(0., 1.), (0.5, 0.575), (1., 0.), (1.5, -0.075)],
+    );
+    check_weights(
+        FilterType::Spline36,
+        &[
+            (0., 1.),
+            (0.5, 91. / 152.),
+            (1.5, -18. / 152.),
+            (2., 0.),
+            (2.5, 3. / 152.),
+        ],
+    );
+}
+
+#[test]
+fn blackman_weights() {
+    check_weights(
+        FilterType::Blackman,
+        &[(0., 1.), (0.5, 0.56851), (1.5, -0.07215), (2.5, 0.003436)],
+    );
+}
+
+#[test]
+fn magic_kernel_sharp_weights() {
+    // Values of kernels in integer points are equal to coefficients
+    // of sharpening step of Magic Kernel Sharp.
+    check_weights(
+        FilterType::MagicKernelSharp2013,
+        &[(0., 17. / 16.), (1., 0.), (2., -1. / 32.)],
+    );
+    check_weights(
+        FilterType::MagicKernelSharp2021,
+        &[
+            (0., 577. / 576.),
+            (1., 0.),
+            (2., 0.),
+            (3., 0.),
+            (4., -1. / 1152.),
+        ],
+    );
+}
+
+#[test]
+fn filters_are_continuous() {
+    let filters = [
+        FilterType::gaussian(0.5).unwrap(),
+        FilterType::Lanczos2,
+        FilterType::Lanczos4,
+        FilterType::BSpline,
+        FilterType::Hermite,
+        FilterType::Spline16,
+        FilterType::Spline36,
+        FilterType::Blackman,
+        FilterType::MagicKernelSharp2013,
+        FilterType::MagicKernelSharp2021,
+    ];
+    for filter_type in filters {
+        let support = filter_type.support();
+        let steps = (support * 1000.) as i32;
+        for i in 0..steps {
+            let x = i as f64 / 1000.;
+            let diff = (filter_type.weight(x + 1e-9) - filter_type.weight(x - 1e-9)).abs();
+            assert!(diff < 1e-6, "{:?}: break in {}", filter_type, x);
+        }
+    }
+}
+
+#[test]
+fn bicubic_weights() {
+    let known_filters = [
+        (1., 0., FilterType::BSpline),
+        (0., 0., FilterType::Hermite),
+        (0., 0.5, FilterType::CatmullRom),
+        (1. / 3., 1. / 3., FilterType::Mitchell),
+    ];
+    for (b, c, filter_type) in known_filters {
+        let bicubic = FilterType::bicubic(b, c).unwrap();
+        for i in -250..=250 {
+            let x = i as f64 / 100.;
+            let diff = (bicubic.weight(x) - filter_type.weight(x)).abs();
+            assert!(diff < 1e-9, "{:?} != {:?} in {}", bicubic, filter_type, x);
+        }
+    }
+
+    // Robidoux filter
+    let b = 12. / (19. + 9. * 2f64.sqrt());
+    let c = 113. / (58. + 216. * 2f64.sqrt());
+    check_weights(
+        FilterType::bicubic(b, c).unwrap(),
+        &[(0., 0.873928), (1., 0.063036), (2., 0.)],
+    );
+}
+
+#[test]
+fn bicubic_with_invalid_params() {
+    assert!(FilterType::bicubic(f64::NAN, 0.5).is_err());
+    assert!(FilterType::bicubic(0., f64::INFINITY).is_err());
+}
@@ -0,0 +1,281 @@
+use std::num::NonZeroU32;
+
+use fast_image_resize::{
+    CpuExtensions, FilterType, Image, MipmapBuilder, MipmapsError, PixelType, ResizeAlg, Resizer,
+};
+
+fn nonzero(v: u32) -> NonZeroU32 {
+    NonZeroU32::new(v).unwrap()
+}
+
+/// Returns image with pseudo-random values of pixels.
+fn noise_image(width: u32, height: u32, pixel_type: PixelType) -> Image<'static> {
+    let size = Image::new(nonzero(width), nonzero(height), pixel_type)
+        .buffer()
+        .len();
+    let mut v: u32 = 12345;
+    let buffer = (0..size)
+        .map(|_| {
+            v = v.wrapping_mul(1103515245).wrapping_add(12345);
+            (v >> 16) as u8
+        })
+        .collect();
+    Image::from_vec_u8(nonzero(width), nonzero(height), buffer, pixel_type).unwrap()
+}
+
+fn cpu_extensions_list() -> Vec<CpuExtensions> {
+    let mut list = vec![CpuExtensions::None];
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("sse4.1") {
+            list.push(CpuExtensions::Sse4_1);
+        }
+        if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+            list.push(CpuExtensions::Avx2);
+        }
+    }
+    list
+}
+
+#[test]
+fn level_sizes() {
+    let sizes = |width, height| -> Vec<(u32, u32)> {
+        MipmapBuilder::level_sizes(nonzero(width), nonzero(height))
+            .iter()
+            .map(|&(w, h)| (w.get(), h.get()))
+            .collect()
+    };
+    assert_eq!(sizes(10, 7), [(5, 3), (2, 1), (1, 1)]);
+    assert_eq!(sizes(8, 2), [(4, 1), (2, 1), (1, 1)]);
+    assert_eq!(sizes(1, 3), [(1, 1)]);
+    assert!(sizes(1, 1).is_empty());
+}
+
+#[test]
+fn box_reduction_u8x4() {
+    // Width of the first level is not multiple of count of pixels
+    // processed by SIMD at once.
+    let src_image = noise_image(70, 32, PixelType::U8x4);
+    let src = src_image.buffer();
+    let row_size = 70 * 4;
+    let expected: Vec<u8> = (0..16)
+        .flat_map(|y| (0..35 * 4).map(move |i| (y, i)))
+        .map(|(y, i)| {
+            let (x, c) = (i / 4, i % 4);
+            let s0 = 2 * y * row_size + 2 * x * 4 + c;
+            let s1 = s0 + row_size;
+            let sum = src[s0] as u32 + src[s0 + 4] as u32 + src[s1] as u32 + src[s1 + 4] as u32;
+            ((sum + 2) / 4) as u8
+        })
+        .collect();
+
+    for cpu_extensions in cpu_extensions_list() {
+        let mut builder = MipmapBuilder::new(FilterType::Box);
+        builder.premultiply_alpha = false;
+        unsafe {
+            builder.set_cpu_extensions(cpu_extensions);
+        }
+        let levels = builder.build(&src_image.view());
+        assert_eq!(levels.len(), 6);
+        assert_eq!(
+            levels[0].buffer(),
+            expected.as_slice(),
+            "{:?}",
+            cpu_extensions
+        );
+    }
+}
+
+#[test]
+fn box_reduction_of_other_pixels() {
+    let src_image = noise_image(6, 2, PixelType::U16x3);
+    let levels = MipmapBuilder::new(FilterType::Box).build(&src_image.view());
+    let src: Vec<u32> = src_image
+        .buffer()
+        .chunks_exact(2)
+        .map(|c| u16::from_ne_bytes([c[0], c[1]]) as u32)
+        .collect();
+    let dst: Vec<u32> = levels[0]
+        .buffer()
+        .chunks_exact(2)
+        .map(|c| u16::from_ne_bytes([c[0], c[1]]) as u32)
+        .collect();
+    for (i, &v) in dst.iter().enumerate() {
+        let (x, c) = (i / 3, i % 3);
+        let s0 = 2 * x * 3 + c;
+        let sum = src[s0] + src[s0 + 3] + src[s0 + 18] + src[s0 + 21];
+        assert_eq!(v, (sum + 2) / 4);
+    }
+
+    let src_image = Image::from_vec_u8(
+        nonzero(2),
+        nonzero(2),
+        [1f32, 2., 3., 6.]
+            .iter()
+            .flat_map(|v| v.to_ne_bytes())
+            .collect(),
+        PixelType::F32,
+    )
+    .unwrap();
+    let levels = MipmapBuilder::new(FilterType::Box).build(&src_image.view());
+    assert_eq!(levels[0].buffer(), 3f32.to_ne_bytes());
+}
+
+#[test]
+fn transparent_pixels_dont_bleed() {
+    // Transparent red pixel and three opaque green pixels.
+    let cases = [
+        (
+            PixelType::U8x4,
+            vec![255, 0, 0, 0, 0, 255, 0, 255, 0, 255, 0, 255, 0, 255, 0, 255],
+            vec![0, 255, 0, 191],
+            // Without multiplication by alpha red color bleeds from
+            // the transparent pixel.
+            vec![64, 191, 0, 191],
+        ),
+        (
+            PixelType::U8x2,
+            vec![255, 0, 0, 255, 0, 255, 0, 255],
+            vec![0, 191],
+            vec![64, 191],
+        ),
+    ];
+    // Division by alpha may truncate components.
+    let is_close = |a: &[u8], b: &[u8]| {
+        a.iter()
+            .zip(b)
+            .all(|(&a, &b)| (a as i16 - b as i16).abs() <= 1)
+    };
+    for (pixel_type, buffer, expected, expected_without_alpha) in cases {
+        let src_image = Image::from_vec_u8(nonzero(2), nonzero(2), buffer, pixel_type).unwrap();
+        for filter_type in [FilterType::Box, FilterType::Bilinear] {
+            for cpu_extensions in cpu_extensions_list() {
+                let mut builder = MipmapBuilder::new(filter_type);
+                unsafe {
+                    builder.set_cpu_extensions(cpu_extensions);
+                }
+                let levels = builder.build(&src_image.view());
+                assert!(
+                    is_close(levels[0].buffer(), &expected),
+                    "{:?} {:?} {:?}: {:?}",
+                    pixel_type,
+                    filter_type,
+                    cpu_extensions,
+                    levels[0].buffer()
+                );
+
+                builder.premultiply_alpha = false;
+                let levels = builder.build(&src_image.view());
+                assert!(
+                    is_close(levels[0].buffer(), &expected_without_alpha),
+                    "{:?} {:?} {:?}: {:?}",
+                    pixel_type,
+                    filter_type,
+                    cpu_extensions,
+                    levels[0].buffer()
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn levels_of_image_with_odd_size() {
+    let pixel_types = [
+        PixelType::U8,
+        PixelType::U8x4,
+        PixelType::U16x4,
+        PixelType::F32x3,
+    ];
+    let filters = [FilterType::Box, FilterType::Lanczos3];
+    for pixel_type in pixel_types {
+        for filter_type in filters {
+            // Every byte of buffer of uniform image is equal to 100.
+            let width = nonzero(13);
+            let height = nonzero(5);
+            let size = (13 * 5) as usize
+                * Image::new(nonzero(1), nonzero(1), pixel_type)
+                    .buffer()
+                    .len();
+            let src_image = Image::from_vec_u8(width, height, vec![100; size], pixel_type).unwrap();
+            let mut builder = MipmapBuilder::new(filter_type);
+            // Multiplication by alpha loses precision of integer components.
+            builder.premultiply_alpha = false;
+            let levels = builder.build(&src_image.view());
+            assert_eq!(levels.len(), 3);
+            for level in levels.iter() {
+                let expected_image = Image::from_vec_u8(
+                    level.width(),
+                    level.height(),
+                    vec![100; level.buffer().len()],
+                    pixel_type,
+                )
+                .unwrap();
+                // Floating point values may be slightly different.
+                let is_close = match pixel_type {
+                    PixelType::F32x3 => level
+                        .buffer()
+                        .chunks_exact(4)
+                        .zip(expected_image.buffer().chunks_exact(4))
+                        .all(|(a, b)| {
+                            let a = f32::from_ne_bytes([a[0], a[1], a[2], a[3]]);
+                            let b = f32::from_ne_bytes([b[0], b[1], b[2], b[3]]);
+                            (a - b).abs() <= b.abs() * 1e-5
+                        }),
+                    _ => level.buffer() == expected_image.buffer(),
+                };
+                assert!(is_close, "{:?} {:?}", pixel_type, filter_type);
+            }
+        }
+    }
+}
+
+#[test]
+fn levels_are_resized_by_resizer() {
+    let src_image = noise_image(33, 20, PixelType::U8x3);
+    let filter_type = FilterType::CatmullRom;
+    let levels = MipmapBuilder::new(filter_type).build(&src_image.view());
+    let mut resizer = Resizer::new(ResizeAlg::Convolution(filter_type));
+    let mut prev_image = src_image;
+    for level in levels {
+        let mut expected_image = Image::new(level.width(), level.height(), PixelType::U8x3);
+        resizer
+            .resize(&prev_image.view(), &mut expected_image.view_mut())
+            .unwrap();
+        assert_eq!(level.buffer(), expected_image.buffer());
+        prev_image = level;
+    }
+}
+
+#[test]
+fn build_into_views() {
+    let src_image = noise_image(20, 11, PixelType::U8x2);
+    let mut builder = MipmapBuilder::new(FilterType::Box);
+    let levels = builder.build(&src_image.view());
+
+    let mut dst_images: Vec<Image> =
+        MipmapBuilder::level_sizes(src_image.width(), src_image.height())
+            .into_iter()
+            .map(|(width, height)| Image::new(width, height, PixelType::U8x2))
+            .collect();
+    let mut dst_views: Vec<_> = dst_images.iter_mut().map(|img| img.view_mut()).collect();
+    builder
+        .build_into(&src_image.view(), &mut dst_views)
+        .unwrap();
+    drop(dst_views);
+    for (dst_image, level) in dst_images.iter().zip(levels.iter()) {
+        assert_eq!(dst_image.buffer(), level.buffer());
+    }
+
+    let mut dst_views: Vec<_> = dst_images.iter_mut().map(|img| img.view_mut()).collect();
+    let res = builder.build_into(&src_image.view(), &mut dst_views[1..]);
+    assert!(matches!(res, Err(MipmapsError::InvalidCountOfLevels)));
+    dst_views.swap(0, 1);
+    let res = builder.build_into(&src_image.view(), &mut dst_views);
+    assert!(matches!(res, Err(MipmapsError::InvalidLevelSize)));
+
+    let mut other_image = Image::new(nonzero(10), nonzero(5), PixelType::U8);
+    dst_views[0] = other_image.view_mut();
+    let res = builder.build_into(&src_image.view(), &mut dst_views);
+    assert!(matches!(res, Err(MipmapsError::DifferentTypesOfPixels)));
+}
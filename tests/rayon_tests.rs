@@ -0,0 +1,86 @@
+#![cfg(feature = "rayon")]
+use std::num::NonZeroU32;
+use std::sync::Arc;
+
+use fast_image_resize::{FilterType, Image, PixelType, ResizeAlg, Resizer};
+
+fn source_image(pixel_type: PixelType, width: u32, height: u32) -> Image<'static> {
+    let (pixel_size, is_float) = match pixel_type {
+        PixelType::U8 => (1, false),
+        PixelType::U8x3 => (3, false),
+        PixelType::U8x4 => (4, false),
+        PixelType::U16x3 => (6, false),
+        PixelType::F32 => (4, true),
+        PixelType::F32x4 => (16, true),
+        _ => unreachable!(),
+    };
+    let size = (width * height * pixel_size) as usize;
+    let buffer = if is_float {
+        (0..size / 4)
+            .flat_map(|i| ((i * 7 % 256) as f32 / 255.).to_ne_bytes())
+            .collect()
+    } else {
+        (0..size).map(|i| (i * 7 % 256) as u8).collect()
+    };
+    Image::from_vec_u8(
+        NonZeroU32::new(width).unwrap(),
+        NonZeroU32::new(height).unwrap(),
+        buffer,
+        pixel_type,
+    )
+    .unwrap()
+}
+
+fn resize(src_image: &Image, algorithm: ResizeAlg, threads: usize, size: (u32, u32)) -> Vec<u8> {
+    let thread_pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .unwrap();
+    let mut resizer = Resizer::new(algorithm);
+    resizer.set_thread_pool(Some(Arc::new(thread_pool)));
+    let mut dst_image = Image::new(
+        NonZeroU32::new(size.0).unwrap(),
+        NonZeroU32::new(size.1).unwrap(),
+        src_image.pixel_type(),
+    );
+    resizer
+        .resize(&src_image.view(), &mut dst_image.view_mut())
+        .unwrap();
+    dst_image.into_buffer()
+}
+
+#[test]
+fn multi_threaded_result_is_equal_to_single_threaded() {
+    let pixel_types = [
+        PixelType::U8,
+        PixelType::U8x3,
+        PixelType::U8x4,
+        PixelType::U16x3,
+        PixelType::F32,
+        PixelType::F32x4,
+    ];
+    let algorithms = [
+        ResizeAlg::Nearest,
+        ResizeAlg::Convolution(FilterType::Lanczos3),
+        ResizeAlg::SuperSampling(FilterType::Bilinear, 2),
+    ];
+    // Downscaling and upscaling
+    let sizes = [((257, 131), (67, 45)), ((37, 21), (203, 97))];
+    for pixel_type in pixel_types {
+        for &(src_size, dst_size) in sizes.iter() {
+            let src_image = source_image(pixel_type, src_size.0, src_size.1);
+            for algorithm in algorithms {
+                let single = resize(&src_image, algorithm, 1, dst_size);
+                let multi = resize(&src_image, algorithm, 7, dst_size);
+                assert!(
+                    single == multi,
+                    "{:?} {:?} {:?} -> {:?}",
+                    pixel_type,
+                    algorithm,
+                    src_size,
+                    dst_size
+                );
+            }
+        }
+    }
+}
@@ -6,8 +6,9 @@ use image::io::Reader as ImageReader;
 use image::{ColorType, GenericImageView};
 
 use fast_image_resize::{
-    CpuExtensions, DifferentTypesOfPixelsError, FilterType, Image, ImageView, PixelType, ResizeAlg,
-    Resizer,
+    CpuExtensions, CropBox, CropBoxError, DifferentTypesOfPixelsError, Filter, FilterType,
+    FloatOutputMode, GammaCorrection, Image, ImageBufferError, ImageView, ImageViewMut, MulDiv,
+    PixelType, ResizeAlg, Resizer,
 };
 
 fn get_source_image_u8x4() -> Image<'static> {
@@ -26,6 +27,38 @@ fn get_source_image_u8x4() -> Image<'static> {
     .unwrap()
 }
 
+fn get_source_image_u8x3() -> Image<'static> {
+    let img = ImageReader::open("./data/nasa-4928x3279.png")
+        .unwrap()
+        .decode()
+        .unwrap();
+    let width = img.width();
+    let height = img.height();
+    Image::from_vec_u8(
+        NonZeroU32::new(width).unwrap(),
+        NonZeroU32::new(height).unwrap(),
+        img.to_rgb8().into_raw(),
+        PixelType::U8x3,
+    )
+    .unwrap()
+}
+
+fn get_source_image_u8x2() -> Image<'static> {
+    let img = ImageReader::open("./data/nasa-4928x3279.png")
+        .unwrap()
+        .decode()
+        .unwrap();
+    let width = img.width();
+    let height = img.height();
+    Image::from_vec_u8(
+        NonZeroU32::new(width).unwrap(),
+        NonZeroU32::new(height).unwrap(),
+        img.to_luma_alpha8().into_raw(),
+        PixelType::U8x2,
+    )
+    .unwrap()
+}
+
 fn get_source_image_u8x1() -> Image<'static> {
     let img = ImageReader::open("./data/nasa-4928x3279.png")
         .unwrap()
@@ -70,6 +103,8 @@ fn save_result(image: &Image, name: &str) {
     std::fs::create_dir_all("./data/result").unwrap();
     let mut file = File::create(format!("./data/result/{}.png", name)).unwrap();
     let color_type = match image.pixel_type() {
+        PixelType::U8x2 => ColorType::La8,
+        PixelType::U8x3 => ColorType::Rgb8,
         PixelType::U8x4 => ColorType::Rgba8,
         PixelType::U8 => ColorType::L8,
         _ => panic!("Unsupported type of pixels"),
@@ -103,6 +138,7 @@ fn resize_wo_simd_lanczos3_test() {
     save_result(&result, "u8x4-lanczos3-native");
 }
 
+#[cfg(target_arch = "x86_64")]
 #[test]
 fn resize_sse4_lanczos3_test() {
     let image = get_source_image_u8x4();
@@ -122,6 +158,7 @@ fn resize_sse4_lanczos3_test() {
     save_result(&result, "u8x4-lanczos3-sse4");
 }
 
+#[cfg(target_arch = "x86_64")]
 #[test]
 fn resize_avx2_lanczos3_test() {
     let image = get_source_image_u8x4();
@@ -141,6 +178,7 @@ fn resize_avx2_lanczos3_test() {
     save_result(&result, "u8x4-lanczos3-avx2");
 }
 
+#[cfg(target_arch = "x86_64")]
 #[test]
 fn resize_avx2_lanczos3_upscale_test() {
     let image = get_small_source_image();
@@ -179,6 +217,7 @@ fn resize_nearest_test() {
     save_result(&result, "u8x4-nearest-native");
 }
 
+#[cfg(target_arch = "x86_64")]
 #[test]
 fn resize_super_sampling_test() {
     let image = get_source_image_u8x4();
@@ -254,3 +293,1303 @@ fn resize_lanczos3_u8x1() {
         .is_ok());
     save_result(&result, "u8x1-lanczos3-native");
 }
+
+fn resize_lanczos3_u8x3(cpu_extensions: CpuExtensions, dst_width: u32) -> Image<'static> {
+    let image = get_source_image_u8x3();
+    assert!(matches!(image.pixel_type(), PixelType::U8x3));
+
+    let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Lanczos3));
+    unsafe {
+        resizer.set_cpu_extensions(cpu_extensions);
+    }
+    let new_height = get_new_height(&image.view(), dst_width);
+    let mut result = Image::new(
+        NonZeroU32::new(dst_width).unwrap(),
+        NonZeroU32::new(new_height).unwrap(),
+        image.pixel_type(),
+    );
+    assert!(resizer
+        .resize(&image.view(), &mut result.view_mut())
+        .is_ok());
+    result
+}
+
+#[test]
+fn resize_lanczos3_u8x3_native() {
+    let result = resize_lanczos3_u8x3(CpuExtensions::None, NEW_WIDTH);
+    save_result(&result, "u8x3-lanczos3-native");
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn resize_lanczos3_u8x3_sse4() {
+    let result = resize_lanczos3_u8x3(CpuExtensions::Sse4_1, NEW_WIDTH);
+    save_result(&result, "u8x3-lanczos3-sse4");
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn resize_lanczos3_u8x3_avx2() {
+    let result = resize_lanczos3_u8x3(CpuExtensions::Avx2, NEW_WIDTH);
+    save_result(&result, "u8x3-lanczos3-avx2");
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn resize_u8x3_simd_is_equal_to_native() {
+    // Odd width checks processing of the last pixels in rows.
+    let dst_width = NEW_WIDTH + 2;
+    let native = resize_lanczos3_u8x3(CpuExtensions::None, dst_width);
+    for cpu_extensions in [CpuExtensions::Sse4_1, CpuExtensions::Avx2] {
+        let result = resize_lanczos3_u8x3(cpu_extensions, dst_width);
+        assert_eq!(result.buffer(), native.buffer());
+    }
+}
+
+fn resize_lanczos3_u8x2(cpu_extensions: CpuExtensions, dst_width: u32) -> Image<'static> {
+    let image = get_source_image_u8x2();
+    assert!(matches!(image.pixel_type(), PixelType::U8x2));
+
+    let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Lanczos3));
+    unsafe {
+        resizer.set_cpu_extensions(cpu_extensions);
+    }
+    let new_height = get_new_height(&image.view(), dst_width);
+    let mut result = Image::new(
+        NonZeroU32::new(dst_width).unwrap(),
+        NonZeroU32::new(new_height).unwrap(),
+        image.pixel_type(),
+    );
+    assert!(resizer
+        .resize(&image.view(), &mut result.view_mut())
+        .is_ok());
+    result
+}
+
+#[test]
+fn resize_lanczos3_u8x2_native() {
+    let result = resize_lanczos3_u8x2(CpuExtensions::None, NEW_WIDTH);
+    save_result(&result, "u8x2-lanczos3-native");
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn resize_lanczos3_u8x2_sse4() {
+    let result = resize_lanczos3_u8x2(CpuExtensions::Sse4_1, NEW_WIDTH);
+    save_result(&result, "u8x2-lanczos3-sse4");
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn resize_lanczos3_u8x2_avx2() {
+    let result = resize_lanczos3_u8x2(CpuExtensions::Avx2, NEW_WIDTH);
+    save_result(&result, "u8x2-lanczos3-avx2");
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn resize_u8x2_simd_is_equal_to_native() {
+    // Odd width checks processing of the last pixels in rows.
+    let dst_width = NEW_WIDTH + 2;
+    let native = resize_lanczos3_u8x2(CpuExtensions::None, dst_width);
+    for cpu_extensions in [CpuExtensions::Sse4_1, CpuExtensions::Avx2] {
+        let result = resize_lanczos3_u8x2(cpu_extensions, dst_width);
+        assert_eq!(result.buffer(), native.buffer());
+    }
+}
+
+fn resize_lanczos3(image: &Image, cpu_extensions: CpuExtensions, dst_width: u32) -> Image<'static> {
+    let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Lanczos3));
+    unsafe {
+        resizer.set_cpu_extensions(cpu_extensions);
+    }
+    let new_height = get_new_height(&image.view(), dst_width);
+    let mut result = Image::new(
+        NonZeroU32::new(dst_width).unwrap(),
+        NonZeroU32::new(new_height).unwrap(),
+        image.pixel_type(),
+    );
+    assert!(resizer
+        .resize(&image.view(), &mut result.view_mut())
+        .is_ok());
+    result
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn resize_u8x1_simd_is_equal_to_native() {
+    let image = get_source_image_u8x1();
+    // Odd widths and heights check processing of the last pixels and rows.
+    for dst_width in [NEW_WIDTH + 3, 1001] {
+        let native = resize_lanczos3(&image, CpuExtensions::None, dst_width);
+        for cpu_extensions in [CpuExtensions::Sse4_1, CpuExtensions::Avx2] {
+            let result = resize_lanczos3(&image, cpu_extensions, dst_width);
+            assert_eq!(result.buffer(), native.buffer());
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn resize_u8x4_simd_is_equal_to_native() {
+    let image = get_source_image_u8x4();
+    // Odd widths and heights check processing of the last pixels and rows.
+    for dst_width in [NEW_WIDTH + 3, 1001] {
+        let native = resize_lanczos3(&image, CpuExtensions::None, dst_width);
+        for cpu_extensions in [CpuExtensions::Sse4_1, CpuExtensions::Avx2] {
+            let result = resize_lanczos3(&image, cpu_extensions, dst_width);
+            assert_eq!(result.buffer(), native.buffer());
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn resize_cropped_u8x4_simd_is_equal_to_native() {
+    let image = get_small_source_image();
+    let mut src_view = image.view();
+    // Left side of crop box is not equal to 0, so windows of
+    // coefficients start from columns with non-zero offset.
+    src_view
+        .set_crop_box(CropBox {
+            left: 101.5,
+            top: 33.,
+            width: 601.,
+            height: 401.,
+        })
+        .unwrap();
+    let resize = |cpu_extensions: CpuExtensions, dst_width: u32| {
+        let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Lanczos3));
+        unsafe {
+            resizer.set_cpu_extensions(cpu_extensions);
+        }
+        let mut result = Image::new(
+            NonZeroU32::new(dst_width).unwrap(),
+            NonZeroU32::new(151).unwrap(),
+            PixelType::U8x4,
+        );
+        resizer.resize(&src_view, &mut result.view_mut()).unwrap();
+        result
+    };
+    // Different widths give windows of coefficients with odd and even sizes.
+    for dst_width in [113, 201, 257] {
+        let native = resize(CpuExtensions::None, dst_width);
+        for cpu_extensions in [CpuExtensions::Sse4_1, CpuExtensions::Avx2] {
+            let result = resize(cpu_extensions, dst_width);
+            assert_eq!(result.buffer(), native.buffer());
+        }
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+#[test]
+fn resize_neon_lanczos3_test() {
+    let image = get_source_image_u8x4();
+    let result = resize_lanczos3(&image, CpuExtensions::Neon, NEW_WIDTH);
+    save_result(&result, "u8x4-lanczos3-neon");
+}
+
+#[cfg(target_arch = "aarch64")]
+#[test]
+fn resize_neon_is_equal_to_native() {
+    // Odd width checks processing of the last pixels in rows.
+    let dst_width = NEW_WIDTH + 3;
+    for image in [get_source_image_u8x4(), get_source_image_u8x1()] {
+        let native = resize_lanczos3(&image, CpuExtensions::None, dst_width);
+        let result = resize_lanczos3(&image, CpuExtensions::Neon, dst_width);
+        assert_eq!(result.buffer(), native.buffer());
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+#[test]
+fn resize_simd128_lanczos3_test() {
+    let image = get_source_image_u8x4();
+    let result = resize_lanczos3(&image, CpuExtensions::Simd128, NEW_WIDTH);
+    save_result(&result, "u8x4-lanczos3-simd128");
+}
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+#[test]
+fn resize_simd128_is_equal_to_native() {
+    let image = get_source_image_u8x4();
+    // Odd widths and heights check processing of the last pixels and rows.
+    for dst_width in [NEW_WIDTH + 3, 1001] {
+        let native = resize_lanczos3(&image, CpuExtensions::None, dst_width);
+        let result = resize_lanczos3(&image, CpuExtensions::Simd128, dst_width);
+        assert_eq!(result.buffer(), native.buffer());
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+fn is_avx512_supported() -> bool {
+    is_x86_feature_detected!("avx512f") && is_x86_feature_detected!("avx512bw")
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+#[test]
+fn default_cpu_extensions_is_avx512() {
+    if is_avx512_supported() {
+        assert!(matches!(CpuExtensions::default(), CpuExtensions::Avx512));
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+#[test]
+fn resize_avx512_is_equal_to_native() {
+    if !is_avx512_supported() {
+        return;
+    }
+    let image = get_source_image_u8x4();
+    // Odd widths and heights check processing of the last pixels and rows.
+    for dst_width in [NEW_WIDTH + 3, 1001] {
+        let native = resize_lanczos3(&image, CpuExtensions::None, dst_width);
+        let result = resize_lanczos3(&image, CpuExtensions::Avx512, dst_width);
+        assert_eq!(result.buffer(), native.buffer());
+    }
+}
+
+/// Types of pixels without AVX-512 kernels must use AVX2 ones.
+#[cfg(all(target_arch = "x86_64", feature = "avx512"))]
+#[test]
+fn resize_avx512_falls_back_to_avx2() {
+    if !is_avx512_supported() {
+        return;
+    }
+    let dst_width = NEW_WIDTH + 3;
+    for image in [get_source_image_u8x3(), get_source_image_u8x1()] {
+        let avx2 = resize_lanczos3(&image, CpuExtensions::Avx2, dst_width);
+        let result = resize_lanczos3(&image, CpuExtensions::Avx512, dst_width);
+        assert_eq!(result.buffer(), avx2.buffer());
+    }
+
+    let image_u8 = get_source_image_u8x4();
+    let avx2 = resize_f32(
+        &image_u8,
+        PixelType::F32x4,
+        FilterType::Lanczos3,
+        CpuExtensions::Avx2,
+    );
+    let result = resize_f32(
+        &image_u8,
+        PixelType::F32x4,
+        FilterType::Lanczos3,
+        CpuExtensions::Avx512,
+    );
+    assert_eq!(result.buffer(), avx2.buffer());
+}
+
+/// Returns 16-bit version of the given 8-bit image.
+fn image_u8_to_u16(image_u8: &Image, pixel_type: PixelType) -> Image<'static> {
+    let buffer = image_u8
+        .buffer()
+        .iter()
+        .flat_map(|&c| (c as u16 * 257).to_ne_bytes())
+        .collect();
+    Image::from_vec_u8(image_u8.width(), image_u8.height(), buffer, pixel_type).unwrap()
+}
+
+#[test]
+fn resize_lanczos3_u16_is_close_to_u8() {
+    let types = [
+        (PixelType::U16, get_source_image_u8x1()),
+        (PixelType::U16x3, get_source_image_u8x3()),
+        (PixelType::U16x4, get_source_image_u8x4()),
+    ];
+    for (pixel_type, image_u8) in types {
+        let image_u16 = image_u8_to_u16(&image_u8, pixel_type);
+        let new_height = get_new_height(&image_u16.view(), NEW_WIDTH);
+        let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Lanczos3));
+
+        let mut result_u16 = Image::new(
+            NonZeroU32::new(NEW_WIDTH).unwrap(),
+            NonZeroU32::new(new_height).unwrap(),
+            pixel_type,
+        );
+        resizer
+            .resize(&image_u16.view(), &mut result_u16.view_mut())
+            .unwrap();
+
+        let mut result_u8 = Image::new(
+            NonZeroU32::new(NEW_WIDTH).unwrap(),
+            NonZeroU32::new(new_height).unwrap(),
+            image_u8.pixel_type(),
+        );
+        resizer
+            .resize(&image_u8.view(), &mut result_u8.view_mut())
+            .unwrap();
+
+        let components_u16 = result_u16
+            .buffer()
+            .chunks_exact(2)
+            .map(|c| u16::from_ne_bytes([c[0], c[1]]));
+        for (c16, &c8) in components_u16.zip(result_u8.buffer()) {
+            // 8-bit version of resizing loses precision of intermediate results.
+            let diff = (c16 as i32 - c8 as i32 * 257).abs();
+            assert!(diff <= 2 * 257, "{:?}: {} vs {}", pixel_type, c16, c8);
+        }
+    }
+}
+
+/// Returns floating point version of the given 8-bit image
+/// with components in the range [0.0, 1.0].
+fn image_u8_to_f32(image_u8: &Image, pixel_type: PixelType) -> Image<'static> {
+    let buffer = image_u8
+        .buffer()
+        .iter()
+        .flat_map(|&c| (c as f32 / 255.).to_ne_bytes())
+        .collect();
+    Image::from_vec_u8(image_u8.width(), image_u8.height(), buffer, pixel_type).unwrap()
+}
+
+fn image_components_f32(image: &Image) -> Vec<f32> {
+    image
+        .buffer()
+        .chunks_exact(4)
+        .map(|c| f32::from_ne_bytes([c[0], c[1], c[2], c[3]]))
+        .collect()
+}
+
+fn resize_f32(
+    image_u8: &Image,
+    pixel_type: PixelType,
+    filter_type: FilterType,
+    cpu_extensions: CpuExtensions,
+) -> Image<'static> {
+    let image_f32 = image_u8_to_f32(image_u8, pixel_type);
+    let new_height = get_new_height(&image_f32.view(), NEW_WIDTH);
+    let mut result = Image::new(
+        NonZeroU32::new(NEW_WIDTH).unwrap(),
+        NonZeroU32::new(new_height).unwrap(),
+        pixel_type,
+    );
+    let mut resizer = Resizer::new(ResizeAlg::Convolution(filter_type));
+    unsafe {
+        resizer.set_cpu_extensions(cpu_extensions);
+    }
+    resizer
+        .resize(&image_f32.view(), &mut result.view_mut())
+        .unwrap();
+    result
+}
+
+/// Bilinear filter is used because 8-bit version of resizing clips
+/// overshoots of filters with negative lobes after the horizontal pass.
+#[test]
+fn resize_bilinear_f32_is_close_to_u8() {
+    let types = [
+        (PixelType::F32x3, get_source_image_u8x3()),
+        (PixelType::F32x4, get_source_image_u8x4()),
+    ];
+    for (pixel_type, image_u8) in types {
+        let result_f32 = resize_f32(
+            &image_u8,
+            pixel_type,
+            FilterType::Bilinear,
+            CpuExtensions::None,
+        );
+
+        let mut result_u8 = Image::new(
+            result_f32.width(),
+            result_f32.height(),
+            image_u8.pixel_type(),
+        );
+        let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Bilinear));
+        resizer
+            .resize(&image_u8.view(), &mut result_u8.view_mut())
+            .unwrap();
+
+        let components_f32 = image_components_f32(&result_f32);
+        for (&c32, &c8) in components_f32.iter().zip(result_u8.buffer()) {
+            let c32 = c32 * 255.;
+            assert!(
+                (c32 - c8 as f32).abs() <= 1.,
+                "{:?}: {} vs {}",
+                pixel_type,
+                c32,
+                c8
+            );
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn resize_f32_simd_is_close_to_native() {
+    let types = [
+        (PixelType::F32, get_source_image_u8x1()),
+        (PixelType::F32x3, get_source_image_u8x3()),
+        (PixelType::F32x4, get_source_image_u8x4()),
+    ];
+    for (pixel_type, image_u8) in types {
+        let native = resize_f32(
+            &image_u8,
+            pixel_type,
+            FilterType::Lanczos3,
+            CpuExtensions::None,
+        );
+        let native = image_components_f32(&native);
+        for cpu_extensions in [CpuExtensions::Sse4_1, CpuExtensions::Avx2] {
+            let result = resize_f32(&image_u8, pixel_type, FilterType::Lanczos3, cpu_extensions);
+            let result = image_components_f32(&result);
+            for (&a, &b) in result.iter().zip(native.iter()) {
+                assert!(
+                    (a - b).abs() <= 1e-6,
+                    "{:?} {:?}: {} vs {}",
+                    pixel_type,
+                    cpu_extensions,
+                    a,
+                    b
+                );
+            }
+        }
+    }
+}
+
+fn resize_i32(image_u8: &Image, cpu_extensions: CpuExtensions) -> Vec<i32> {
+    // Big values of pixels check accumulation of sums with enough precision.
+    let buffer = image_u8
+        .buffer()
+        .iter()
+        .flat_map(|&c| (c as i32 * 1_000_000 - 100_000_000).to_ne_bytes())
+        .collect();
+    let image =
+        Image::from_vec_u8(image_u8.width(), image_u8.height(), buffer, PixelType::I32).unwrap();
+    // Odd width checks processing of the last pixels in rows.
+    let dst_width = NEW_WIDTH + 3;
+    let new_height = get_new_height(&image.view(), dst_width);
+    let mut result = Image::new(
+        NonZeroU32::new(dst_width).unwrap(),
+        NonZeroU32::new(new_height).unwrap(),
+        PixelType::I32,
+    );
+    let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Lanczos3));
+    unsafe {
+        resizer.set_cpu_extensions(cpu_extensions);
+    }
+    resizer
+        .resize(&image.view(), &mut result.view_mut())
+        .unwrap();
+    result
+        .buffer()
+        .chunks_exact(4)
+        .map(|c| i32::from_ne_bytes([c[0], c[1], c[2], c[3]]))
+        .collect()
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn resize_i32_simd_is_close_to_native() {
+    let image_u8 = get_source_image_u8x1();
+    let native = resize_i32(&image_u8, CpuExtensions::None);
+    for cpu_extensions in [CpuExtensions::Sse4_1, CpuExtensions::Avx2] {
+        let result = resize_i32(&image_u8, cpu_extensions);
+        for (&a, &b) in result.iter().zip(native.iter()) {
+            // Sums may be accumulated in other order.
+            assert!((a - b).abs() <= 1, "{:?}: {} vs {}", cpu_extensions, a, b);
+        }
+    }
+}
+
+/// Returns image with floating point pixels in which every component
+/// of pixel in column `x` is equal to `f(x)`.
+fn f32_columns_image(
+    pixel_type: PixelType,
+    width: u32,
+    height: u32,
+    f: impl Fn(u32) -> f32,
+) -> Image<'static> {
+    let components = match pixel_type {
+        PixelType::F32x3 => 3,
+        PixelType::F32x4 => 4,
+        _ => 1,
+    };
+    let buffer = (0..height)
+        .flat_map(|_| (0..width).flat_map(|x| vec![f(x); components]))
+        .flat_map(|c| c.to_ne_bytes())
+        .collect();
+    Image::from_vec_u8(
+        NonZeroU32::new(width).unwrap(),
+        NonZeroU32::new(height).unwrap(),
+        buffer,
+        pixel_type,
+    )
+    .unwrap()
+}
+
+fn resize_f32_columns(
+    src_image: &Image,
+    filter_type: FilterType,
+    float_output_mode: FloatOutputMode,
+) -> Vec<f32> {
+    let mut dst_image = Image::new(
+        NonZeroU32::new(src_image.width().get() / 2).unwrap(),
+        src_image.height(),
+        src_image.pixel_type(),
+    );
+    let mut resizer = Resizer::new(ResizeAlg::Convolution(filter_type));
+    resizer.float_output_mode = float_output_mode;
+    resizer
+        .resize(&src_image.view(), &mut dst_image.view_mut())
+        .unwrap();
+    image_components_f32(&dst_image)
+}
+
+const F32_PIXEL_TYPES: [PixelType; 3] = [PixelType::F32, PixelType::F32x3, PixelType::F32x4];
+
+#[test]
+fn resize_fractional_f32() {
+    for pixel_type in F32_PIXEL_TYPES {
+        // Linear gradient from 0.0 to 1.0
+        let src_image = f32_columns_image(pixel_type, 64, 8, |x| x as f32 / 63.);
+        let components = resize_f32_columns(&src_image, FilterType::Bilinear, Default::default());
+
+        let sum: f32 = components.iter().sum();
+        let mean = sum / components.len() as f32;
+        assert!(
+            (mean - 0.5).abs() < 1e-3,
+            "{:?}: mean is {}",
+            pixel_type,
+            mean
+        );
+        assert!(
+            components.iter().all(|&c| (0. ..=1.).contains(&c)),
+            "{:?}",
+            pixel_type
+        );
+        let fractional = components.iter().filter(|&&c| c.fract() != 0.).count();
+        assert!(
+            fractional > components.len() / 2,
+            "{:?}: results were rounded",
+            pixel_type
+        );
+    }
+}
+
+#[test]
+fn resize_f32_with_rounding() {
+    for pixel_type in F32_PIXEL_TYPES {
+        let src_image = f32_columns_image(pixel_type, 64, 8, |x| x as f32 / 7.);
+        let exact = resize_f32_columns(&src_image, FilterType::Bilinear, FloatOutputMode::Exact);
+        let rounded = resize_f32_columns(&src_image, FilterType::Bilinear, FloatOutputMode::Round);
+        for (&e, &r) in exact.iter().zip(rounded.iter()) {
+            assert_eq!(e.round(), r, "{:?}", pixel_type);
+        }
+    }
+}
+
+#[test]
+fn resize_f32_with_clamping() {
+    let mode = FloatOutputMode::Clamp { min: 0., max: 1. };
+    for pixel_type in F32_PIXEL_TYPES {
+        // Sharp edge produces overshoots of Lanczos3 filter.
+        let src_image = f32_columns_image(pixel_type, 64, 8, |x| if x < 32 { 0. } else { 1. });
+        let exact = resize_f32_columns(&src_image, FilterType::Lanczos3, FloatOutputMode::Exact);
+        assert!(
+            exact.iter().any(|&c| !(0. ..=1.).contains(&c)),
+            "{:?}",
+            pixel_type
+        );
+
+        let clamped = resize_f32_columns(&src_image, FilterType::Lanczos3, mode);
+        for (&e, &c) in exact.iter().zip(clamped.iter()) {
+            assert_eq!(e.clamp(0., 1.), c, "{:?}", pixel_type);
+        }
+    }
+}
+
+/// Copies rows of the image into buffer with padded rows.
+fn buffer_with_stride(image: &Image, stride: usize, padding: u8) -> Vec<u8> {
+    let row_size = image.buffer().len() / image.height().get() as usize;
+    image
+        .buffer()
+        .chunks(row_size)
+        .flat_map(|row| {
+            let mut row = row.to_vec();
+            row.resize(stride, padding);
+            row
+        })
+        .collect()
+}
+
+#[test]
+fn resize_buffers_with_stride() {
+    let width = NonZeroU32::new(16).unwrap();
+    let height = NonZeroU32::new(8).unwrap();
+    let buffer: Vec<u8> = (0..16 * 8 * 4).map(|i| (i * 7 % 256) as u8).collect();
+    let src_image = Image::from_vec_u8(width, height, buffer, PixelType::U8x4).unwrap();
+    let dst_width = NonZeroU32::new(7).unwrap();
+    let dst_height = NonZeroU32::new(5).unwrap();
+
+    let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Lanczos3));
+    let mut valid_image = Image::new(dst_width, dst_height, PixelType::U8x4);
+    resizer
+        .resize(&src_image.view(), &mut valid_image.view_mut())
+        .unwrap();
+
+    let src_stride = 16 * 4 + 12;
+    let src_buffer = buffer_with_stride(&src_image, src_stride, 0xaa);
+    let src_view =
+        ImageView::from_buffer_with_stride(width, height, &src_buffer, PixelType::U8x4, src_stride)
+            .unwrap();
+
+    let dst_stride = 7 * 4 + 8;
+    let mut dst_buffer = vec![0xbb; dst_stride * 5];
+    let mut dst_image = Image::from_slice_u8_with_stride(
+        dst_width,
+        dst_height,
+        &mut dst_buffer,
+        PixelType::U8x4,
+        dst_stride,
+    )
+    .unwrap();
+    resizer
+        .resize(&src_view, &mut dst_image.view_mut())
+        .unwrap();
+
+    assert_eq!(
+        dst_image.buffer(),
+        buffer_with_stride(&valid_image, dst_stride, 0xbb)
+    );
+}
+
+#[test]
+fn invalid_buffer_with_stride() {
+    let width = NonZeroU32::new(3).unwrap();
+    let height = NonZeroU32::new(2).unwrap();
+    let buffer = vec![0u8; 32];
+
+    let res = ImageView::from_buffer_with_stride(width, height, &buffer, PixelType::U16, 5);
+    assert!(matches!(res, Err(ImageBufferError::InvalidStride)));
+
+    let res = ImageView::from_buffer_with_stride(width, height, &buffer[..13], PixelType::U16, 7);
+    assert!(matches!(res, Err(ImageBufferError::InvalidBufferAlignment)));
+
+    // Last row may have no padding.
+    let res = ImageView::from_buffer_with_stride(width, height, &buffer[..22], PixelType::U16, 16);
+    assert!(res.is_ok());
+    let res = ImageView::from_buffer_with_stride(width, height, &buffer[..21], PixelType::U16, 16);
+    assert!(matches!(res, Err(ImageBufferError::InvalidBufferSize)));
+    let res = ImageView::from_buffer_with_stride(width, height, &buffer, PixelType::U16, 10);
+    assert!(matches!(res, Err(ImageBufferError::InvalidBufferSize)));
+
+    // Size of image with such stride overflows `usize`.
+    let height = NonZeroU32::new(3).unwrap();
+    let stride = 1 << (usize::BITS - 1);
+    let res = ImageView::from_buffer_with_stride(width, height, &buffer, PixelType::U16, stride);
+    assert!(matches!(res, Err(ImageBufferError::InvalidBufferSize)));
+    let mut buffer = buffer;
+    let res =
+        ImageViewMut::from_buffer_with_stride(width, height, &mut buffer, PixelType::U16, stride);
+    assert!(matches!(res, Err(ImageBufferError::InvalidBufferSize)));
+}
+
+/// Resizes image with pixels from `pixels` to half of its width.
+fn resize_u8_pixels_to_half_width(
+    pixels: Vec<u8>,
+    width: u32,
+    pixel_type: PixelType,
+    gamma_correction: GammaCorrection,
+) -> Image<'static> {
+    let height = NonZeroU32::new(2).unwrap();
+    let pixels: Vec<u8> = pixels.repeat(2);
+    let src_image =
+        Image::from_vec_u8(NonZeroU32::new(width).unwrap(), height, pixels, pixel_type).unwrap();
+    let mut dst_image = Image::new(NonZeroU32::new(width / 2).unwrap(), height, pixel_type);
+    let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Box));
+    resizer.gamma_correction = gamma_correction;
+    resizer
+        .resize(&src_image.view(), &mut dst_image.view_mut())
+        .unwrap();
+    dst_image
+}
+
+#[test]
+fn resize_black_and_white_columns_in_linear_light() {
+    for (pixel_type, components) in [(PixelType::U8, 1), (PixelType::U8x4, 4)] {
+        let pixels: Vec<u8> = (0..32)
+            .flat_map(|x| {
+                let v = if x % 2 == 0 { 0 } else { 255 };
+                let mut pixel = vec![v; components];
+                if components == 4 {
+                    pixel[3] = 255;
+                }
+                pixel
+            })
+            .collect();
+
+        let dst_image =
+            resize_u8_pixels_to_half_width(pixels.clone(), 32, pixel_type, GammaCorrection::None);
+        for pixel in dst_image.buffer().chunks(components) {
+            assert!((127..=128).contains(&pixel[0]), "{:?}", pixel_type);
+        }
+
+        // Average of black and white in linear light is 0.5,
+        // that is 188 in sRGB.
+        let dst_image =
+            resize_u8_pixels_to_half_width(pixels, 32, pixel_type, GammaCorrection::Srgb);
+        for pixel in dst_image.buffer().chunks(components) {
+            let expected = [188, 188, 188, 255];
+            assert_eq!(pixel, &expected[..components], "{:?}", pixel_type);
+        }
+    }
+}
+
+#[test]
+fn resize_uniform_image_in_linear_light() {
+    // Every pair of adjacent pixels has the same value.
+    let pixels: Vec<u8> = (0..=255u8).flat_map(|v| [v, v]).collect();
+    let dst_image =
+        resize_u8_pixels_to_half_width(pixels, 512, PixelType::U8, GammaCorrection::Srgb);
+    let expected: Vec<u8> = (0..=255u8).collect();
+    assert_eq!(dst_image.buffer(), expected.repeat(2));
+
+    let pixels: Vec<u8> = (0..=255u8)
+        .flat_map(|v| [v, 255 - v, v / 2, 255, v, 255 - v, v / 2, 255])
+        .collect();
+    let dst_image =
+        resize_u8_pixels_to_half_width(pixels, 512, PixelType::U8x4, GammaCorrection::Srgb);
+    let expected: Vec<u8> = (0..=255u8).flat_map(|v| [v, 255 - v, v / 2, 255]).collect();
+    assert_eq!(dst_image.buffer(), expected.repeat(2));
+}
+
+#[test]
+fn resize_premultiplied_image_in_linear_light() {
+    let mul_div = MulDiv::default();
+    for alpha in [64u8, 128, 200, 254] {
+        let pixels: Vec<u8> = [[20, 120, 240, alpha], [220, 120, 40, alpha]]
+            .iter()
+            .flatten()
+            .copied()
+            .collect::<Vec<u8>>()
+            .repeat(16);
+        let mut src_image = Image::from_vec_u8(
+            NonZeroU32::new(32).unwrap(),
+            NonZeroU32::new(1).unwrap(),
+            pixels,
+            PixelType::U8x4,
+        )
+        .unwrap();
+        mul_div
+            .multiply_alpha_inplace(&mut src_image.view_mut())
+            .unwrap();
+        let premultiplied = src_image.buffer().to_vec();
+
+        let mut dst_image = resize_u8_pixels_to_half_width(
+            premultiplied,
+            32,
+            PixelType::U8x4,
+            GammaCorrection::Srgb,
+        );
+        mul_div
+            .divide_alpha_inplace(&mut dst_image.view_mut())
+            .unwrap();
+
+        // Expected values are averages of components in linear light.
+        for pixel in dst_image.buffer().chunks(4) {
+            assert_eq!(pixel[3], alpha);
+            for (&c, expected) in pixel.iter().zip([162, 120, 178]) {
+                assert!(
+                    (c as i32 - expected).abs() <= 3,
+                    "alpha {}: {:?}",
+                    alpha,
+                    pixel
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn resize_cropped_image_in_linear_light() {
+    // Result of resizing must not depend on position of the crop box
+    // and pixels that are not used by convolution.
+    let (width, height) = (64u32, 48u32);
+    let padding = 16u32;
+    let pixels = |x: u32, y: u32| [(x * 7 + y * 3) as u8, (x * y) as u8, (x ^ y) as u8, 255];
+    let buffer: Vec<u8> = (0..height)
+        .flat_map(|y| (0..width).flat_map(move |x| pixels(x, y)))
+        .collect();
+    let image = Image::from_vec_u8(
+        NonZeroU32::new(width).unwrap(),
+        NonZeroU32::new(height).unwrap(),
+        buffer,
+        PixelType::U8x4,
+    )
+    .unwrap();
+    let padded_buffer: Vec<u8> = (0..height + 2 * padding)
+        .flat_map(|y| {
+            (0..width + 2 * padding).flat_map(move |x| {
+                if (padding..padding + width).contains(&x)
+                    && (padding..padding + height).contains(&y)
+                {
+                    pixels(x - padding, y - padding)
+                } else {
+                    [255, 0, 255, 255]
+                }
+            })
+        })
+        .collect();
+    let padded_image = Image::from_vec_u8(
+        NonZeroU32::new(width + 2 * padding).unwrap(),
+        NonZeroU32::new(height + 2 * padding).unwrap(),
+        padded_buffer,
+        PixelType::U8x4,
+    )
+    .unwrap();
+
+    let crop_box = CropBox {
+        left: 10.5,
+        top: 7.25,
+        width: 30.,
+        height: 20.,
+    };
+    let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Lanczos3));
+    resizer.gamma_correction = GammaCorrection::Srgb;
+    let mut resize = |image: &Image, offset: f64| {
+        let mut src_view = image.view();
+        src_view
+            .set_crop_box(CropBox {
+                left: crop_box.left + offset,
+                top: crop_box.top + offset,
+                ..crop_box
+            })
+            .unwrap();
+        let mut dst_image = Image::new(
+            NonZeroU32::new(15).unwrap(),
+            NonZeroU32::new(10).unwrap(),
+            PixelType::U8x4,
+        );
+        resizer
+            .resize(&src_view, &mut dst_image.view_mut())
+            .unwrap();
+        dst_image
+    };
+    let dst_image = resize(&image, 0.);
+    let padded_dst_image = resize(&padded_image, padding as f64);
+    assert_eq!(dst_image.buffer(), padded_dst_image.buffer());
+}
+
+fn custom_bilinear(x: f64) -> f64 {
+    (1. - x.abs()).max(0.)
+}
+
+#[test]
+fn resize_with_custom_filter() {
+    let filter = Filter::new("bilinear", custom_bilinear, 1.0).unwrap();
+    let image_u8x4 = get_small_source_image();
+    // Red channel of the source image
+    let buffer = image_u8x4.buffer().iter().step_by(4).copied().collect();
+    let image_u8 = Image::from_vec_u8(
+        image_u8x4.width(),
+        image_u8x4.height(),
+        buffer,
+        PixelType::U8,
+    )
+    .unwrap();
+    let image_f32 = image_u8_to_f32(&image_u8, PixelType::F32);
+    for src_image in [image_u8x4, image_u8, image_f32] {
+        let pixel_type = src_image.pixel_type();
+        let mut results = Vec::new();
+        for filter_type in [FilterType::Bilinear, FilterType::Custom(filter)] {
+            let mut dst_image = Image::new(
+                NonZeroU32::new(301).unwrap(),
+                NonZeroU32::new(199).unwrap(),
+                pixel_type,
+            );
+            let mut resizer = Resizer::new(ResizeAlg::Convolution(filter_type));
+            resizer
+                .resize(&src_image.view(), &mut dst_image.view_mut())
+                .unwrap();
+            results.push(dst_image.into_buffer());
+        }
+        assert_eq!(results[0], results[1], "{:?}", pixel_type);
+    }
+}
+
+#[test]
+fn custom_filter_with_invalid_support() {
+    for support in [0., -1., f64::NAN, f64::INFINITY] {
+        assert!(Filter::new("bilinear", custom_bilinear, support).is_err());
+    }
+}
+
+/// Gaussian function with standard deviation passed as parameter.
+fn custom_gaussian(x: f64, params: &[f64]) -> f64 {
+    let sigma = params[0];
+    if x.abs() < 4. * sigma {
+        (-x * x / (2. * sigma * sigma)).exp() / (sigma * (2. * std::f64::consts::PI).sqrt())
+    } else {
+        0.0
+    }
+}
+
+fn plain_gaussian_0_5(x: f64) -> f64 {
+    custom_gaussian(x, &[0.5])
+}
+
+fn plain_gaussian_1_5(x: f64) -> f64 {
+    custom_gaussian(x, &[1.5])
+}
+
+fn gaussian_filter(sigma: f64) -> Filter {
+    Filter::with_params("gaussian", custom_gaussian, &[sigma], 4. * sigma).unwrap()
+}
+
+#[test]
+fn resize_with_custom_filter_with_params() {
+    let src_image = get_small_source_image();
+    let dst_width = NonZeroU32::new(101).unwrap();
+    let dst_height = NonZeroU32::new(67).unwrap();
+    let resize = |resizer: &mut Resizer| {
+        let mut dst_image = Image::new(dst_width, dst_height, PixelType::U8x4);
+        resizer
+            .resize(&src_image.view(), &mut dst_image.view_mut())
+            .unwrap();
+        dst_image.into_buffer()
+    };
+
+    // Resizer must not reuse coefficients calculated for other parameters.
+    let mut custom_resizer = Resizer::new(ResizeAlg::Nearest);
+    let plain_funcs = [
+        (0.5, plain_gaussian_0_5 as fn(f64) -> f64),
+        (1.5, plain_gaussian_1_5),
+    ];
+    for (sigma, func) in plain_funcs {
+        let filter = Filter::new("plain_gaussian", func, 4. * sigma).unwrap();
+        let filter_type = FilterType::Custom(filter);
+        let expected = resize(&mut Resizer::new(ResizeAlg::Convolution(filter_type)));
+        let filter_type = FilterType::Custom(gaussian_filter(sigma));
+        custom_resizer.algorithm = ResizeAlg::Convolution(filter_type);
+        assert_eq!(resize(&mut custom_resizer), expected, "sigma = {}", sigma);
+    }
+}
+
+#[test]
+fn custom_filters_are_compared_by_params() {
+    assert_eq!(gaussian_filter(0.5), gaussian_filter(0.5));
+    assert_ne!(gaussian_filter(0.5), gaussian_filter(1.5));
+    assert_eq!(gaussian_filter(0.5).params(), [0.5]);
+}
+
+#[test]
+fn custom_filter_with_invalid_params() {
+    for sigma in [f64::NAN, f64::INFINITY] {
+        assert!(Filter::with_params("gaussian", custom_gaussian, &[sigma], 1.).is_err());
+    }
+    let params = [1.; Filter::MAX_PARAMS + 1];
+    assert!(Filter::with_params("gaussian", custom_gaussian, &params, 1.).is_err());
+    assert!(Filter::with_params("gaussian", custom_gaussian, &[1.], 0.).is_err());
+}
+
+#[test]
+fn resize_into_sub_view() {
+    let src_image = get_small_source_image();
+    let dst_width = NonZeroU32::new(100).unwrap();
+    let dst_height = NonZeroU32::new(67).unwrap();
+    let mut resizer = Resizer::new(ResizeAlg::Convolution(FilterType::Lanczos3));
+
+    let mut valid_image = Image::new(dst_width, dst_height, PixelType::U8x4);
+    resizer
+        .resize(&src_image.view(), &mut valid_image.view_mut())
+        .unwrap();
+
+    let canvas_width = 150;
+    let canvas_height = 90;
+    let buffer = vec![0xaa; canvas_width * canvas_height * 4];
+    let mut canvas = Image::from_vec_u8(
+        NonZeroU32::new(canvas_width as u32).unwrap(),
+        NonZeroU32::new(canvas_height as u32).unwrap(),
+        buffer,
+        PixelType::U8x4,
+    )
+    .unwrap();
+    let (region_left, region_top) = (30, 20);
+    let mut canvas_view = canvas.view_mut();
+    let mut dst_view = canvas_view
+        .sub_view_mut(region_left, region_top, dst_width, dst_height)
+        .unwrap();
+    assert_eq!(dst_view.width(), dst_width);
+    assert_eq!(dst_view.height(), dst_height);
+    resizer.resize(&src_image.view(), &mut dst_view).unwrap();
+
+    // Pixels outside the region are not changed.
+    let row_size = canvas_width * 4;
+    let valid_row_size = dst_width.get() as usize * 4;
+    let left = region_left as usize * 4;
+    let right = left + valid_row_size;
+    let rows = canvas.buffer().chunks(row_size).enumerate();
+    for (y, row) in rows {
+        let y = y as u32;
+        if y < region_top || y >= region_top + dst_height.get() {
+            assert!(row.iter().all(|&b| b == 0xaa), "row {}", y);
+            continue;
+        }
+        assert!(row[..left].iter().all(|&b| b == 0xaa), "row {}", y);
+        assert!(row[right..].iter().all(|&b| b == 0xaa), "row {}", y);
+        let valid_row_start = (y - region_top) as usize * valid_row_size;
+        let valid_row = &valid_image.buffer()[valid_row_start..valid_row_start + valid_row_size];
+        assert_eq!(&row[left..right], valid_row, "row {}", y);
+    }
+}
+
+#[test]
+fn sub_view_out_of_image() {
+    let mut image = Image::new(
+        NonZeroU32::new(10).unwrap(),
+        NonZeroU32::new(10).unwrap(),
+        PixelType::U8,
+    );
+    let mut view = image.view_mut();
+    let size = NonZeroU32::new(5).unwrap();
+    assert!(matches!(
+        view.sub_view_mut(10, 0, size, size),
+        Err(CropBoxError::PositionIsOutOfImageBoundaries)
+    ));
+    assert!(matches!(
+        view.sub_view_mut(6, 5, size, size),
+        Err(CropBoxError::SizeIsOutOfImageBoundaries)
+    ));
+    assert!(view.sub_view_mut(5, 5, size, size).is_ok());
+}
+
+#[test]
+fn resize_with_fractional_crop_box() {
+    // Every pixel contains its own position (the center of pixel `x` is `x + 0.5`).
+    let mut src_image = f32_columns_image(PixelType::F32, 64, 4, |x| x as f32 + 0.5);
+    let mut src_view = src_image.view();
+    let crop_box = CropBox {
+        left: 10.25,
+        top: 0.,
+        width: 20.,
+        height: 4.,
+    };
+    src_view.set_crop_box(crop_box).unwrap();
+
+    for filter_type in [FilterType::Bilinear, FilterType::CatmullRom] {
+        let mut dst_image = Image::new(
+            NonZeroU32::new(20).unwrap(),
+            NonZeroU32::new(4).unwrap(),
+            PixelType::F32,
+        );
+        let mut resizer = Resizer::new(ResizeAlg::Convolution(filter_type));
+        resizer
+            .resize(&src_view, &mut dst_image.view_mut())
+            .unwrap();
+        // Interpolation of linear function is exact.
+        for (x, &c) in image_components_f32(&dst_image).iter().take(20).enumerate() {
+            let expected = crop_box.left as f32 + x as f32 + 0.5;
+            assert!(
+                (c - expected).abs() < 1e-4,
+                "{:?}: {} != {}",
+                filter_type,
+                c,
+                expected
+            );
+        }
+    }
+
+    // Nearest pixels to centers of destination pixels
+    let mut dst_image = Image::new(
+        NonZeroU32::new(10).unwrap(),
+        NonZeroU32::new(4).unwrap(),
+        PixelType::F32,
+    );
+    let mut resizer = Resizer::new(ResizeAlg::Nearest);
+    resizer
+        .resize(&src_view, &mut dst_image.view_mut())
+        .unwrap();
+    let expected: Vec<f32> = (0..10).map(|x| 11.5 + 2. * x as f32).collect();
+    assert_eq!(&image_components_f32(&dst_image)[..10], expected.as_slice());
+
+    let res = src_view.set_crop_box(CropBox {
+        left: 0.5,
+        top: -0.5,
+        width: 10.,
+        height: 2.,
+    });
+    assert!(matches!(
+        res,
+        Err(CropBoxError::PositionIsOutOfImageBoundaries)
+    ));
+    let res = src_view.set_crop_box(CropBox {
+        left: 0.5,
+        top: 0.5,
+        width: 63.75,
+        height: 2.,
+    });
+    assert!(matches!(res, Err(CropBoxError::SizeIsOutOfImageBoundaries)));
+    let res = src_view.set_crop_box(CropBox {
+        left: 0.5,
+        top: 0.5,
+        width: f64::NAN,
+        height: 2.,
+    });
+    assert!(matches!(
+        res,
+        Err(CropBoxError::WidthOrHeightLessOrEqualToZero)
+    ));
+    src_image = f32_columns_image(PixelType::F32, 3, 1, |x| x as f32);
+    let mut src_view = src_image.view();
+    src_view.set_crop_box_to_fit_dst_size(
+        NonZeroU32::new(2).unwrap(),
+        NonZeroU32::new(1).unwrap(),
+        None,
+    );
+    // The crop box is not rounded.
+    assert_eq!(
+        src_view.crop_box(),
+        CropBox {
+            left: 0.5,
+            top: 0.,
+            width: 2.,
+            height: 1.,
+        }
+    );
+}
+
+#[test]
+fn reuse_cached_coefficients() {
+    let src_image = get_small_source_image();
+    let mut src_view = src_image.view();
+    // Alternating sizes are resized with coefficients from the cache too.
+    let sizes = [(200, 150), (300, 100), (200, 150), (200, 150), (300, 100)];
+    let crop_boxes = [
+        None,
+        None,
+        Some(CropBox {
+            left: 10.5,
+            top: 20.25,
+            width: 500.,
+            height: 300.,
+        }),
+        None,
+        None,
+    ];
+    let algorithms = [
+        ResizeAlg::Convolution(FilterType::Lanczos3),
+        ResizeAlg::Convolution(FilterType::Bilinear),
+        ResizeAlg::SuperSampling(FilterType::CatmullRom, 2),
+    ];
+
+    for algorithm in algorithms {
+        let mut resizer = Resizer::new(algorithm);
+        for (&(width, height), &crop_box) in sizes.iter().zip(crop_boxes.iter()) {
+            match crop_box {
+                Some(crop_box) => src_view.set_crop_box(crop_box).unwrap(),
+                None => src_view = src_image.view(),
+            }
+            let width = NonZeroU32::new(width).unwrap();
+            let height = NonZeroU32::new(height).unwrap();
+
+            let mut dst_image = Image::new(width, height, PixelType::U8x4);
+            resizer
+                .resize(&src_view, &mut dst_image.view_mut())
+                .unwrap();
+
+            // The result must be the same as the result of resizer
+            // without cached coefficients.
+            let mut expected_image = Image::new(width, height, PixelType::U8x4);
+            Resizer::new(algorithm)
+                .resize(&src_view, &mut expected_image.view_mut())
+                .unwrap();
+            assert!(
+                dst_image.buffer() == expected_image.buffer(),
+                "{:?}: {}x{}, {:?}",
+                algorithm,
+                width,
+                height,
+                crop_box
+            );
+        }
+        assert!(resizer.size_of_internal_buffers() > 0);
+        resizer.reset_internal_buffers();
+        assert_eq!(resizer.size_of_internal_buffers(), 0);
+    }
+}
+
+#[test]
+fn resize_into_many_images() {
+    let src_image = get_small_source_image();
+    let mut src_view = src_image.view();
+    src_view
+        .set_crop_box(CropBox {
+            left: 20.5,
+            top: 10.,
+            width: 800.,
+            height: 540.25,
+        })
+        .unwrap();
+    let sizes = [
+        (400, 270),
+        (200, 135),
+        (400, 100),
+        (400, 567),
+        (852, 300),
+        (200, 135),
+    ];
+    // Index of destination image which is the source of destination image
+    // (or `None` for the source image): the smallest image which is at least
+    // two times bigger in both dimensions.
+    let cascade_sources = [None, Some(0), Some(4), None, None, Some(0)];
+    let algorithms = [
+        ResizeAlg::Convolution(FilterType::Lanczos3),
+        ResizeAlg::Nearest,
+        ResizeAlg::SuperSampling(FilterType::Bilinear, 2),
+    ];
+    for algorithm in algorithms {
+        let mut dst_images: Vec<Image> = sizes
+            .iter()
+            .map(|&(width, height)| {
+                Image::new(
+                    NonZeroU32::new(width).unwrap(),
+                    NonZeroU32::new(height).unwrap(),
+                    PixelType::U8x4,
+                )
+            })
+            .collect();
+        let mut dst_views: Vec<_> = dst_images.iter_mut().map(|img| img.view_mut()).collect();
+        let mut resizer = Resizer::new(algorithm);
+        resizer.resize_many(&src_view, &mut dst_views).unwrap();
+        drop(dst_views);
+
+        for (dst_image, &source) in dst_images.iter().zip(cascade_sources.iter()) {
+            // Nearest neighbor algorithm always resizes the source image.
+            let source = source.filter(|_| !matches!(algorithm, ResizeAlg::Nearest));
+            let source_image = source.map(|i| &dst_images[i]);
+            let expected_src_view = match source_image {
+                Some(image) => image.view(),
+                None => src_view.clone(),
+            };
+            let mut expected_image =
+                Image::new(dst_image.width(), dst_image.height(), PixelType::U8x4);
+            Resizer::new(algorithm)
+                .resize(&expected_src_view, &mut expected_image.view_mut())
+                .unwrap();
+            assert!(
+                dst_image.buffer() == expected_image.buffer(),
+                "{:?}: {}x{} from {:?}",
+                algorithm,
+                dst_image.width(),
+                dst_image.height(),
+                source
+            );
+        }
+    }
+
+    let mut dst_images = [
+        Image::new(
+            NonZeroU32::new(10).unwrap(),
+            NonZeroU32::new(10).unwrap(),
+            PixelType::U8x4,
+        ),
+        Image::new(
+            NonZeroU32::new(10).unwrap(),
+            NonZeroU32::new(10).unwrap(),
+            PixelType::U8x3,
+        ),
+    ];
+    let mut dst_views: Vec<_> = dst_images.iter_mut().map(|img| img.view_mut()).collect();
+    let res = Resizer::new(ResizeAlg::Nearest).resize_many(&src_view, &mut dst_views);
+    assert!(matches!(res, Err(DifferentTypesOfPixelsError)));
+}
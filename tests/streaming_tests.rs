@@ -0,0 +1,150 @@
+use std::num::NonZeroU32;
+
+use image::io::Reader as ImageReader;
+use image::GenericImageView;
+
+use fast_image_resize::{
+    FilterType, Image, ImageView, PixelType, ResizeAlg, Resizer, StreamingResizeError,
+    StreamingResizer,
+};
+
+fn get_source_image(pixel_type: PixelType) -> Image<'static> {
+    let img = ImageReader::open("./data/nasa-852x567.png")
+        .unwrap()
+        .decode()
+        .unwrap();
+    let width = NonZeroU32::new(img.width()).unwrap();
+    let height = NonZeroU32::new(img.height()).unwrap();
+    let buffer = match pixel_type {
+        PixelType::U8 => img.to_luma8().into_raw(),
+        PixelType::U8x3 => img.to_rgb8().into_raw(),
+        PixelType::U8x4 => img.to_rgba8().into_raw(),
+        PixelType::U16x3 => img
+            .to_rgb16()
+            .into_raw()
+            .iter()
+            .flat_map(|c| c.to_ne_bytes())
+            .collect(),
+        PixelType::F32 => img
+            .to_luma8()
+            .into_raw()
+            .iter()
+            .flat_map(|&c| (c as f32).to_ne_bytes())
+            .collect(),
+        _ => unreachable!(),
+    };
+    Image::from_vec_u8(width, height, buffer, pixel_type).unwrap()
+}
+
+/// Pushes source image into streaming resizer by bands of `band_height` rows.
+fn resize_by_bands(
+    src_image: &Image,
+    dst_width: NonZeroU32,
+    dst_height: NonZeroU32,
+    filter_type: FilterType,
+    band_height: u32,
+) -> Vec<u8> {
+    let pixel_type = src_image.pixel_type();
+    let mut resizer = StreamingResizer::new(
+        src_image.width(),
+        src_image.height(),
+        dst_width,
+        dst_height,
+        pixel_type,
+        filter_type,
+    );
+    let row_size = src_image.stride();
+    let mut dst_buffer = Vec::new();
+    let mut next_row = 0;
+    for band in src_image.buffer().chunks(row_size * band_height as usize) {
+        let height = NonZeroU32::new((band.len() / row_size) as u32).unwrap();
+        let band_view =
+            ImageView::from_buffer(src_image.width(), height, band, pixel_type).unwrap();
+        resizer
+            .push_rows(&band_view, |y, row| {
+                assert_eq!(y, next_row);
+                next_row += 1;
+                dst_buffer.extend_from_slice(row);
+            })
+            .unwrap();
+    }
+    assert!(resizer.is_finished());
+    assert_eq!(resizer.pushed_rows(), src_image.height().get());
+    assert_eq!(resizer.finished_rows(), dst_height.get());
+    dst_buffer
+}
+
+#[test]
+fn streaming_resize_is_equal_to_resizer() {
+    let sizes = [(255, 170), (852, 300), (300, 567), (1000, 700)];
+    let pixel_types = [
+        PixelType::U8,
+        PixelType::U8x3,
+        PixelType::U8x4,
+        PixelType::U16x3,
+        PixelType::F32,
+    ];
+    for pixel_type in pixel_types {
+        let src_image = get_source_image(pixel_type);
+        for (width, height) in sizes {
+            let dst_width = NonZeroU32::new(width).unwrap();
+            let dst_height = NonZeroU32::new(height).unwrap();
+            let filter_type = FilterType::Lanczos3;
+
+            let mut expected_image = Image::new(dst_width, dst_height, pixel_type);
+            Resizer::new(ResizeAlg::Convolution(filter_type))
+                .resize(&src_image.view(), &mut expected_image.view_mut())
+                .unwrap();
+
+            for band_height in [1, 7] {
+                let buffer =
+                    resize_by_bands(&src_image, dst_width, dst_height, filter_type, band_height);
+                assert!(
+                    buffer == expected_image.buffer(),
+                    "{:?} {}x{} by bands of {} rows: result is not equal to result of Resizer",
+                    pixel_type,
+                    width,
+                    height,
+                    band_height
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn streaming_resize_errors() {
+    let width = NonZeroU32::new(8).unwrap();
+    let height = NonZeroU32::new(2).unwrap();
+    let mut resizer = StreamingResizer::new(
+        width,
+        height,
+        NonZeroU32::new(4).unwrap(),
+        NonZeroU32::new(1).unwrap(),
+        PixelType::U8,
+        FilterType::Bilinear,
+    );
+
+    let image = Image::new(width, height, PixelType::U8x2);
+    let res = resizer.push_rows(&image.view(), |_, _| {});
+    assert!(matches!(
+        res,
+        Err(StreamingResizeError::DifferentTypesOfPixels)
+    ));
+
+    let image = Image::new(height, height, PixelType::U8);
+    let res = resizer.push_rows(&image.view(), |_, _| {});
+    assert!(matches!(res, Err(StreamingResizeError::InvalidRowsWidth)));
+
+    let image = Image::new(width, NonZeroU32::new(3).unwrap(), PixelType::U8);
+    let res = resizer.push_rows(&image.view(), |_, _| {});
+    assert!(matches!(res, Err(StreamingResizeError::TooManyRows)));
+
+    let image = Image::new(width, height, PixelType::U8);
+    let mut count = 0;
+    resizer.push_rows(&image.view(), |_, _| count += 1).unwrap();
+    assert_eq!(count, 1);
+    assert!(resizer.is_finished());
+    let res = resizer.push_rows(&image.view(), |_, _| {});
+    assert!(matches!(res, Err(StreamingResizeError::TooManyRows)));
+}